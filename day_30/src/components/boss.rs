@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// 頭目招式腳本中的一招，由 `assets/enemies/` 的 `phases` 設定
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum BossAttack {
+    /// 朝玩家發射一顆魔法彈
+    Bolt,
+    /// 以玩家方向為中心呈扇形齊射
+    Spread { count: u32, angle_degrees: f32 },
+    /// 會緩慢轉向追蹤玩家的法球
+    HomingOrb { count: u32, turn_rate_degrees: f32 },
+    /// 瞬移到玩家的另一側
+    Blink { distance: f32 },
+    /// 在身旁召喚手下
+    Summon { archetype_id: String, count: u32 },
+}
+
+impl BossAttack {
+    /// 施法前預兆光圈的顏色，讓玩家分辨接下來的招式
+    pub fn telegraph_color(&self) -> Color {
+        match self {
+            BossAttack::Bolt => Color::srgba(0.72, 0.28, 0.92, 0.45),
+            BossAttack::Spread { .. } => Color::srgba(0.95, 0.35, 0.75, 0.5),
+            BossAttack::HomingOrb { .. } => Color::srgba(0.35, 0.85, 1.0, 0.5),
+            BossAttack::Blink { .. } => Color::srgba(1.0, 1.0, 1.0, 0.55),
+            BossAttack::Summon { .. } => Color::srgba(0.4, 0.95, 0.4, 0.5),
+        }
+    }
+}
+
+/// 頭目的一個階段；生命比例降到 `health_fraction` 以下時切換
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossPhase {
+    pub name: String,
+    pub health_fraction: f32,
+    /// 依序循環施放的招式
+    pub pattern: Vec<BossAttack>,
+    pub cooldown_seconds: f32,
+    pub telegraph_seconds: f32,
+    #[serde(default = "default_phase_multiplier")]
+    pub speed_multiplier: f32,
+    #[serde(default = "default_phase_multiplier")]
+    pub damage_multiplier: f32,
+    /// 狂暴階段會讓頭目染上紅色
+    #[serde(default)]
+    pub enraged: bool,
+}
+
+fn default_phase_multiplier() -> f32 {
+    1.0
+}
+
+/// 依生命比例切換階段、照腳本施放招式的頭目
+#[derive(Component, Debug, Clone)]
+pub struct BossScript {
+    pub phases: Vec<BossPhase>,
+    pub phase: usize,
+    pub step: usize,
+    pub cooldown: Timer,
+    /// 正在預兆中的招式與預兆光圈實體
+    pub telegraph: Option<(Timer, Entity)>,
+}
+
+impl BossScript {
+    pub fn new(phases: Vec<BossPhase>) -> Self {
+        let mut script = Self {
+            phases,
+            phase: 0,
+            step: 0,
+            cooldown: Timer::default(),
+            telegraph: None,
+        };
+        script.enter_phase(0);
+        script
+    }
+
+    pub fn current_phase(&self) -> &BossPhase {
+        &self.phases[self.phase]
+    }
+
+    pub fn next_attack(&self) -> &BossAttack {
+        let pattern = &self.current_phase().pattern;
+        &pattern[self.step % pattern.len()]
+    }
+
+    /// 依目前生命比例應處於的階段，只會往後推進
+    pub fn phase_for_health(&self, fraction: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| fraction <= phase.health_fraction)
+            .unwrap_or(0)
+            .max(self.phase)
+    }
+
+    /// 進入新階段，回傳被取消的預兆光圈
+    pub fn enter_phase(&mut self, index: usize) -> Option<Entity> {
+        self.phase = index;
+        self.step = 0;
+        self.cooldown = Timer::from_seconds(self.current_phase().cooldown_seconds, TimerMode::Once);
+        self.telegraph.take().map(|(_, visual)| visual)
+    }
+
+    /// 招式施放完畢，換下一招並重新冷卻
+    pub fn advance(&mut self) {
+        self.step += 1;
+        self.cooldown.reset();
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        self.current_phase().speed_multiplier
+    }
+
+    pub fn enraged(&self) -> bool {
+        self.current_phase().enraged
+    }
+}
+
+/// 頭目召喚出的手下：不給經驗與掉落，頭目倒下時一起消散
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BossMinion {
+    pub boss: Entity,
+}
+
+/// 頭目施法前腳下的預兆光圈
+#[derive(Component)]
+pub struct BossTelegraph;
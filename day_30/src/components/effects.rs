@@ -50,3 +50,48 @@ impl DeathParticle {
         }
     }
 }
+
+/// 浮動戰鬥文字的種類，決定顏色與字級
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatTextKind {
+    DamageDealt,
+    CriticalHit,
+    DamageTaken,
+    Poison,
+    Burn,
+    Heal,
+    Experience,
+    /// 頭目進入新階段時顯示的階段名稱
+    BossPhase,
+}
+
+impl CombatTextKind {
+    pub fn color(self) -> Color {
+        match self {
+            CombatTextKind::DamageDealt => Color::srgb(1.0, 0.95, 0.85),
+            CombatTextKind::CriticalHit => Color::srgb(1.0, 0.78, 0.2),
+            CombatTextKind::DamageTaken => Color::srgb(0.95, 0.3, 0.28),
+            CombatTextKind::Poison => Color::srgb(0.55, 0.9, 0.35),
+            CombatTextKind::Burn => Color::srgb(1.0, 0.55, 0.2),
+            CombatTextKind::Heal => Color::srgb(0.4, 0.95, 0.6),
+            CombatTextKind::Experience => Color::srgb(0.55, 0.75, 1.0),
+            CombatTextKind::BossPhase => Color::srgb(0.85, 0.5, 1.0),
+        }
+    }
+
+    pub fn font_scale(self) -> f32 {
+        match self {
+            CombatTextKind::CriticalHit | CombatTextKind::BossPhase => 1.4,
+            CombatTextKind::Poison | CombatTextKind::Burn => 0.85,
+            _ => 1.0,
+        }
+    }
+}
+
+/// 上升並淡出的戰鬥文字；播完後隱藏，等待下一次重複使用
+#[derive(Component)]
+pub struct CombatText {
+    pub kind: CombatTextKind,
+    pub timer: Timer,
+    pub active: bool,
+}
@@ -1,52 +1,86 @@
+use crate::constants::{ENEMY_INVESTIGATE_SECONDS, ENEMY_REPATH_SECONDS};
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[derive(Component)]
 pub struct Enemy;
 
+/// 敵人對應的資料 id，詳細數值從 `EnemyArchetypeRegistry` 查詢
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct EnemyArchetypeId(pub String);
+
+/// 巡邏並追擊玩家的 AI
 #[derive(Component)]
-pub struct Slime;
+pub struct ChaserAi;
 
+/// 蓄力衝撞的 AI
 #[derive(Component)]
-pub struct Cyclops;
+pub struct ChargerAi;
 
+/// 在走道上移動並吐蛛網的 AI
 #[derive(Component)]
-pub struct Spider;
+pub struct WebShooterAi;
 
+/// 保持距離施法的 AI
 #[derive(Component)]
-pub struct Mimic;
+pub struct SpellCasterAi;
 
+/// 靠近玩家時造成接觸傷害
 #[derive(Component)]
-pub struct BossWizard;
+pub struct ContactAttacker;
 
+/// 敵人身上的附屬圖像（例如法杖）
 #[derive(Component)]
-pub struct BossWizardStaff;
+pub struct EnemyAccessoryVisual;
 
 #[derive(Component)]
 pub struct BossWizardProjectile {
-    pub velocity: Vec2,
     pub damage: i32,
 }
 
-#[derive(Component)]
-pub struct BossWizardProjectileLifetime {
-    pub timer: Timer,
-}
-
 #[derive(Component)]
 pub struct SpiderWebProjectile {
-    pub velocity: Vec2,
     pub damage: i32,
     pub direction: Vec2,
 }
 
-#[derive(Component)]
-pub struct SpiderWebProjectileLifetime {
-    pub timer: Timer,
-}
-
 #[derive(Component)]
 pub struct EnemyAIState {
     pub state: EnemyBehaviorState,
+    /// 最後一次看到玩家的位置，跟丟時前往查看
+    pub last_known_position: Option<Vec2>,
+    pub investigate: Timer,
+}
+
+impl EnemyAIState {
+    pub fn new(state: EnemyBehaviorState) -> Self {
+        Self {
+            state,
+            last_known_position: None,
+            investigate: Timer::from_seconds(ENEMY_INVESTIGATE_SECONDS, TimerMode::Once),
+        }
+    }
+
+    /// 看見玩家：記下位置並開始追擊
+    pub fn spot(&mut self, position: Vec2) {
+        self.state = EnemyBehaviorState::Chasing;
+        self.last_known_position = Some(position);
+    }
+
+    /// 跟丟玩家：沒有記下位置時直接回去巡邏
+    pub fn lose_sight(&mut self) {
+        if self.last_known_position.is_some() {
+            self.state = EnemyBehaviorState::Investigating;
+            self.investigate.reset();
+        } else {
+            self.state = EnemyBehaviorState::Patrolling;
+        }
+    }
+
+    pub fn give_up(&mut self) {
+        self.state = EnemyBehaviorState::Patrolling;
+        self.last_known_position = None;
+    }
 }
 
 #[derive(Component)]
@@ -54,12 +88,14 @@ pub struct EnemyDeathEffect {
     pub timer: Timer,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EnemyBehaviorState {
     Patrolling,
     Chasing,
     WindUp,
     Charging,
+    /// 跟丟玩家後前往最後看到的位置查看
+    Investigating,
 }
 
 #[derive(Component)]
@@ -73,6 +109,53 @@ impl EnemyPatrol {
     pub fn bounds(&self) -> (f32, f32) {
         (self.origin.x - self.range, self.origin.x + self.range)
     }
+
+    /// 放棄追擊後朝巡邏起點的方向走回去
+    pub fn face_origin(&mut self, x: f32) {
+        self.direction = if x >= self.origin.x { -1.0 } else { 1.0 };
+    }
+}
+
+/// 依序走訪的巡邏路徑點；沒有這個元件的敵人只在 `EnemyPatrol` 的水平範圍內來回
+#[derive(Component, Debug, Clone)]
+pub struct EnemyPatrolRoute {
+    pub waypoints: Vec<Vec2>,
+    pub index: usize,
+}
+
+impl EnemyPatrolRoute {
+    pub fn new(waypoints: Vec<Vec2>) -> Self {
+        Self {
+            waypoints,
+            index: 0,
+        }
+    }
+
+    pub fn target(&self) -> Vec2 {
+        self.waypoints[self.index % self.waypoints.len()]
+    }
+
+    pub fn advance(&mut self) {
+        self.index = (self.index + 1) % self.waypoints.len();
+    }
+}
+
+/// 在導航網格上規劃出的路徑，目標換格或定時重新規劃
+#[derive(Component, Debug, Clone)]
+pub struct EnemyPath {
+    pub waypoints: Vec<Vec2>,
+    pub goal_cell: Option<(i32, i32)>,
+    pub repath: Timer,
+}
+
+impl Default for EnemyPath {
+    fn default() -> Self {
+        Self {
+            waypoints: Vec::new(),
+            goal_cell: None,
+            repath: Timer::from_seconds(ENEMY_REPATH_SECONDS, TimerMode::Once),
+        }
+    }
 }
 
 #[derive(Component)]
@@ -81,12 +164,22 @@ pub struct EnemyAlert {
     pub leash_radius: f32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct EnemySpeeds {
     pub patrol: f32,
     pub chase: f32,
 }
 
+impl EnemySpeeds {
+    /// 套用緩速等狀態後的實際速度
+    pub fn scaled(self, multiplier: f32) -> Self {
+        Self {
+            patrol: self.patrol * multiplier,
+            chase: self.chase * multiplier,
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct EnemyAttack {
     pub radius: f32,
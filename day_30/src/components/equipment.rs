@@ -1,7 +1,10 @@
+use crate::components::stats::DamageType;
+use crate::components::status_effects::{StatusEffectKind, StatusEffectSpec};
+use crate::constants::PLAYER_ATTACK_FACING_COS_THRESHOLD;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ShieldKind {
     Level1,
     Level2,
@@ -15,6 +18,46 @@ impl ShieldKind {
         }
     }
 
+    /// 正面擋下的攻擊減少的傷害比例
+    pub fn block_reduction(&self) -> f32 {
+        match self {
+            ShieldKind::Level1 => 0.5,
+            ShieldKind::Level2 => 0.75,
+        }
+    }
+
+    /// 攻擊方向與面向的夾角餘弦至少要這麼大才擋得住，越小代表防護範圍越寬
+    pub fn block_cos_threshold(&self) -> f32 {
+        match self {
+            ShieldKind::Level1 => 0.4,
+            ShieldKind::Level2 => 0.0,
+        }
+    }
+
+    /// 舉盾後這段時間內擋下的攻擊算作格擋反擊
+    pub fn parry_window_seconds(&self) -> f32 {
+        match self {
+            ShieldKind::Level1 => 0.15,
+            ShieldKind::Level2 => 0.25,
+        }
+    }
+
+    /// 舉盾時每秒消耗的耐力
+    pub fn block_stamina_drain(&self) -> f32 {
+        match self {
+            ShieldKind::Level1 => 22.0,
+            ShieldKind::Level2 => 15.0,
+        }
+    }
+
+    /// 擋下攻擊後保留的擊退比例；大盾站得穩，不會被推開
+    pub fn block_knockback_scale(&self) -> f32 {
+        match self {
+            ShieldKind::Level1 => 0.5,
+            ShieldKind::Level2 => 0.0,
+        }
+    }
+
     pub fn sprite_path(&self) -> &'static str {
         match self {
             ShieldKind::Level1 => "armors/shield_lv1.png",
@@ -30,7 +73,129 @@ impl ShieldKind {
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+/// 近戰武器的種類，決定攻擊距離、角度、速度、耐力消耗與特殊效果
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeaponFamily {
+    Sword,
+    Spear,
+    Axe,
+    Dagger,
+    Hammer,
+}
+
+/// 武器的特殊效果
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeaponSpecial {
+    None,
+    /// 無視目標防禦
+    Pierce,
+    /// 命中目標周圍 `radius` 內的其他敵人也會受到部分傷害
+    Cleave {
+        radius: f32,
+        damage_multiplier: f32,
+    },
+    /// 命中後讓目標硬直較久
+    Stun {
+        seconds: f32,
+    },
+}
+
+/// 揮擊動畫的樣式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeaponSwingStyle {
+    /// 由起始角度掃到結束角度
+    Arc,
+    /// 朝前方刺出再收回
+    Thrust,
+    /// 先慢後快的重砸
+    Slam,
+}
+
+impl WeaponFamily {
+    /// 攻擊距離相對於劍（`PLAYER_ATTACK_RADIUS`）的倍率
+    pub fn reach_scale(&self) -> f32 {
+        match self {
+            WeaponFamily::Sword => 1.0,
+            WeaponFamily::Spear => 1.5,
+            WeaponFamily::Axe => 1.1,
+            WeaponFamily::Dagger => 0.75,
+            WeaponFamily::Hammer => 1.2,
+        }
+    }
+
+    /// 揮擊角度相對於劍的倍率
+    pub fn arc_scale(&self) -> f32 {
+        match self {
+            WeaponFamily::Sword => 1.0,
+            WeaponFamily::Spear => 0.3,
+            WeaponFamily::Axe => 1.6,
+            WeaponFamily::Dagger => 0.8,
+            WeaponFamily::Hammer => 1.2,
+        }
+    }
+
+    /// 敵人方向與面向的夾角餘弦至少要這麼大才會被打中
+    pub fn facing_cos_threshold(&self) -> f32 {
+        match self {
+            WeaponFamily::Sword => PLAYER_ATTACK_FACING_COS_THRESHOLD,
+            WeaponFamily::Spear => 0.8,
+            WeaponFamily::Axe => -0.2,
+            WeaponFamily::Dagger => 0.4,
+            WeaponFamily::Hammer => 0.1,
+        }
+    }
+
+    /// 揮擊時間的倍率，越小出手越快
+    pub fn swing_time_scale(&self) -> f32 {
+        match self {
+            WeaponFamily::Sword => 1.0,
+            WeaponFamily::Spear => 1.1,
+            WeaponFamily::Axe => 1.25,
+            WeaponFamily::Dagger => 0.6,
+            WeaponFamily::Hammer => 1.6,
+        }
+    }
+
+    pub fn stamina_scale(&self) -> f32 {
+        match self {
+            WeaponFamily::Sword | WeaponFamily::Spear => 1.0,
+            WeaponFamily::Axe => 1.3,
+            WeaponFamily::Dagger => 0.6,
+            WeaponFamily::Hammer => 1.6,
+        }
+    }
+
+    pub fn damage_scale(&self) -> f32 {
+        match self {
+            WeaponFamily::Sword | WeaponFamily::Spear => 1.0,
+            WeaponFamily::Axe => 1.15,
+            WeaponFamily::Dagger => 0.75,
+            WeaponFamily::Hammer => 1.4,
+        }
+    }
+
+    pub fn special(&self) -> WeaponSpecial {
+        match self {
+            WeaponFamily::Sword | WeaponFamily::Dagger => WeaponSpecial::None,
+            WeaponFamily::Spear => WeaponSpecial::Pierce,
+            WeaponFamily::Axe => WeaponSpecial::Cleave {
+                radius: 40.0,
+                damage_multiplier: 0.5,
+            },
+            WeaponFamily::Hammer => WeaponSpecial::Stun { seconds: 0.9 },
+        }
+    }
+
+    pub fn swing_style(&self) -> WeaponSwingStyle {
+        match self {
+            WeaponFamily::Sword | WeaponFamily::Axe | WeaponFamily::Dagger => WeaponSwingStyle::Arc,
+            WeaponFamily::Spear => WeaponSwingStyle::Thrust,
+            WeaponFamily::Hammer => WeaponSwingStyle::Slam,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WeaponKind {
     Level1,
     Level2,
@@ -50,6 +215,21 @@ impl WeaponKind {
         }
     }
 
+    /// 關卡檔的武器掉落物沒有指定種類時使用
+    pub fn default_family(&self) -> WeaponFamily {
+        match self {
+            WeaponKind::Level1 => WeaponFamily::Sword,
+            WeaponKind::Level2 => WeaponFamily::Dagger,
+            WeaponKind::Level3 => WeaponFamily::Spear,
+            WeaponKind::Level4 => WeaponFamily::Axe,
+            WeaponKind::Level5 => WeaponFamily::Hammer,
+        }
+    }
+
+    pub fn level(&self) -> u32 {
+        *self as u32 + 1
+    }
+
     pub fn right_sprite_path(&self) -> &'static str {
         match self {
             WeaponKind::Level1 => "weapons/lv1.png",
@@ -65,18 +245,103 @@ impl WeaponKind {
         self.right_sprite_path()
     }
 
+    pub fn display_name(&self, family: WeaponFamily) -> String {
+        format!("{:?}Lv{}", family, self.level())
+    }
+}
+
+/// 遠程武器：射出飛行物，弓消耗箭矢、法杖消耗耐力
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RangedWeaponKind {
+    Bow,
+    MagicStaff,
+}
+
+impl RangedWeaponKind {
+    /// 飛行物傷害佔玩家攻擊力的比例
+    pub fn damage_multiplier(&self) -> f32 {
+        match self {
+            RangedWeaponKind::Bow => 0.8,
+            RangedWeaponKind::MagicStaff => 0.7,
+        }
+    }
+
+    pub fn damage_type(&self) -> DamageType {
+        match self {
+            RangedWeaponKind::Bow => DamageType::Physical,
+            RangedWeaponKind::MagicStaff => DamageType::Magic,
+        }
+    }
+
+    /// 箭矢上限，None 代表不使用彈藥
+    pub fn ammo_capacity(&self) -> Option<u32> {
+        match self {
+            RangedWeaponKind::Bow => Some(20),
+            RangedWeaponKind::MagicStaff => None,
+        }
+    }
+
+    pub fn stamina_cost(&self) -> f32 {
+        match self {
+            RangedWeaponKind::Bow => 0.0,
+            RangedWeaponKind::MagicStaff => 20.0,
+        }
+    }
+
+    /// 命中時附加的狀態異常
+    pub fn on_hit_status(&self) -> Option<StatusEffectSpec> {
+        match self {
+            RangedWeaponKind::Bow => None,
+            RangedWeaponKind::MagicStaff => {
+                Some(StatusEffectSpec::new(StatusEffectKind::Freeze, 0.8, 0.0))
+            }
+        }
+    }
+
+    pub fn cooldown_seconds(&self) -> f32 {
+        match self {
+            RangedWeaponKind::Bow => 0.45,
+            RangedWeaponKind::MagicStaff => 0.6,
+        }
+    }
+
+    pub fn projectile_speed(&self) -> f32 {
+        match self {
+            RangedWeaponKind::Bow => 520.0,
+            RangedWeaponKind::MagicStaff => 380.0,
+        }
+    }
+
+    pub fn projectile_size(&self) -> Vec2 {
+        match self {
+            RangedWeaponKind::Bow => Vec2::new(22.0, 4.0),
+            RangedWeaponKind::MagicStaff => Vec2::splat(14.0),
+        }
+    }
+
+    pub fn projectile_color(&self) -> Color {
+        match self {
+            RangedWeaponKind::Bow => Color::srgb(0.85, 0.75, 0.55),
+            RangedWeaponKind::MagicStaff => Color::srgb(0.45, 0.8, 1.0),
+        }
+    }
+
+    pub fn sprite_path(&self) -> &'static str {
+        match self {
+            RangedWeaponKind::Bow => "weapons/bow.png",
+            RangedWeaponKind::MagicStaff => "weapons/enemy/wizard_staff.png",
+        }
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
-            WeaponKind::Level1 => "SwordLv1",
-            WeaponKind::Level2 => "SwordLv2",
-            WeaponKind::Level3 => "SwordLv3",
-            WeaponKind::Level4 => "SwordLv4",
-            WeaponKind::Level5 => "SwordLv5",
+            RangedWeaponKind::Bow => "Bow",
+            RangedWeaponKind::MagicStaff => "MagicStaff",
         }
     }
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone)]
 pub struct EquippedShield {
     pub kind: ShieldKind,
     pub defense_bonus: i32,
@@ -91,17 +356,53 @@ impl EquippedShield {
     }
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone)]
 pub struct EquippedWeapon {
+    /// 階級，決定攻擊力加成與圖像
     pub kind: WeaponKind,
+    /// 種類，決定攻擊方式
+    pub family: WeaponFamily,
     pub attack_bonus: i32,
 }
 
 impl EquippedWeapon {
-    pub fn new(kind: WeaponKind) -> Self {
+    pub fn new(kind: WeaponKind, family: WeaponFamily) -> Self {
         Self {
             attack_bonus: kind.attack_bonus(),
             kind,
+            family,
+        }
+    }
+
+    pub fn display_name(&self) -> String {
+        self.kind.display_name(self.family)
+    }
+}
+
+/// 玩家目前的遠程武器，與近戰武器分開裝備
+#[derive(Component, Debug, Clone)]
+pub struct EquippedRangedWeapon {
+    pub kind: RangedWeaponKind,
+    /// 剩餘箭矢，不使用彈藥的武器固定為 0
+    pub ammo: u32,
+    pub cooldown: Timer,
+}
+
+impl EquippedRangedWeapon {
+    /// 裝備時補滿箭矢
+    pub fn new(kind: RangedWeaponKind) -> Self {
+        Self::with_ammo(kind, kind.ammo_capacity().unwrap_or(0))
+    }
+
+    pub fn with_ammo(kind: RangedWeaponKind, ammo: u32) -> Self {
+        let mut cooldown = Timer::from_seconds(kind.cooldown_seconds(), TimerMode::Once);
+        cooldown.set_elapsed(cooldown.duration());
+        Self {
+            kind,
+            ammo: kind
+                .ammo_capacity()
+                .map_or(0, |capacity| ammo.min(capacity)),
+            cooldown,
         }
     }
 }
@@ -1,19 +1,26 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::components::equipment::{ShieldKind, WeaponKind};
+use crate::components::equipment::{RangedWeaponKind, ShieldKind, WeaponFamily, WeaponKind};
 
 #[derive(Component, Debug, Clone)]
 pub struct Pickup {
     pub effect: PickupEffect,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PickupEffect {
     Heal(i32),
     RestoreStamina(f32),
     CurePoison,
     EquipShield(ShieldKind),
-    EquipWeapon(WeaponKind),
+    /// 關卡檔可以省略 `family`，省略時使用該階級預設的種類
+    #[serde(deserialize_with = "deserialize_weapon_drop")]
+    EquipWeapon {
+        kind: WeaponKind,
+        family: WeaponFamily,
+    },
+    EquipRangedWeapon(RangedWeaponKind),
 }
 
 impl Pickup {
@@ -21,3 +28,17 @@ impl Pickup {
         Self { effect }
     }
 }
+
+fn deserialize_weapon_drop<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(WeaponKind, WeaponFamily), D::Error> {
+    #[derive(Deserialize)]
+    struct WeaponDrop {
+        kind: WeaponKind,
+        family: Option<WeaponFamily>,
+    }
+
+    let drop = WeaponDrop::deserialize(deserializer)?;
+    let family = drop.family.unwrap_or_else(|| drop.kind.default_family());
+    Ok((drop.kind, family))
+}
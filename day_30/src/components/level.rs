@@ -15,3 +15,24 @@ impl LevelExitDoor {
         Self { target_level }
     }
 }
+
+/// Full-screen message shown when game data failed to load and no level can be built.
+#[derive(Component, Debug)]
+pub struct DataErrorScreen {
+    pub message: String,
+}
+
+/// Marker for doors sealed by an encounter; they ignore the interact key until it is cleared.
+#[derive(Component, Debug, Default)]
+pub struct DoorLocked;
+
+/// Marker for enemies spawned by an encounter wave.
+#[derive(Component, Debug, Default)]
+pub struct EncounterEnemy;
+
+/// Pulsing marker where an encounter enemy will appear once the timer finishes.
+#[derive(Component, Debug)]
+pub struct EncounterSpawnTelegraph {
+    pub archetype_id: String,
+    pub timer: Timer,
+}
@@ -1,4 +1,5 @@
 pub mod audio;
+pub mod boss;
 pub mod camera;
 pub mod chest;
 pub mod effects;
@@ -8,11 +9,14 @@ pub mod items;
 pub mod level;
 pub mod player;
 pub mod progression;
+pub mod projectile;
 pub mod stats;
+pub mod status_effects;
 pub mod ui;
 pub mod world;
 
 pub use audio::*;
+pub use boss::*;
 pub use camera::*;
 pub use chest::*;
 pub use effects::*;
@@ -21,6 +25,8 @@ pub use equipment::*;
 pub use items::*;
 pub use player::*;
 pub use progression::*;
+pub use projectile::*;
 pub use stats::*;
+pub use status_effects::*;
 pub use ui::*;
 pub use world::*;
@@ -1,3 +1,5 @@
+use crate::components::equipment::{ShieldKind, WeaponFamily, WeaponSpecial, WeaponSwingStyle};
+use crate::constants::*;
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -6,7 +8,7 @@ pub struct Player;
 #[derive(Component)]
 pub struct PlayerDead;
 
-#[derive(Component)]
+#[derive(Component, Debug, Clone)]
 pub struct Health {
     pub current: i32,
     pub max: i32,
@@ -34,6 +36,60 @@ impl Velocity {
     }
 }
 
+/// 翻滾中：沿固定方向高速移動，期間不受玩家操作
+#[derive(Component, Debug, Clone)]
+pub struct DodgeRoll {
+    pub direction: Vec2,
+    pub timer: Timer,
+}
+
+impl DodgeRoll {
+    pub fn new(direction: Vec2, seconds: f32) -> Self {
+        Self {
+            direction,
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// 舉盾中，記錄舉起了多久
+#[derive(Component, Debug, Clone, Default)]
+pub struct Blocking {
+    pub elapsed_seconds: f32,
+}
+
+/// 舉盾面對一次攻擊的結果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockOutcome {
+    /// 攻擊來自側面或背後，沒有擋住
+    Unblocked,
+    /// 正面擋下，依盾牌等級減傷
+    Blocked,
+    /// 剛舉盾就擋下，攻擊完全無效並反擊
+    Parried,
+}
+
+impl Blocking {
+    /// `incoming` 是攻擊前進的方向（擊退方向），為零時無法判斷來源，視為沒擋住
+    pub fn outcome(&self, shield: ShieldKind, facing: Vec2, incoming: Vec2) -> BlockOutcome {
+        let toward_attacker = -incoming.normalize_or_zero();
+        let facing = facing.normalize_or_zero();
+        if toward_attacker == Vec2::ZERO || facing == Vec2::ZERO {
+            return BlockOutcome::Unblocked;
+        }
+
+        if facing.dot(toward_attacker) < shield.block_cos_threshold() {
+            return BlockOutcome::Unblocked;
+        }
+
+        if self.elapsed_seconds <= shield.parry_window_seconds() {
+            BlockOutcome::Parried
+        } else {
+            BlockOutcome::Blocked
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct PlayerFacing {
     pub direction: Vec2,
@@ -67,6 +123,138 @@ pub struct WeaponSwing {
     pub timer: Timer,
     pub from_angle: f32,
     pub to_angle: f32,
+    pub style: WeaponSwingStyle,
+    /// 刺擊時武器往前伸出的距離
+    pub reach: f32,
+}
+
+impl WeaponSwing {
+    /// 依照揮擊參數重新開始揮動
+    pub fn start(&mut self, spec: &MeleeSwingSpec) {
+        self.timer = Timer::from_seconds(spec.duration_seconds, TimerMode::Once);
+        self.from_angle = spec.from_angle;
+        self.to_angle = spec.to_angle;
+        self.style = spec.style;
+        self.reach = spec.radius;
+    }
+}
+
+/// 一次近戰揮擊的參數，連擊的每一段與蓄力重擊各有一組
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeleeSwingSpec {
+    pub from_angle: f32,
+    pub to_angle: f32,
+    pub duration_seconds: f32,
+    pub damage_multiplier: f32,
+    pub stamina_cost: f32,
+    pub radius: f32,
+    pub facing_cos_threshold: f32,
+    pub knockback_multiplier: f32,
+    /// 重擊以暴擊結算
+    pub heavy: bool,
+    pub style: WeaponSwingStyle,
+    pub special: WeaponSpecial,
+}
+
+impl MeleeSwingSpec {
+    /// 以劍為基準的連擊第 `step` 段（從 0 開始），超過長度時循環，再依武器種類調整
+    pub fn combo_step(step: usize, family: WeaponFamily) -> Self {
+        let step = step % PLAYER_COMBO_LENGTH;
+        let (from_degrees, to_degrees) = PLAYER_COMBO_ARCS_DEGREES[step];
+
+        Self {
+            from_angle: from_degrees.to_radians(),
+            to_angle: to_degrees.to_radians(),
+            duration_seconds: PLAYER_COMBO_SWING_SECONDS[step],
+            damage_multiplier: PLAYER_COMBO_DAMAGE_MULTIPLIERS[step],
+            stamina_cost: PLAYER_COMBO_STAMINA_COSTS[step],
+            radius: PLAYER_ATTACK_RADIUS,
+            facing_cos_threshold: PLAYER_ATTACK_FACING_COS_THRESHOLD,
+            knockback_multiplier: 1.0,
+            heavy: false,
+            style: WeaponSwingStyle::Arc,
+            special: WeaponSpecial::None,
+        }
+        .for_family(family)
+    }
+
+    pub fn heavy(family: WeaponFamily) -> Self {
+        Self {
+            from_angle: -PLAYER_HEAVY_ATTACK_ARC_DEGREES.to_radians(),
+            to_angle: PLAYER_HEAVY_ATTACK_ARC_DEGREES.to_radians(),
+            duration_seconds: PLAYER_HEAVY_ATTACK_SECONDS,
+            damage_multiplier: PLAYER_HEAVY_ATTACK_DAMAGE_MULTIPLIER,
+            stamina_cost: PLAYER_HEAVY_ATTACK_STAMINA_COST,
+            radius: PLAYER_HEAVY_ATTACK_RADIUS,
+            facing_cos_threshold: PLAYER_HEAVY_ATTACK_FACING_COS_THRESHOLD,
+            knockback_multiplier: PLAYER_HEAVY_ATTACK_KNOCKBACK_MULTIPLIER,
+            heavy: true,
+            style: WeaponSwingStyle::Arc,
+            special: WeaponSpecial::None,
+        }
+        .for_family(family)
+    }
+
+    /// 劍的數值套用武器種類的倍率；判定角度依與劍的差距放寬或收窄
+    fn for_family(self, family: WeaponFamily) -> Self {
+        let cos_offset = family.facing_cos_threshold() - PLAYER_ATTACK_FACING_COS_THRESHOLD;
+
+        Self {
+            from_angle: self.from_angle * family.arc_scale(),
+            to_angle: self.to_angle * family.arc_scale(),
+            duration_seconds: self.duration_seconds * family.swing_time_scale(),
+            damage_multiplier: self.damage_multiplier * family.damage_scale(),
+            stamina_cost: self.stamina_cost * family.stamina_scale(),
+            radius: self.radius * family.reach_scale(),
+            facing_cos_threshold: (self.facing_cos_threshold + cos_offset).clamp(-1.0, 1.0),
+            style: family.swing_style(),
+            special: family.special(),
+            ..self
+        }
+    }
+
+    /// 套用倍率後的傷害
+    pub fn damage(&self, attack: i32) -> i32 {
+        (attack as f32 * self.damage_multiplier).round() as i32
+    }
+}
+
+/// 玩家的連擊進度與蓄力狀態
+#[derive(Component, Debug, Clone)]
+pub struct MeleeCombo {
+    /// 下一次輕攻擊使用的段數
+    pub step: usize,
+    /// 計時結束前沒有接續攻擊，連擊就從第一段重新開始
+    pub window: Timer,
+    /// 按住攻擊鍵的秒數，None 代表沒有在蓄力
+    pub charge_seconds: Option<f32>,
+}
+
+impl MeleeCombo {
+    pub fn new() -> Self {
+        Self {
+            step: 0,
+            window: Timer::from_seconds(0.0, TimerMode::Once),
+            charge_seconds: None,
+        }
+    }
+
+    /// 打出一段連擊後前進到下一段，並重設接續時間
+    pub fn advance(&mut self, swing_seconds: f32) {
+        self.step = (self.step + 1) % PLAYER_COMBO_LENGTH;
+        self.window =
+            Timer::from_seconds(swing_seconds + PLAYER_COMBO_WINDOW_SECONDS, TimerMode::Once);
+    }
+
+    pub fn reset(&mut self) {
+        self.step = 0;
+        self.window = Timer::from_seconds(0.0, TimerMode::Once);
+    }
+
+    pub fn is_charged(&self) -> bool {
+        self.charge_seconds
+            .is_some_and(|seconds| seconds >= PLAYER_HEAVY_ATTACK_CHARGE_SECONDS)
+    }
 }
 
 #[derive(Component)]
@@ -0,0 +1,38 @@
+use crate::components::stats::DamageType;
+use bevy::prelude::*;
+
+/// 所有飛行物共用的移動資料，由 `move_projectiles_system` 推進並處理撞牆
+#[derive(Component, Debug, Clone)]
+pub struct Projectile {
+    pub velocity: Vec2,
+    /// 沿飛行方向的半長，前後兩端碰到牆或關閉的門都會消失
+    pub half_length: f32,
+    pub lifetime: Timer,
+}
+
+impl Projectile {
+    pub fn new(velocity: Vec2, half_length: f32, lifetime_seconds: f32) -> Self {
+        Self {
+            velocity,
+            half_length,
+            lifetime: Timer::from_seconds(lifetime_seconds, TimerMode::Once),
+        }
+    }
+
+    pub fn direction(&self) -> Vec2 {
+        self.velocity.normalize_or_zero()
+    }
+}
+
+/// 玩家遠程武器射出的飛行物，只會命中敵人
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PlayerProjectile {
+    pub damage: i32,
+    pub damage_type: DamageType,
+}
+
+/// 飛行途中會轉向玩家的飛行物，`turn_rate` 為每秒最多轉動的弧度
+#[derive(Component, Debug, Clone, Copy)]
+pub struct HomingProjectile {
+    pub turn_rate: f32,
+}
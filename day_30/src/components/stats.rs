@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::Deserialize;
 
 /// 攻擊屬性，方便未來做加成或是裝備調整
 #[derive(Component, Debug, Clone)]
@@ -82,6 +83,87 @@ pub fn compute_damage(attack: i32, defense: Option<i32>) -> i32 {
     mitigated.max(1)
 }
 
+/// 傷害類型，決定套用哪些減免
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    /// 受防禦力與物理抗性減免
+    Physical,
+    /// 受防禦力與魔法抗性減免
+    Magic,
+    /// 無視防禦與無敵時間，只看毒抗性
+    Poison,
+    /// 燃燒的持續傷害，無視防禦與無敵時間，只看火焰抗性
+    Fire,
+}
+
+impl DamageType {
+    /// 狀態異常造成的持續傷害，不受防禦與無敵時間影響
+    pub fn is_damage_over_time(self) -> bool {
+        matches!(self, DamageType::Poison | DamageType::Fire)
+    }
+}
+
+/// 各種傷害類型的減免比例，0 為不減免、1 為完全免疫
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Resistances {
+    #[serde(default)]
+    pub physical: f32,
+    #[serde(default)]
+    pub magic: f32,
+    #[serde(default)]
+    pub poison: f32,
+    #[serde(default)]
+    pub fire: f32,
+}
+
+/// 無敵時間，期間內不會受到物理與魔法傷害
+#[derive(Component, Debug, Clone)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+impl Invulnerable {
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// 被擊退時的位移速度，會隨時間衰減
+#[derive(Component, Debug, Clone)]
+pub struct Knockback {
+    pub velocity: Vec2,
+    pub timer: Timer,
+}
+
+impl Knockback {
+    pub fn new(velocity: Vec2, seconds: f32) -> Self {
+        Self {
+            velocity,
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// 受擊硬直，期間內暫停 AI 與玩家操作
+#[derive(Component, Debug, Clone)]
+pub struct HitStun {
+    pub timer: Timer,
+}
+
+impl HitStun {
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// 擊退重量，越重被推得越短；沒有此元件時視為 1
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct KnockbackWeight(pub f32);
+
 /// 玩家／敵人共享的耐力值，用來限制爆發行為
 #[derive(Component, Debug, Clone)]
 pub struct Stamina {
@@ -128,23 +210,3 @@ impl Stamina {
         self.current = self.max;
     }
 }
-
-/// 中毒狀態，會定期造成 HP 損失
-#[derive(Component, Debug)]
-pub struct Poisoned {
-    pub tick_timer: Timer,
-    pub damage_per_tick: i32,
-}
-
-impl Poisoned {
-    pub fn new(tick_seconds: f32, damage_per_tick: i32) -> Self {
-        Self {
-            tick_timer: Timer::from_seconds(tick_seconds, TimerMode::Repeating),
-            damage_per_tick,
-        }
-    }
-
-    pub fn reset_timer(&mut self) {
-        self.tick_timer.reset();
-    }
-}
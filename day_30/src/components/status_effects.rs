@@ -0,0 +1,267 @@
+use crate::components::stats::DamageType;
+use crate::constants::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 可以掛在玩家與敵人身上的狀態異常
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StatusEffectKind {
+    /// 定期造成毒素傷害，可疊加
+    Poison,
+    /// 定期造成火焰傷害
+    Burn,
+    /// 降低移動速度
+    Slow,
+    /// 完全無法行動
+    Freeze,
+    /// 降低攻擊力
+    Weakness,
+}
+
+/// 同一種狀態再次命中時的處理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusStacking {
+    /// 重設持續時間，強度取較高者
+    Refresh,
+    /// 重設持續時間並增加層數，強度隨層數相加
+    Intensify { max_stacks: u32 },
+}
+
+impl StatusEffectKind {
+    pub const ALL: [StatusEffectKind; 5] = [
+        StatusEffectKind::Poison,
+        StatusEffectKind::Burn,
+        StatusEffectKind::Slow,
+        StatusEffectKind::Freeze,
+        StatusEffectKind::Weakness,
+    ];
+
+    pub fn stacking(self) -> StatusStacking {
+        match self {
+            StatusEffectKind::Poison => StatusStacking::Intensify {
+                max_stacks: STATUS_POISON_MAX_STACKS,
+            },
+            StatusEffectKind::Burn
+            | StatusEffectKind::Slow
+            | StatusEffectKind::Freeze
+            | StatusEffectKind::Weakness => StatusStacking::Refresh,
+        }
+    }
+
+    /// 持續傷害的間隔，沒有持續傷害的狀態回傳 None
+    pub fn tick_seconds(self) -> Option<f32> {
+        match self {
+            StatusEffectKind::Poison => Some(STATUS_POISON_TICK_SECONDS),
+            StatusEffectKind::Burn => Some(STATUS_BURN_TICK_SECONDS),
+            StatusEffectKind::Slow | StatusEffectKind::Freeze | StatusEffectKind::Weakness => None,
+        }
+    }
+
+    pub fn damage_type(self) -> Option<DamageType> {
+        match self {
+            StatusEffectKind::Poison => Some(DamageType::Poison),
+            StatusEffectKind::Burn => Some(DamageType::Fire),
+            StatusEffectKind::Slow | StatusEffectKind::Freeze | StatusEffectKind::Weakness => None,
+        }
+    }
+
+    pub fn icon_path(self) -> &'static str {
+        match self {
+            StatusEffectKind::Poison => PLAYER_STATS_POISON_ICON_PATH,
+            StatusEffectKind::Burn => PLAYER_STATS_BURN_ICON_PATH,
+            StatusEffectKind::Slow => PLAYER_STATS_SLOW_ICON_PATH,
+            StatusEffectKind::Freeze => PLAYER_STATS_FREEZE_ICON_PATH,
+            StatusEffectKind::Weakness => PLAYER_STATS_WEAKNESS_ICON_PATH,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            StatusEffectKind::Poison => "Poisoned",
+            StatusEffectKind::Burn => "Burning",
+            StatusEffectKind::Slow => "Slowed",
+            StatusEffectKind::Freeze => "Frozen",
+            StatusEffectKind::Weakness => "Weakened",
+        }
+    }
+}
+
+/// 一次命中會附加的狀態；magnitude 依種類代表每跳傷害或降低的比例
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffectSpec {
+    pub kind: StatusEffectKind,
+    pub duration_seconds: f32,
+    #[serde(default)]
+    pub magnitude: f32,
+}
+
+impl StatusEffectSpec {
+    pub fn new(kind: StatusEffectKind, duration_seconds: f32, magnitude: f32) -> Self {
+        Self {
+            kind,
+            duration_seconds,
+            magnitude,
+        }
+    }
+}
+
+/// 目前生效中的一個狀態
+#[derive(Debug, Clone)]
+pub struct ActiveStatusEffect {
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub stacks: u32,
+    pub remaining: Timer,
+    pub tick: Option<Timer>,
+}
+
+impl ActiveStatusEffect {
+    pub fn new(spec: StatusEffectSpec) -> Self {
+        Self {
+            kind: spec.kind,
+            magnitude: spec.magnitude,
+            stacks: 1,
+            remaining: Timer::from_seconds(spec.duration_seconds, TimerMode::Once),
+            tick: spec
+                .kind
+                .tick_seconds()
+                .map(|seconds| Timer::from_seconds(seconds, TimerMode::Repeating)),
+        }
+    }
+
+    /// 疊加後的強度
+    pub fn total_magnitude(&self) -> f32 {
+        self.magnitude * self.stacks as f32
+    }
+
+    pub fn remaining_seconds(&self) -> f32 {
+        self.remaining.remaining_secs()
+    }
+}
+
+/// 實體身上所有的狀態異常
+#[derive(Component, Debug, Clone, Default)]
+pub struct StatusEffects {
+    effects: Vec<ActiveStatusEffect>,
+}
+
+impl StatusEffects {
+    /// 依種類的疊加規則套用新的狀態
+    pub fn apply(&mut self, spec: StatusEffectSpec) {
+        if spec.duration_seconds <= 0.0 {
+            return;
+        }
+
+        let Some(existing) = self
+            .effects
+            .iter_mut()
+            .find(|effect| effect.kind == spec.kind)
+        else {
+            self.effects.push(ActiveStatusEffect::new(spec));
+            return;
+        };
+
+        let duration = Duration::from_secs_f32(spec.duration_seconds);
+        if existing.remaining.remaining() < duration {
+            existing.remaining = Timer::new(duration, TimerMode::Once);
+        }
+
+        match spec.kind.stacking() {
+            StatusStacking::Refresh => {
+                existing.magnitude = existing.magnitude.max(spec.magnitude);
+            }
+            StatusStacking::Intensify { max_stacks } => {
+                existing.magnitude = existing.magnitude.max(spec.magnitude);
+                existing.stacks = (existing.stacks + 1).min(max_stacks.max(1));
+            }
+        }
+    }
+
+    /// 直接放入一個狀態（讀檔還原用），會取代同種類的狀態
+    pub fn restore(&mut self, effect: ActiveStatusEffect) {
+        self.remove(effect.kind);
+        self.effects.push(effect);
+    }
+
+    pub fn remove(&mut self, kind: StatusEffectKind) -> bool {
+        let before = self.effects.len();
+        self.effects.retain(|effect| effect.kind != kind);
+        self.effects.len() != before
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    pub fn get(&self, kind: StatusEffectKind) -> Option<&ActiveStatusEffect> {
+        self.effects.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveStatusEffect> {
+        self.effects.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// 移動速度倍率，緩速會降低、冰凍時為 0
+    pub fn movement_multiplier(&self) -> f32 {
+        if self.has(StatusEffectKind::Freeze) {
+            return 0.0;
+        }
+
+        self.get(StatusEffectKind::Slow)
+            .map(|slow| (1.0 - slow.total_magnitude()).clamp(STATUS_MIN_MOVEMENT_MULTIPLIER, 1.0))
+            .unwrap_or(1.0)
+    }
+
+    /// 攻擊力倍率，虛弱時降低
+    pub fn attack_multiplier(&self) -> f32 {
+        self.get(StatusEffectKind::Weakness)
+            .map(|weakness| {
+                (1.0 - weakness.total_magnitude()).clamp(STATUS_MIN_ATTACK_MULTIPLIER, 1.0)
+            })
+            .unwrap_or(1.0)
+    }
+
+    /// 冰凍剩餘的秒數
+    pub fn frozen_seconds(&self) -> Option<f32> {
+        self.get(StatusEffectKind::Freeze)
+            .map(ActiveStatusEffect::remaining_seconds)
+    }
+
+    /// 推進所有狀態的時間，回傳這個影格該結算的持續傷害，並移除已結束的狀態
+    pub fn tick(&mut self, delta: Duration) -> Vec<(StatusEffectKind, i32)> {
+        let mut damage = Vec::new();
+
+        for effect in &mut self.effects {
+            if let Some(tick) = effect.tick.as_mut() {
+                let ticks = tick.tick(delta).times_finished_this_tick();
+                let amount = effect.total_magnitude().round() as i32;
+                if ticks > 0 && amount > 0 {
+                    damage.push((effect.kind, amount * ticks as i32));
+                }
+            }
+            effect.remaining.tick(delta);
+        }
+
+        self.effects.retain(|effect| !effect.remaining.finished());
+        damage
+    }
+}
+
+/// 對特定狀態免疫
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct StatusImmunities(pub Vec<StatusEffectKind>);
+
+impl StatusImmunities {
+    pub fn contains(&self, kind: StatusEffectKind) -> bool {
+        self.0.contains(&kind)
+    }
+}
+
+/// 命中時附加的狀態，掛在攻擊者或飛行物上
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct OnHitStatusEffects(pub Vec<StatusEffectSpec>);
@@ -1,3 +1,4 @@
+use crate::resources::InputAction;
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -64,7 +65,9 @@ pub struct MainMenuButton {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MainMenuAction {
     NewGame,
+    EndlessMode,
     LoadGame,
+    Controls,
 }
 
 #[derive(Component)]
@@ -80,4 +83,36 @@ pub enum PauseMenuAction {
     Resume,
     Save,
     Load,
+    Controls,
+}
+
+#[derive(Component)]
+pub struct SaveSlotPickerRoot;
+
+#[derive(Component)]
+pub struct SaveSlotButton {
+    pub action: SaveSlotAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaveSlotAction {
+    Save(usize),
+    Load(usize),
+    Delete(usize),
+    Back,
+}
+
+#[derive(Component)]
+pub struct ControlsMenuRoot;
+
+#[derive(Component)]
+pub struct ControlsMenuButton {
+    pub action: ControlsMenuAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlsMenuAction {
+    Rebind(InputAction),
+    ResetDefaults,
+    Back,
 }
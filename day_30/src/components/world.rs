@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 #[derive(Component)]
 pub struct GridTile;
@@ -31,6 +32,20 @@ impl EnvironmentProp {
     }
 }
 
+/// 以中心為準的方形碰撞箱，移動時沿著牆滑動
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BoxCollider {
+    pub half_size: Vec2,
+}
+
+impl BoxCollider {
+    pub fn square(half_size: f32) -> Self {
+        Self {
+            half_size: Vec2::splat(half_size),
+        }
+    }
+}
+
 #[derive(Component, Debug)]
 pub struct RoomTile {
     pub tile_type: RoomTileType,
@@ -55,7 +70,7 @@ pub enum RoomTileType {
     FloorOutdoor,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoomRect {
     pub x: i32,
     pub y: i32,
@@ -69,7 +84,7 @@ pub struct CompoundRoom {
     pub room_type: CompoundRoomType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CompoundRoomType {
     LShape,
     TShape,
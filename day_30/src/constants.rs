@@ -1,11 +1,23 @@
-use bevy::prelude::Color;
+use bevy::prelude::{Color, GamepadButton};
 
 pub const PLAYER_SPEED: f32 = 300.0;
 pub const PLAYER_SCALE: f32 = 4.0;
+// 玩家碰撞箱半寬，與牆壁中心保持約 0.7 格的距離
+pub const PLAYER_COLLIDER_HALF_SIZE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE * 0.2;
 pub const PLAYER_INITIAL_HEALTH: i32 = 100;
 pub const PLAYER_MAX_STAMINA: f32 = 100.0;
 pub const PLAYER_STAMINA_REGEN_PER_SECOND: f32 = 25.0;
 pub const PLAYER_ATTACK_STAMINA_COST: f32 = 35.0;
+pub const PLAYER_DODGE_STAMINA_COST: f32 = 30.0;
+pub const PLAYER_DODGE_SPEED: f32 = 560.0;
+pub const PLAYER_DODGE_SECONDS: f32 = 0.25;
+// 無敵時間略長於翻滾本身，落地瞬間也不會被打中
+pub const PLAYER_DODGE_INVULNERABLE_SECONDS: f32 = 0.3;
+pub const PLAYER_DODGE_ALPHA: f32 = 0.55;
+// 舉盾時的移動速度倍率
+pub const PLAYER_BLOCK_MOVE_MULTIPLIER: f32 = 0.45;
+// 被格擋反擊的近戰敵人會硬直這麼久
+pub const PLAYER_PARRY_STAGGER_SECONDS: f32 = 1.2;
 pub const PLAYER_MAX_LEVEL: usize = 3;
 pub const PLAYER_LEVEL_XP_REQUIREMENTS: [u32; PLAYER_MAX_LEVEL] = [120, 240, 420];
 pub const PLAYER_LEVEL_BASE_ATTACK: [i32; PLAYER_MAX_LEVEL + 1] = [15, 24, 34, 46];
@@ -16,8 +28,6 @@ pub const PLAYER_LEVEL_SPRITE_PATHS: [&str; PLAYER_MAX_LEVEL + 1] = [
     "characters/players/knight_lv2.png",
     "characters/players/knight_lv3.png",
 ];
-pub const PLAYER_POISON_TICK_SECONDS: f32 = 1.25;
-pub const PLAYER_POISON_TICK_DAMAGE: i32 = 3;
 pub const PLAYER_HEALTH_BAR_WIDTH: f32 = 220.0;
 pub const PLAYER_HEALTH_BAR_HEIGHT: f32 = 22.0;
 pub const PLAYER_HEALTH_BAR_MARGIN: f32 = 18.0;
@@ -40,6 +50,12 @@ pub const MENU_BUTTON_FONT_SIZE: f32 = 28.0;
 pub const MENU_BUTTON_WIDTH: f32 = 260.0;
 pub const MENU_BUTTON_HEIGHT: f32 = 52.0;
 pub const MENU_OVERLAY_COLOR: [f32; 4] = [0.02, 0.02, 0.04, 0.92];
+pub const SAVE_SLOT_COUNT: usize = 3;
+pub const SAVE_SLOT_PANEL_WIDTH: f32 = 720.0;
+pub const SAVE_SLOT_INFO_FONT_SIZE: f32 = 18.0;
+pub const SAVE_SLOT_BUTTON_WIDTH: f32 = 130.0;
+pub const CONTROLS_PANEL_WIDTH: f32 = 680.0;
+pub const CONTROLS_BINDING_BUTTON_WIDTH: f32 = 380.0;
 pub const MENU_MUSIC_VOLUME: f32 = 0.8;
 pub const GAMEPLAY_MUSIC_VOLUME: f32 = 0.45;
 
@@ -53,13 +69,63 @@ pub const PLAYER_STATS_ICON_SIZE: f32 = 28.0;
 pub const PLAYER_STATS_ATTACK_ICON_PATH: &str = "icons/sword.png";
 pub const PLAYER_STATS_DEFENSE_ICON_PATH: &str = "icons/shield.png";
 pub const PLAYER_STATS_POISON_ICON_PATH: &str = "icons/skull.png";
+pub const PLAYER_STATS_BURN_ICON_PATH: &str = "icons/flame.png";
+pub const PLAYER_STATS_SLOW_ICON_PATH: &str = "icons/web.png";
+pub const PLAYER_STATS_FREEZE_ICON_PATH: &str = "icons/snowflake.png";
+pub const PLAYER_STATS_WEAKNESS_ICON_PATH: &str = "icons/broken_sword.png";
+
+// 浮動戰鬥文字同時存在的上限，超過時重複使用最舊的文字
+pub const COMBAT_TEXT_POOL_SIZE: usize = 32;
+
+// 狀態異常
+pub const STATUS_POISON_TICK_SECONDS: f32 = 1.25;
+pub const STATUS_POISON_MAX_STACKS: u32 = 3;
+pub const STATUS_BURN_TICK_SECONDS: f32 = 0.5;
+pub const STATUS_MIN_MOVEMENT_MULTIPLIER: f32 = 0.2;
+pub const STATUS_MIN_ATTACK_MULTIPLIER: f32 = 0.25;
 
 pub const WEAPON_SCALE: f32 = 1.0;
 pub const WEAPON_IDLE_OFFSET_X: f32 = 10.0;
 pub const WEAPON_IDLE_OFFSET_Y: f32 = 2.0;
 pub const WEAPON_Z: f32 = 1.0;
+// 刺擊時武器伸出的距離佔攻擊距離的比例（武器是玩家的子實體，會再乘上玩家縮放）
+pub const WEAPON_THRUST_REACH_FRACTION: f32 = 0.08;
 pub const PLAYER_ATTACK_RADIUS: f32 = 48.0;
 pub const PLAYER_ATTACK_FACING_COS_THRESHOLD: f32 = 0.25;
+pub const PLAYER_ATTACK_KNOCKBACK: f32 = 420.0;
+// 連擊依序使用的揮擊：角度（度）、傷害倍率、體力消耗與揮擊時間
+pub const PLAYER_COMBO_LENGTH: usize = 3;
+pub const PLAYER_COMBO_ARCS_DEGREES: [(f32, f32); PLAYER_COMBO_LENGTH] =
+    [(-45.0, 45.0), (45.0, -45.0), (-80.0, 80.0)];
+pub const PLAYER_COMBO_DAMAGE_MULTIPLIERS: [f32; PLAYER_COMBO_LENGTH] = [1.0, 1.2, 1.6];
+pub const PLAYER_COMBO_STAMINA_COSTS: [f32; PLAYER_COMBO_LENGTH] =
+    [PLAYER_ATTACK_STAMINA_COST, 25.0, 30.0];
+pub const PLAYER_COMBO_SWING_SECONDS: [f32; PLAYER_COMBO_LENGTH] = [0.5, 0.4, 0.55];
+// 揮擊結束後在這段時間內再次攻擊才會接到下一段
+pub const PLAYER_COMBO_WINDOW_SECONDS: f32 = 0.6;
+// 按住攻擊鍵超過這個秒數，放開時施放重擊
+pub const PLAYER_HEAVY_ATTACK_CHARGE_SECONDS: f32 = 0.7;
+pub const PLAYER_HEAVY_ATTACK_RADIUS: f32 = 76.0;
+pub const PLAYER_HEAVY_ATTACK_ARC_DEGREES: f32 = 110.0;
+pub const PLAYER_HEAVY_ATTACK_FACING_COS_THRESHOLD: f32 = -0.1;
+pub const PLAYER_HEAVY_ATTACK_DAMAGE_MULTIPLIER: f32 = 1.5;
+pub const PLAYER_HEAVY_ATTACK_STAMINA_COST: f32 = 50.0;
+pub const PLAYER_HEAVY_ATTACK_SECONDS: f32 = 0.65;
+pub const PLAYER_HEAVY_ATTACK_KNOCKBACK_MULTIPLIER: f32 = 1.6;
+pub const ENEMY_CONTACT_KNOCKBACK: f32 = 380.0;
+pub const PROJECTILE_KNOCKBACK: f32 = 220.0;
+pub const KNOCKBACK_SECONDS: f32 = 0.2;
+pub const KNOCKBACK_DAMPING: f32 = 8.0;
+// 擊退力道依實際傷害縮放：傷害等於此值時為 1 倍
+pub const KNOCKBACK_DAMAGE_REFERENCE: f32 = 10.0;
+pub const KNOCKBACK_MIN_SCALE: f32 = 0.6;
+pub const KNOCKBACK_MAX_SCALE: f32 = 1.8;
+pub const ENEMY_HIT_STUN_SECONDS: f32 = 0.3;
+pub const PLAYER_HIT_STUN_SECONDS: f32 = 0.15;
+pub const CRITICAL_HIT_MULTIPLIER: f32 = 1.5;
+pub const PLAYER_PROJECTILE_SPAWN_OFFSET: f32 = 20.0;
+pub const PLAYER_PROJECTILE_LIFETIME: f32 = 1.2;
+pub const PLAYER_PROJECTILE_HIT_RADIUS: f32 = 28.0;
 pub const ATTACK_RETICLE_SPRITE_PATH: &str = "reticle/reticle_aiming.png";
 pub const ATTACK_RETICLE_DISTANCE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;
 pub const ATTACK_RETICLE_Z_OFFSET: f32 = -1.0;
@@ -83,15 +149,7 @@ pub const CHEST_SCALE: f32 = 4.0;
 pub const CHEST_Z: f32 = 9.0;
 pub const CHEST_ITEM_REVEAL_SECONDS: f32 = 2.0;
 pub const CHEST_MIMIC_PROBABILITY: f32 = 0.25;
-pub const CHEST_MIMIC_TRIGGER_RADIUS: f32 = 120.0;
-pub const CHEST_MIMIC_LEASH_RADIUS: f32 = 160.0;
-pub const CHEST_MIMIC_PATROL_RANGE: f32 = 20.0;
-pub const CHEST_MIMIC_CHASE_SPEED: f32 = 90.0;
-pub const CHEST_MIMIC_HEALTH: i32 = 65;
-pub const CHEST_MIMIC_ATTACK: i32 = 12;
-pub const CHEST_MIMIC_DEFENSE: i32 = 4;
-pub const CHEST_MIMIC_ATTACK_RADIUS: f32 = 32.0;
-pub const CHEST_MIMIC_ATTACK_COOLDOWN: f32 = 1.4;
+pub const CHEST_MIMIC_ARCHETYPE_ID: &str = "mimic";
 pub const CHEST_ITEM_DISPLAY_OFFSET: f32 = 20.0;
 pub const CHEST_ITEM_DISPLAY_SCALE: f32 = 1.25;
 
@@ -114,48 +172,43 @@ pub const ENVIRONMENT_PROP_Z: f32 = Z_LAYER_GRID + 5.0;
 pub const ENVIRONMENT_PROP_SCALE: f32 = 4.0;
 pub const ENVIRONMENT_PROP_COLLISION_RADIUS: f32 = ROOM_TILE_SIZE * PLAYER_SCALE * 0.5;
 
+pub const LEVEL_CAMPAIGN_PATH: &str = "levels/main.campaign.json";
+pub const LEVEL_MIN_ROOM_SPAN_TILES: usize = 3;
+
+pub const ENEMY_ARCHETYPES_PATH: &str = "enemies/bestiary.enemies.json";
+
+pub const ENDLESS_DEPTH_SEED_STRIDE: u64 = 0x9E37_79B9_7F4A_7C15;
+pub const ENDLESS_ROOM_GROWTH_INTERVAL: usize = 3;
+pub const ENDLESS_MAX_ROOM_GROWTH_TILES: usize = 4;
+pub const ENDLESS_BASE_SLIMES: usize = 5;
+pub const ENDLESS_MAX_SLIMES: usize = 10;
+pub const ENDLESS_MAX_CYCLOPS: usize = 10;
+pub const ENDLESS_MAX_SPIDERS: usize = 8;
+pub const ENDLESS_MAX_PROPS_PER_KIND: usize = 6;
+pub const ENDLESS_ELITE_BOSS_DEPTH: usize = 3;
+// 菁英頭目之後每深一層，頭目能力再提高的比例與上限
+pub const ENDLESS_BOSS_GROWTH_PER_DEPTH: f32 = 0.15;
+pub const ENDLESS_MAX_BOSS_MULTIPLIER: f32 = 2.5;
+// 菁英頭目之後每隔幾層多掉一瓶藥水，以及最多掉幾瓶
+pub const ENDLESS_BOSS_POTION_INTERVAL: usize = 2;
+pub const ENDLESS_MAX_BOSS_POTIONS: usize = 3;
+pub const ENDLESS_SLIME_ARCHETYPE_ID: &str = "slime";
+pub const ENDLESS_CYCLOPS_ARCHETYPE_ID: &str = "cyclops";
+pub const ENDLESS_SPIDER_ARCHETYPE_ID: &str = "spider";
+pub const ENDLESS_BOSS_ARCHETYPE_ID: &str = "wizard_boss";
+
 pub const INPUT_DEADZONE: f32 = 0.1;
+pub const GAMEPAD_INTERACT_BUTTON: GamepadButton = GamepadButton::South;
+pub const GAMEPAD_ATTACK_BUTTON: GamepadButton = GamepadButton::West;
+pub const GAMEPAD_DODGE_BUTTON: GamepadButton = GamepadButton::East;
+pub const GAMEPAD_FIRE_BUTTON: GamepadButton = GamepadButton::RightTrigger;
+pub const GAMEPAD_BLOCK_BUTTON: GamepadButton = GamepadButton::LeftTrigger;
+pub const GAMEPAD_PAUSE_BUTTON: GamepadButton = GamepadButton::Start;
+pub const GAMEPAD_MENU_CONFIRM_BUTTON: GamepadButton = GamepadButton::South;
 
-pub const SLIME_SCALE: f32 = 4.0;
-pub const SLIME_HEALTH: i32 = 35;
-pub const SLIME_PATROL_RANGE: f32 = 120.0;
-pub const SLIME_PATROL_SPEED: f32 = 60.0;
-pub const SLIME_CHASE_SPEED: f32 = 90.0;
-pub const SLIME_ALERT_RADIUS: f32 = 200.0;
-pub const SLIME_LEASH_RADIUS: f32 = 260.0;
-pub const SLIME_BASE_ATTACK: i32 = 5;
-pub const SLIME_BASE_DEFENSE: i32 = 2;
-pub const SLIME_EXPERIENCE_REWARD: u32 = 30;
-pub const SLIME_ATTACK_RADIUS: f32 = 28.0;
-pub const SLIME_ATTACK_COOLDOWN: f32 = 1.0;
-
-pub const CYCLOPS_SCALE: f32 = 4.5;
-pub const CYCLOPS_HEALTH: i32 = 80;
-pub const CYCLOPS_PATROL_RANGE: f32 = 140.0;
-pub const CYCLOPS_PATROL_SPEED: f32 = 55.0;
-pub const CYCLOPS_CHASE_SPEED: f32 = 85.0;
-pub const CYCLOPS_ALERT_RADIUS: f32 = 220.0;
-pub const CYCLOPS_LEASH_RADIUS: f32 = 220.0;
-pub const CYCLOPS_BASE_ATTACK: i32 = 12;
-pub const CYCLOPS_BASE_DEFENSE: i32 = 6;
-pub const CYCLOPS_EXPERIENCE_REWARD: u32 = 90;
-pub const CYCLOPS_ATTACK_RADIUS: f32 = 36.0;
-pub const CYCLOPS_ATTACK_COOLDOWN: f32 = 1.25;
-pub const CYCLOPS_WINDUP_SECONDS: f32 = 0.6;
-pub const CYCLOPS_CHARGE_SECONDS: f32 = 0.45;
 pub const CYCLOPS_CHARGE_MULTIPLIER: f32 = 2.2;
-pub const CYCLOPS_COOLDOWN_SECONDS: f32 = 1.2;
-
-pub const SPIDER_SCALE: f32 = 3.6;
-pub const SPIDER_HEALTH: i32 = 55;
-pub const SPIDER_PATROL_RANGE: f32 = 150.0;
-pub const SPIDER_PATROL_SPEED: f32 = 65.0;
-pub const SPIDER_BASE_ATTACK: i32 = 10;
-pub const SPIDER_BASE_DEFENSE: i32 = 3;
-pub const SPIDER_ATTACK_RADIUS: f32 = 280.0;
-pub const SPIDER_ATTACK_COOLDOWN: f32 = 1.9;
+
 pub const SPIDER_MIN_ATTACK_DISTANCE: f32 = 140.0;
-pub const SPIDER_EXPERIENCE_REWARD: u32 = 140;
 pub const SPIDER_WEB_PROJECTILE_SPEED: f32 = 220.0;
 pub const SPIDER_WEB_PROJECTILE_LIFETIME: f32 = 1.1;
 pub const SPIDER_WEB_PROJECTILE_LENGTH: f32 = 120.0;
@@ -163,24 +216,7 @@ pub const SPIDER_WEB_PROJECTILE_THICKNESS: f32 = 6.0;
 pub const SPIDER_WEB_PROJECTILE_HIT_RADIUS: f32 = 26.0;
 pub const SPIDER_WEB_PROJECTILE_SPAWN_OFFSET: f32 = 28.0;
 
-pub const WIZARD_BOSS_SCALE: f32 = 4.5;
-pub const WIZARD_BOSS_HEALTH: i32 = 180;
-pub const WIZARD_BOSS_PATROL_RANGE: f32 = 40.0;
-pub const WIZARD_BOSS_PATROL_SPEED: f32 = 45.0;
-pub const WIZARD_BOSS_CHASE_SPEED: f32 = 75.0;
-pub const WIZARD_BOSS_ALERT_RADIUS: f32 = 200.0;
-pub const WIZARD_BOSS_LEASH_RADIUS: f32 = 260.0;
-pub const WIZARD_BOSS_BASE_ATTACK: i32 = 20;
-pub const WIZARD_BOSS_BASE_DEFENSE: i32 = 10;
-pub const WIZARD_BOSS_ATTACK_RADIUS: f32 = 260.0;
-pub const WIZARD_BOSS_ATTACK_COOLDOWN: f32 = 0.9;
-pub const WIZARD_BOSS_EXPERIENCE_REWARD: u32 = 250;
-pub const WIZARD_BOSS_SPRITE_PATH: &str = "characters/enemies/wizard.png";
-pub const WIZARD_BOSS_STAFF_SPRITE_PATH: &str = "weapons/enemy/wizard_staff.png";
 pub const WIZARD_BOSS_STAFF_OFFSET_X: f32 = 8.5;
-pub const WIZARD_BOSS_STAFF_OFFSET_Y: f32 = -2.0;
-pub const WIZARD_BOSS_STAFF_OFFSET_Z: f32 = 1.0;
-pub const WIZARD_BOSS_STAFF_SCALE: f32 = 1.0;
 pub const WIZARD_BOSS_CAST_MIN_DISTANCE: f32 = 140.0;
 pub const WIZARD_BOSS_CAST_HEIGHT_OFFSET: f32 = 4.0;
 pub const WIZARD_BOSS_PROJECTILE_SPEED: f32 = 360.0;
@@ -188,13 +224,41 @@ pub const WIZARD_BOSS_PROJECTILE_LIFETIME: f32 = 2.0;
 pub const WIZARD_BOSS_PROJECTILE_SIZE: f32 = 18.0;
 pub const WIZARD_BOSS_PROJECTILE_HIT_RADIUS: f32 = 24.0;
 pub const WIZARD_BOSS_PROJECTILE_COLOR: Color = Color::srgb(0.72, 0.28, 0.92);
+pub const WIZARD_BOSS_ORB_SPEED: f32 = 210.0;
+pub const WIZARD_BOSS_ORB_LIFETIME: f32 = 3.5;
+pub const WIZARD_BOSS_ORB_SIZE: f32 = 22.0;
+pub const WIZARD_BOSS_ORB_COLOR: Color = Color::srgb(0.35, 0.85, 1.0);
+/// 同時發射多顆追蹤法球時的散開角度
+pub const WIZARD_BOSS_ORB_FAN_DEGREES: f32 = 50.0;
+
+// 敵人尋路
+pub const NAV_MAX_SEARCH_NODES: usize = 4096;
+pub const NAV_BODY_HALF_WIDTH_TILES: f32 = 0.3;
+pub const ENEMY_REPATH_SECONDS: f32 = 0.4;
+pub const ENEMY_WAYPOINT_REACHED_DISTANCE: f32 = 6.0;
+// 跟丟玩家後在最後看到的位置附近搜尋的時間
+pub const ENEMY_INVESTIGATE_SECONDS: f32 = 4.0;
+
+// 頭目階段
+pub const BOSS_TELEGRAPH_SIZE: f32 = 18.0;
+pub const BOSS_TELEGRAPH_PULSE_SCALE: f32 = 0.35;
+pub const BOSS_ENRAGED_TINT: Color = Color::srgb(1.0, 0.55, 0.5);
+pub const BOSS_MAX_MINIONS: usize = 4;
+pub const BOSS_SUMMON_OFFSET: f32 = 56.0;
+
+// 鎖門遭遇戰
+pub const ENCOUNTER_DEFAULT_TRIGGER_DEPTH_TILES: f32 = 3.0;
+pub const ENCOUNTER_DEFAULT_TELEGRAPH_SECONDS: f32 = 1.0;
+// 敵人不會在玩家身邊這個距離（格）內出現
+pub const ENCOUNTER_SPAWN_MIN_DISTANCE_TILES: f32 = 2.5;
+pub const ENCOUNTER_TELEGRAPH_SIZE: f32 = 40.0;
+pub const ENCOUNTER_TELEGRAPH_PULSE_SCALE: f32 = 0.6;
+pub const ENCOUNTER_TELEGRAPH_COLOR: Color = Color::srgba(0.9, 0.2, 0.25, 0.55);
 
 pub const ENEMY_HEALTH_BAR_WIDTH: f32 = 50.0;
 pub const ENEMY_HEALTH_BAR_HEIGHT: f32 = 6.0;
 pub const ENEMY_HEALTH_BAR_OFFSET_Y: f32 = 48.0;
 
-pub const MIMIC_EXPERIENCE_REWARD: u32 = 110;
-
 pub const PLAYER_DAMAGE_FLASH_COUNT: u8 = 4;
 pub const PLAYER_DAMAGE_FLASH_INTERVAL: f32 = 0.08;
 pub const PLAYER_DAMAGE_FLASH_COLOR: [f32; 4] = [1.0, 0.4, 0.4, 1.0];
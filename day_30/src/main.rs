@@ -14,6 +14,9 @@ mod plugins;
 mod resources;
 mod systems;
 
+#[cfg(test)]
+mod tests;
+
 use plugins::*;
 
 fn main() {
@@ -39,7 +42,7 @@ fn main() {
     #[cfg(not(target_arch = "wasm32"))]
     app.add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()));
 
-    app.add_plugins(SessionPlugin)
+    app.add_plugins((SessionPlugin, ControlsPlugin))
         .add_plugins((
             WorldPlugin,
             LevelPlugin,
@@ -57,6 +60,12 @@ fn main() {
             DoorInteractionPlugin,
             RoomTransitionPlugin,
         ))
-        .add_plugins(EffectsPlugin)
+        .add_plugins((
+            DamagePlugin,
+            StatusEffectsPlugin,
+            ProjectilePlugin,
+            EffectsPlugin,
+            CombatTextPlugin,
+        ))
         .run();
 }
@@ -1,3 +1,4 @@
+use crate::resources::GameplaySet;
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -12,13 +13,15 @@ impl Plugin for AttackPlugin {
                 Update,
                 (
                     attack_input_system,
+                    melee_combo_system.after(attack_input_system),
                     update_attack_reticle_system.after(movement_system),
                     player_melee_attack_system
-                        .after(attack_input_system)
+                        .after(melee_combo_system)
                         .after(update_attack_reticle_system),
                     update_weapon_offset_system,
-                    update_weapon_swing_animation_system,
-                ),
+                    update_weapon_swing_animation_system.after(update_weapon_offset_system),
+                )
+                    .in_set(GameplaySet),
             );
     }
 }
@@ -1,4 +1,4 @@
-use crate::resources::CameraShake;
+use crate::resources::{CameraShake, GameplaySet};
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -13,7 +13,8 @@ impl Plugin for CameraPlugin {
                 apply_camera_shake_system
                     .after(camera_follow_system)
                     .after(trigger_camera_shake_on_enemy_hit),
-            ),
+            )
+                .in_set(GameplaySet),
         );
     }
 }
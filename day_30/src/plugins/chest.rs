@@ -1,3 +1,4 @@
+use crate::resources::GameplaySet;
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -5,7 +6,9 @@ pub struct ChestPlugin;
 
 impl Plugin for ChestPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<ChestInteractionEvent>()
-            .add_systems(Update, (chest_interaction_system, chest_item_reveal_system));
+        app.add_event::<ChestInteractionEvent>().add_systems(
+            Update,
+            (chest_interaction_system, chest_item_reveal_system).in_set(GameplaySet),
+        );
     }
 }
@@ -0,0 +1,28 @@
+use crate::resources::GameplaySet;
+use crate::systems::*;
+use bevy::prelude::*;
+
+pub struct CombatTextPlugin;
+
+impl Plugin for CombatTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CombatTextRequest>()
+            .add_systems(Startup, setup_combat_text_pool)
+            .add_systems(
+                Update,
+                (
+                    queue_damage_combat_text.after(resolve_damage_requests),
+                    queue_heal_combat_text,
+                    queue_experience_combat_text.after(despawn_dead_enemies_system),
+                    queue_boss_phase_combat_text.after(boss_phase_system),
+                    spawn_combat_text_system
+                        .after(queue_damage_combat_text)
+                        .after(queue_heal_combat_text)
+                        .after(queue_experience_combat_text)
+                        .after(queue_boss_phase_combat_text),
+                    update_combat_text_system.after(spawn_combat_text_system),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
@@ -0,0 +1,40 @@
+use crate::resources::{ActionState, InputBindings, SettingsStorage};
+use crate::systems::controls::{
+    InputBindingsChangedEvent, OpenControlsMenuEvent, capture_rebind_input,
+    handle_controls_menu_interactions, load_input_settings, update_action_state,
+    update_controls_menu,
+};
+use crate::systems::game_session::{
+    handle_main_menu_interactions, handle_pause_menu_interactions, toggle_pause_menu_on_escape,
+};
+use crate::systems::gamepad::navigate_menus_with_gamepad;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsStorage>()
+            .init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .add_event::<OpenControlsMenuEvent>()
+            .add_event::<InputBindingsChangedEvent>()
+            .add_systems(Startup, load_input_settings)
+            .add_systems(PreUpdate, update_action_state.after(InputSystem))
+            .add_systems(
+                Update,
+                (
+                    // 先讀取新按鍵再處理點擊，避免按下確認鍵的同一個影格就被綁定
+                    capture_rebind_input.after(toggle_pause_menu_on_escape),
+                    handle_controls_menu_interactions
+                        .after(capture_rebind_input)
+                        .after(navigate_menus_with_gamepad),
+                    update_controls_menu
+                        .after(handle_controls_menu_interactions)
+                        .after(handle_main_menu_interactions)
+                        .after(handle_pause_menu_interactions),
+                ),
+            );
+    }
+}
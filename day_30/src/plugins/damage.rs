@@ -0,0 +1,33 @@
+use crate::resources::GameplaySet;
+use crate::systems::*;
+use bevy::prelude::*;
+
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageRequest>()
+            .add_event::<DamageAppliedEvent>()
+            .add_systems(
+                Update,
+                (
+                    tick_invulnerability_system,
+                    resolve_damage_requests
+                        .after(tick_invulnerability_system)
+                        .after(player_melee_attack_system)
+                        .after(enemy_contact_attack_system)
+                        .after(boss_wizard_projectile_system)
+                        .after(spider_web_projectile_system)
+                        .after(player_projectile_hit_system),
+                    apply_knockback_on_hit_system.after(resolve_damage_requests),
+                    tick_hit_stun_system.after(apply_knockback_on_hit_system),
+                    knockback_movement_system
+                        .after(apply_knockback_on_hit_system)
+                        .after(movement_system)
+                        .before(wall_collision_system)
+                        .before(enemy_wall_collision_system),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::resources::GameplaySet;
 use crate::systems::{
     DoorInteractionEvent, DoorStateChangedEvent, door_interaction_system, input_system,
 };
@@ -9,6 +10,9 @@ impl Plugin for DoorInteractionPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<DoorInteractionEvent>()
             .add_event::<DoorStateChangedEvent>()
-            .add_systems(Update, (input_system, door_interaction_system));
+            .add_systems(
+                Update,
+                (input_system, door_interaction_system).in_set(GameplaySet),
+            );
     }
 }
@@ -1,3 +1,4 @@
+use crate::resources::GameplaySet;
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -8,14 +9,16 @@ impl Plugin for EffectsPlugin {
         app.add_systems(
             Update,
             (
-                spawn_hit_spark_system.after(player_melee_attack_system),
+                spawn_hit_spark_system.after(resolve_damage_requests),
                 update_hit_spark_system,
-                apply_enemy_hit_flash_system.after(player_melee_attack_system),
+                apply_enemy_hit_flash_system.after(resolve_damage_requests),
                 update_enemy_hit_flash_system,
-                spawn_enemy_death_particles_system.after(player_melee_attack_system),
+                spawn_enemy_death_particles_system.after(resolve_damage_requests),
                 update_death_particles_system,
-                trigger_camera_shake_on_enemy_hit.after(player_melee_attack_system),
-            ),
+                trigger_camera_shake_on_enemy_hit.after(resolve_damage_requests),
+                trigger_camera_shake_on_boss_phase.after(boss_phase_system),
+            )
+                .in_set(GameplaySet),
         );
     }
 }
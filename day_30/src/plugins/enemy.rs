@@ -1,3 +1,6 @@
+use crate::resources::{
+    EnemyArchetypeLoader, EnemyArchetypeRegistry, EnemyArchetypeSet, GameplaySet, NavigationGrid,
+};
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -5,26 +8,42 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<EnemyDefeatedEvent>()
+        app.init_asset::<EnemyArchetypeSet>()
+            .init_asset_loader::<EnemyArchetypeLoader>()
+            .init_resource::<EnemyArchetypeRegistry>()
+            .init_resource::<NavigationGrid>()
+            .add_event::<EnemyDefeatedEvent>()
             .add_event::<EnemyAttackHitEvent>()
             .add_event::<BossWizardSpellCastEvent>()
+            .add_event::<BossPhaseChangedEvent>()
+            .add_systems(Startup, load_enemy_archetypes)
             .add_systems(
                 Update,
                 (
-                    mimic_ai_system,
-                    slime_ai_system,
-                    spider_ai_system,
-                    spider_ranged_attack_system,
-                    cyclops_ai_system,
-                    boss_wizard_ai_system,
-                    boss_wizard_projectile_system,
-                    spider_web_projectile_system,
-                    enemy_contact_attack_system,
-                    despawn_dead_enemies_system.after(player_melee_attack_system),
-                    reset_enemies_on_player_respawn
-                        .after(player_respawn_system)
-                        .before(enemy_death_effect_system),
-                    enemy_death_effect_system.after(despawn_dead_enemies_system),
+                    apply_loaded_enemy_archetypes,
+                    (
+                        rebuild_navigation_grid_system.after(sync_tile_map_system),
+                        chaser_ai_system.after(rebuild_navigation_grid_system),
+                        web_shooter_ai_system,
+                        web_shooter_attack_system,
+                        charger_ai_system,
+                        spell_caster_ai_system,
+                        boss_phase_system.after(resolve_damage_requests),
+                        boss_pattern_system
+                            .after(boss_phase_system)
+                            .after(spell_caster_ai_system),
+                        boss_telegraph_pulse_system.after(boss_pattern_system),
+                        boss_defeat_cleanup_system.after(despawn_dead_enemies_system),
+                        boss_wizard_projectile_system.after(move_projectiles_system),
+                        spider_web_projectile_system.after(move_projectiles_system),
+                        enemy_contact_attack_system,
+                        despawn_dead_enemies_system.after(resolve_damage_requests),
+                        reset_enemies_on_player_respawn
+                            .after(player_respawn_system)
+                            .before(enemy_death_effect_system),
+                        enemy_death_effect_system.after(despawn_dead_enemies_system),
+                    )
+                        .in_set(GameplaySet),
                 ),
             );
     }
@@ -17,5 +17,11 @@ impl Plugin for EquipmentPlugin {
                 .after(chest_item_reveal_system)
                 .after(player_pickup_detection_system),
         );
+        app.add_event::<RangedWeaponEquipEvent>().add_systems(
+            Update,
+            handle_ranged_weapon_equip_events
+                .after(chest_item_reveal_system)
+                .after(player_pickup_detection_system),
+        );
     }
 }
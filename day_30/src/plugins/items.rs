@@ -1,3 +1,4 @@
+use crate::resources::GameplaySet;
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -7,6 +8,6 @@ impl Plugin for ItemPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PlayerPickupEvent>()
             .add_systems(PostStartup, spawn_random_pickups)
-            .add_systems(Update, player_pickup_detection_system);
+            .add_systems(Update, player_pickup_detection_system.in_set(GameplaySet));
     }
 }
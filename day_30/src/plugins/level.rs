@@ -1,7 +1,17 @@
-use crate::resources::{LevelBuildContext, LevelExitAssets, LevelState, PendingLevelRewards};
+use crate::resources::{
+    Encounter, GameplaySet, LevelBuildContext, LevelCampaign, LevelCampaignLoader, LevelExitAssets,
+    LevelState, PendingLevelRewards, PendingWorldRestore,
+};
 use crate::systems::level::{
-    finalize_level_load, handle_level_requests, process_level_layout, schedule_initial_level,
-    spawn_rewards_on_boss_defeat,
+    apply_loaded_level_campaign, finalize_level_load, handle_level_requests, load_level_campaign,
+    process_level_layout, schedule_initial_level, spawn_rewards_on_boss_defeat,
+    update_data_error_screen,
+};
+use crate::systems::world_snapshot::restore_world_snapshot;
+use crate::systems::{
+    apply_loaded_enemy_archetypes, door_interaction_system, encounter_telegraph_system,
+    encounter_trigger_system, encounter_unlock_system, encounter_wave_system,
+    reset_encounter_on_player_respawn, sync_tile_map_system,
 };
 use bevy::prelude::*;
 
@@ -9,17 +19,49 @@ pub struct LevelPlugin;
 
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<LevelState>()
+        app.init_asset::<LevelCampaign>()
+            .init_asset_loader::<LevelCampaignLoader>()
+            .init_resource::<LevelState>()
             .init_resource::<LevelBuildContext>()
             .init_resource::<PendingLevelRewards>()
+            .init_resource::<PendingWorldRestore>()
+            .init_resource::<Encounter>()
             .add_event::<crate::systems::level::LevelAdvanceRequestEvent>()
             .add_event::<crate::systems::level::LevelLoadedEvent>()
-            .add_systems(Startup, initialize_level_exit_assets)
+            .add_systems(Startup, (initialize_level_exit_assets, load_level_campaign))
             .add_systems(PostStartup, schedule_initial_level)
-            .add_systems(Update, handle_level_requests)
+            .add_systems(Update, apply_loaded_level_campaign)
+            .add_systems(
+                Update,
+                handle_level_requests.after(apply_loaded_level_campaign),
+            )
             .add_systems(Update, process_level_layout.after(handle_level_requests))
+            .add_systems(
+                Update,
+                update_data_error_screen
+                    .after(apply_loaded_level_campaign)
+                    .after(apply_loaded_enemy_archetypes),
+            )
             .add_systems(PostUpdate, finalize_level_load)
-            .add_systems(Update, spawn_rewards_on_boss_defeat);
+            .add_systems(
+                PostUpdate,
+                restore_world_snapshot.after(finalize_level_load),
+            )
+            .add_systems(Update, spawn_rewards_on_boss_defeat.in_set(GameplaySet))
+            .add_systems(
+                Update,
+                (
+                    reset_encounter_on_player_respawn,
+                    encounter_trigger_system,
+                    encounter_wave_system,
+                    encounter_telegraph_system,
+                    encounter_unlock_system,
+                )
+                    .chain()
+                    .after(door_interaction_system)
+                    .before(sync_tile_map_system)
+                    .in_set(GameplaySet),
+            );
     }
 }
 
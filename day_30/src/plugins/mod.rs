@@ -2,6 +2,9 @@ pub mod attack;
 pub mod audio;
 pub mod camera;
 pub mod chest;
+pub mod combat_text;
+pub mod controls;
+pub mod damage;
 pub mod door_interaction;
 pub mod effects;
 pub mod enemy;
@@ -10,8 +13,10 @@ pub mod items;
 pub mod level;
 pub mod player;
 pub mod progression;
+pub mod projectile;
 pub mod room_transition;
 pub mod session;
+pub mod status_effects;
 pub mod ui;
 pub mod wall_collision;
 pub mod world;
@@ -20,6 +25,9 @@ pub use attack::*;
 pub use audio::*;
 pub use camera::*;
 pub use chest::*;
+pub use combat_text::*;
+pub use controls::*;
+pub use damage::*;
 pub use door_interaction::*;
 pub use effects::*;
 pub use enemy::*;
@@ -28,8 +36,10 @@ pub use items::*;
 pub use level::*;
 pub use player::*;
 pub use progression::*;
+pub use projectile::*;
 pub use room_transition::*;
 pub use session::*;
+pub use status_effects::*;
 pub use ui::*;
 pub use wall_collision::*;
 pub use world::*;
@@ -1,4 +1,4 @@
-use crate::resources::PlayerDeathState;
+use crate::resources::{GameplaySet, PlayerDeathState};
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -9,27 +9,37 @@ impl Plugin for PlayerPlugin {
         app.init_resource::<PlayerDeathState>()
             .add_event::<PlayerDamagedEvent>()
             .add_event::<PlayerPoisonDamageEvent>()
+            .add_event::<PlayerHealedEvent>()
             .add_event::<PlayerDiedEvent>()
             .add_event::<PlayerRespawnedEvent>()
+            .add_event::<DodgeInputEvent>()
+            .add_event::<PlayerParryEvent>()
             .add_systems(Startup, setup)
             .add_systems(PostStartup, spawn_player)
             .add_systems(
                 Update,
                 (
                     movement_system,
-                    player_poison_tick_system,
+                    dodge_input_system.after(input_system),
+                    dodge_roll_system
+                        .after(dodge_input_system)
+                        .after(movement_system)
+                        .before(wall_collision_system),
+                    block_input_system.before(movement_system),
+                    parry_stagger_system.after(resolve_damage_requests),
                     player_stamina_regen_system,
-                    health_system,
-                    start_player_death_sequence_system.after(health_system),
+                    start_player_death_sequence_system.after(resolve_damage_requests),
                     player_respawn_system.after(start_player_death_sequence_system),
-                ),
+                )
+                    .in_set(GameplaySet),
             )
             .add_systems(
                 PostUpdate,
                 (
                     trigger_player_damage_flash_system,
                     player_damage_flash_tick_system,
-                ),
+                )
+                    .in_set(GameplaySet),
             );
     }
 }
@@ -1,3 +1,4 @@
+use crate::resources::GameplaySet;
 use crate::systems::*;
 use bevy::prelude::*;
 
@@ -10,7 +11,8 @@ impl Plugin for ProgressionPlugin {
             (
                 apply_enemy_experience_rewards.after(despawn_dead_enemies_system),
                 apply_player_level_up_effects.after(apply_enemy_experience_rewards),
-            ),
+            )
+                .in_set(GameplaySet),
         );
     }
 }
@@ -0,0 +1,22 @@
+use crate::resources::GameplaySet;
+use crate::systems::*;
+use bevy::prelude::*;
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RangedAttackInputEvent>().add_systems(
+            Update,
+            (
+                homing_projectile_system.before(move_projectiles_system),
+                move_projectiles_system.after(sync_tile_map_system),
+                player_ranged_attack_system
+                    .after(input_system)
+                    .after(update_attack_reticle_system),
+                player_projectile_hit_system.after(move_projectiles_system),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
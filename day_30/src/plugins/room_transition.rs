@@ -1,3 +1,4 @@
+use crate::resources::GameplaySet;
 use crate::systems::{TransitionCooldown, room_transition_system};
 use bevy::prelude::*;
 
@@ -6,6 +7,6 @@ pub struct RoomTransitionPlugin;
 impl Plugin for RoomTransitionPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TransitionCooldown>()
-            .add_systems(Update, room_transition_system);
+            .add_systems(Update, room_transition_system.in_set(GameplaySet));
     }
 }
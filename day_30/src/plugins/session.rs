@@ -1,9 +1,15 @@
-use crate::resources::GameSession;
+use crate::resources::{GamePhase, GameSession, GamepadMenuFocus, GameplaySet, SaveStorage};
 use crate::systems::game_session::{
     MenuClickEvent, RequestLoadGameEvent, RequestSaveGameEvent, ResumeGameplayEvent,
-    StartNewGameEvent, activate_gameplay_after_start, handle_main_menu_interactions,
-    handle_pause_menu_interactions, process_load_game_requests, process_save_game_requests,
-    resume_gameplay, spawn_main_menu, toggle_pause_menu_on_escape,
+    StartEndlessRunEvent, StartNewGameEvent, activate_gameplay_after_start,
+    handle_main_menu_interactions, handle_pause_menu_interactions, pause_virtual_time,
+    process_load_game_requests, process_save_game_requests, resume_gameplay, resume_virtual_time,
+    spawn_main_menu, start_endless_run, toggle_pause_menu_on_escape, track_playtime,
+};
+use crate::systems::gamepad::navigate_menus_with_gamepad;
+use crate::systems::save_slots::{
+    DeleteSaveSlotEvent, OpenSaveSlotPickerEvent, SaveSlotErrorEvent, SaveSlotsChangedEvent,
+    handle_save_slot_interactions, process_delete_save_requests, update_save_slot_picker,
 };
 use bevy::prelude::*;
 
@@ -11,29 +17,55 @@ pub struct SessionPlugin;
 
 impl Plugin for SessionPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<GameSession>()
+        app.init_state::<GamePhase>()
+            .configure_sets(Update, GameplaySet.run_if(in_state(GamePhase::Playing)))
+            .configure_sets(PostUpdate, GameplaySet.run_if(in_state(GamePhase::Playing)))
+            .init_resource::<GameSession>()
+            .init_resource::<SaveStorage>()
+            .init_resource::<GamepadMenuFocus>()
             .add_event::<StartNewGameEvent>()
+            .add_event::<StartEndlessRunEvent>()
             .add_event::<RequestLoadGameEvent>()
             .add_event::<RequestSaveGameEvent>()
             .add_event::<ResumeGameplayEvent>()
             .add_event::<MenuClickEvent>()
+            .add_event::<OpenSaveSlotPickerEvent>()
+            .add_event::<DeleteSaveSlotEvent>()
+            .add_event::<SaveSlotsChangedEvent>()
+            .add_event::<SaveSlotErrorEvent>()
             .add_systems(Startup, spawn_main_menu)
+            .add_systems(OnEnter(GamePhase::MainMenu), pause_virtual_time)
+            .add_systems(OnEnter(GamePhase::Paused), pause_virtual_time)
+            .add_systems(OnEnter(GamePhase::Playing), resume_virtual_time)
             .add_systems(
                 Update,
                 (
+                    navigate_menus_with_gamepad
+                        .before(handle_main_menu_interactions)
+                        .before(handle_pause_menu_interactions)
+                        .before(handle_save_slot_interactions),
                     handle_main_menu_interactions,
                     activate_gameplay_after_start.after(handle_main_menu_interactions),
+                    start_endless_run.after(handle_main_menu_interactions),
                     handle_pause_menu_interactions,
-                    process_save_game_requests.after(handle_pause_menu_interactions),
+                    handle_save_slot_interactions,
+                    process_save_game_requests.after(handle_save_slot_interactions),
+                    process_delete_save_requests.after(handle_save_slot_interactions),
                     process_load_game_requests
+                        .after(handle_save_slot_interactions)
+                        .after(process_save_game_requests),
+                    update_save_slot_picker
                         .after(handle_main_menu_interactions)
                         .after(handle_pause_menu_interactions)
-                        .after(process_save_game_requests),
+                        .after(process_save_game_requests)
+                        .after(process_delete_save_requests)
+                        .before(process_load_game_requests),
                     toggle_pause_menu_on_escape,
                     resume_gameplay
                         .after(handle_pause_menu_interactions)
                         .after(toggle_pause_menu_on_escape)
                         .after(process_load_game_requests),
+                    track_playtime.in_set(GameplaySet),
                 ),
             );
     }
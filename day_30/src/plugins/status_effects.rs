@@ -0,0 +1,18 @@
+use crate::resources::GameplaySet;
+use crate::systems::*;
+use bevy::prelude::*;
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StatusEffectRequest>().add_systems(
+            Update,
+            (
+                tick_status_effects_system.before(resolve_damage_requests),
+                apply_status_effect_requests.after(resolve_damage_requests),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
@@ -1,5 +1,6 @@
+use crate::resources::GameplaySet;
 use crate::systems::{
-    enforce_world_bounds_system,
+    enforce_world_bounds_system, sync_tile_map_system,
     wall_collision::{enemy_wall_collision_system, wall_collision_system},
 };
 use bevy::prelude::*;
@@ -13,7 +14,9 @@ impl Plugin for WallCollisionPlugin {
             (
                 wall_collision_system.after(enforce_world_bounds_system),
                 enemy_wall_collision_system.after(enforce_world_bounds_system),
-            ),
+            )
+                .after(sync_tile_map_system)
+                .in_set(GameplaySet),
         );
     }
 }
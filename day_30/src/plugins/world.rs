@@ -1,21 +1,30 @@
-use crate::resources::{environment_assets::EnvironmentAssets, room_assets::RoomAssets};
-use crate::systems::{movement_system, world::*};
+use crate::resources::{
+    GameplaySet, TileMap, environment_assets::EnvironmentAssets, room_assets::RoomAssets,
+};
+use crate::systems::{door_interaction_system, movement_system, sync_tile_map_system, world::*};
 use bevy::prelude::*;
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Startup,
-            (
-                initialize_room_assets,
-                initialize_environment_assets,
-                spawn_world_floor_and_bounds,
+        app.init_resource::<TileMap>()
+            .add_systems(
+                Startup,
+                (
+                    initialize_room_assets,
+                    initialize_environment_assets,
+                    spawn_world_floor_and_bounds,
+                )
+                    .chain(),
             )
-                .chain(),
-        )
-        .add_systems(Update, enforce_world_bounds_system.after(movement_system));
+            .add_systems(
+                Update,
+                enforce_world_bounds_system
+                    .after(movement_system)
+                    .in_set(GameplaySet),
+            )
+            .add_systems(Update, sync_tile_map_system.after(door_interaction_system));
     }
 }
 
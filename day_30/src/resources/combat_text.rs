@@ -0,0 +1,42 @@
+use crate::constants::COMBAT_TEXT_POOL_SIZE;
+use bevy::prelude::*;
+
+/// 浮動戰鬥文字的實體池；數量到上限後依序重複使用最舊的文字
+#[derive(Resource, Default)]
+pub struct CombatTextPool {
+    entities: Vec<Entity>,
+    next: usize,
+    pub font: Handle<Font>,
+}
+
+impl CombatTextPool {
+    pub fn new(font: Handle<Font>) -> Self {
+        Self {
+            entities: Vec::with_capacity(COMBAT_TEXT_POOL_SIZE),
+            next: 0,
+            font,
+        }
+    }
+
+    /// 池還沒滿時回傳 None，呼叫端應生成新的實體並交給 `register`
+    pub fn acquire(&mut self) -> Option<Entity> {
+        if self.entities.len() < COMBAT_TEXT_POOL_SIZE {
+            return None;
+        }
+
+        let entity = self.entities[self.next];
+        self.next = (self.next + 1) % self.entities.len();
+        Some(entity)
+    }
+
+    pub fn register(&mut self, entity: Entity) {
+        self.entities.push(entity);
+    }
+
+    /// 實體已不存在（例如被整批清除）時換成新生成的實體
+    pub fn replace(&mut self, old: Entity, new: Entity) {
+        if let Some(slot) = self.entities.iter_mut().find(|entity| **entity == old) {
+            *slot = new;
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::resources::{EncounterPlan, EncounterWave};
+use bevy::prelude::*;
+use std::collections::HashSet;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncounterStage {
+    /// 等待玩家走進房間
+    #[default]
+    Dormant,
+    /// 門已上鎖，敵人分波出現中
+    Active,
+    /// 最後一波已全滅，門已解鎖
+    Cleared,
+}
+
+/// 目前關卡的鎖門遭遇戰進度，換關時重建
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Encounter {
+    plan: Option<EncounterPlan>,
+    /// 觸發範圍，也是敵人出現的範圍（瓷磚座標）
+    area: HashSet<(i32, i32)>,
+    seed: u64,
+    stage: EncounterStage,
+    next_wave: usize,
+    wave_delay: Timer,
+}
+
+impl Encounter {
+    pub fn new(plan: EncounterPlan, area: HashSet<(i32, i32)>, seed: u64) -> Self {
+        Self {
+            plan: Some(plan),
+            area,
+            seed,
+            ..Default::default()
+        }
+    }
+
+    pub fn stage(&self) -> EncounterStage {
+        self.stage
+    }
+
+    pub fn contains(&self, cell: (i32, i32)) -> bool {
+        self.area.contains(&cell)
+    }
+
+    pub fn area(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.area.iter().copied()
+    }
+
+    pub fn telegraph_seconds(&self) -> f32 {
+        self.plan
+            .as_ref()
+            .map_or(0.0, |plan| plan.telegraph_seconds)
+    }
+
+    /// 每一波各自的亂數種子，讓同一關的出生點固定
+    pub fn wave_seed(&self) -> u64 {
+        self.seed.wrapping_add(self.next_wave as u64)
+    }
+
+    pub fn should_trigger(&self, player_cell: (i32, i32)) -> bool {
+        self.plan.is_some() && self.stage == EncounterStage::Dormant && self.contains(player_cell)
+    }
+
+    pub fn begin(&mut self) {
+        self.stage = EncounterStage::Active;
+        self.next_wave = 0;
+        self.reset_wave_delay();
+    }
+
+    pub fn has_remaining_waves(&self) -> bool {
+        self.plan
+            .as_ref()
+            .is_some_and(|plan| self.next_wave < plan.waves.len())
+    }
+
+    /// 倒數下一波的等待時間，時間到時回傳該波；真的放出後才呼叫 `advance_wave`
+    pub fn tick_next_wave(&mut self, delta: Duration) -> Option<EncounterWave> {
+        if self.stage != EncounterStage::Active || !self.has_remaining_waves() {
+            return None;
+        }
+
+        self.wave_delay.tick(delta);
+        if !self.wave_delay.finished() {
+            return None;
+        }
+
+        self.plan
+            .as_ref()
+            .and_then(|plan| plan.waves.get(self.next_wave))
+            .cloned()
+    }
+
+    /// 目前這一波已經放出，換成下一波的倒數
+    pub fn advance_wave(&mut self) {
+        self.next_wave += 1;
+        self.reset_wave_delay();
+    }
+
+    pub fn clear(&mut self) {
+        self.stage = EncounterStage::Cleared;
+    }
+
+    /// 回到等待玩家進房的狀態，下次觸發從第一波開始
+    pub fn reset(&mut self) {
+        self.stage = EncounterStage::Dormant;
+        self.next_wave = 0;
+        self.reset_wave_delay();
+    }
+
+    fn reset_wave_delay(&mut self) {
+        let delay = self
+            .plan
+            .as_ref()
+            .and_then(|plan| plan.waves.get(self.next_wave))
+            .map_or(0.0, |wave| wave.delay_seconds);
+        self.wave_delay = Timer::from_seconds(delay, TimerMode::Once);
+    }
+}
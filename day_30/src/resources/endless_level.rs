@@ -0,0 +1,211 @@
+use super::level::{EnemyCounts, LevelDefinition, PropPlan, RoomLayout};
+use crate::components::world::{CompoundRoomType, RoomRect};
+use crate::components::{PickupEffect, RangedWeaponKind, ShieldKind, WeaponFamily, WeaponKind};
+use crate::constants::*;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+/// 無盡模式的關卡產生器：同一個 run seed 在同一深度永遠產生相同的關卡
+#[derive(Debug, Clone, Copy)]
+pub struct EndlessLevelGenerator {
+    run_seed: u64,
+}
+
+impl EndlessLevelGenerator {
+    pub fn new(run_seed: u64) -> Self {
+        Self { run_seed }
+    }
+
+    pub fn run_seed(&self) -> u64 {
+        self.run_seed
+    }
+
+    pub fn level_seed(&self, depth: usize) -> u64 {
+        self.run_seed ^ (depth as u64 + 1).wrapping_mul(ENDLESS_DEPTH_SEED_STRIDE)
+    }
+
+    pub fn generate(&self, depth: usize) -> LevelDefinition {
+        let seed = self.level_seed(depth);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let growth = (depth / ENDLESS_ROOM_GROWTH_INTERVAL).min(ENDLESS_MAX_ROOM_GROWTH_TILES);
+
+        LevelDefinition {
+            index: depth,
+            name: format!("Endless Depth {}", depth + 1),
+            layout: generate_layout(&mut rng, growth),
+            enemy_counts: scaled_enemy_counts(&mut rng, depth),
+            prop_plan: scaled_prop_plan(&mut rng, depth),
+            seed,
+            boss_loot: endless_boss_loot(depth),
+            boss_stat_multiplier: endless_boss_multiplier(depth),
+            encounter: None,
+        }
+    }
+}
+
+/// 進行中的無盡模式：保留已產生過的關卡，讓存檔與傳送門可以用索引找回
+#[derive(Debug, Clone)]
+pub struct EndlessRun {
+    generator: EndlessLevelGenerator,
+    levels: Vec<LevelDefinition>,
+}
+
+impl EndlessRun {
+    pub fn new(run_seed: u64) -> Self {
+        let mut run = Self {
+            generator: EndlessLevelGenerator::new(run_seed),
+            levels: Vec::new(),
+        };
+        run.ensure_depth(0);
+        run
+    }
+
+    pub fn run_seed(&self) -> u64 {
+        self.generator.run_seed()
+    }
+
+    pub fn levels(&self) -> &[LevelDefinition] {
+        &self.levels
+    }
+
+    pub fn ensure_depth(&mut self, depth: usize) {
+        while self.levels.len() <= depth {
+            let next = self.generator.generate(self.levels.len());
+            self.levels.push(next);
+        }
+    }
+}
+
+fn generate_layout(rng: &mut StdRng, growth: usize) -> RoomLayout {
+    // 隨深度只放大不會撞到世界邊界的那一邊，整個房間維持在 WORLD_HALF_*_TILES 之內
+    let half_growth = growth / 2;
+
+    // 複合房間沿用原本關卡的排列方式，只調整尺寸，確保走廊與入口門能正確生成
+    match rng.gen_range(0..4) {
+        0 => RoomLayout::Rectangle {
+            width: rng.gen_range(12..=16) + growth,
+            height: rng.gen_range(9..=12) + growth,
+        },
+        1 => {
+            let main_width = rng.gen_range(8..=10);
+            let main_height = rng.gen_range(11..=13) + growth;
+            let main = RoomRect {
+                x: -(main_width as i32) / 2 - 2,
+                y: -(main_height as i32) / 2,
+                width: main_width,
+                height: main_height,
+            };
+            let extension = RoomRect {
+                x: main.x + main_width as i32 - 3,
+                y: main.y + 4,
+                width: rng.gen_range(8..=10),
+                height: rng.gen_range(8..=10) + growth,
+            };
+            RoomLayout::Compound {
+                room_type: CompoundRoomType::LShape,
+                rectangles: vec![main, extension],
+            }
+        }
+        2 => {
+            let beam_width = rng.gen_range(12..=16) + growth;
+            let beam_height = rng.gen_range(5..=7);
+            let pillar_width = rng.gen_range(5..=7);
+            let pillar_height = rng.gen_range(10..=12) + half_growth;
+            let beam = RoomRect {
+                x: -(beam_width as i32) / 2,
+                y: 1,
+                width: beam_width,
+                height: beam_height,
+            };
+            let pillar = RoomRect {
+                x: -(pillar_width as i32) / 2,
+                y: beam.y + 4 - pillar_height as i32,
+                width: pillar_width,
+                height: pillar_height,
+            };
+            RoomLayout::Compound {
+                room_type: CompoundRoomType::TShape,
+                rectangles: vec![beam, pillar],
+            }
+        }
+        _ => {
+            let horizontal_width = rng.gen_range(14..=18) + growth;
+            let horizontal_height = rng.gen_range(5..=7);
+            let vertical_width = rng.gen_range(5..=7);
+            let vertical_height = rng.gen_range(13..=15) + half_growth;
+            let horizontal = RoomRect {
+                x: -(horizontal_width as i32) / 2,
+                y: -2,
+                width: horizontal_width,
+                height: horizontal_height,
+            };
+            let vertical = RoomRect {
+                x: -(vertical_width as i32) / 2,
+                y: -(vertical_height as i32) / 2 - 1,
+                width: vertical_width,
+                height: vertical_height,
+            };
+            RoomLayout::Compound {
+                room_type: CompoundRoomType::Cross,
+                rectangles: vec![horizontal, vertical],
+            }
+        }
+    }
+}
+
+fn scaled_enemy_counts(rng: &mut StdRng, depth: usize) -> EnemyCounts {
+    let mut counts = EnemyCounts::default();
+    counts.set(
+        ENDLESS_SLIME_ARCHETYPE_ID,
+        (ENDLESS_BASE_SLIMES + depth + rng.gen_range(0..=1)).min(ENDLESS_MAX_SLIMES),
+    );
+    counts.set(ENDLESS_CYCLOPS_ARCHETYPE_ID, depth.min(ENDLESS_MAX_CYCLOPS));
+    counts.set(
+        ENDLESS_SPIDER_ARCHETYPE_ID,
+        (depth / 2).min(ENDLESS_MAX_SPIDERS),
+    );
+    counts.set(ENDLESS_BOSS_ARCHETYPE_ID, 1);
+    counts
+}
+
+fn scaled_prop_plan(rng: &mut StdRng, depth: usize) -> PropPlan {
+    PropPlan {
+        trees: (3 + depth / 2 + rng.gen_range(0..=1)).min(ENDLESS_MAX_PROPS_PER_KIND),
+        rocks: (3 + depth / 2 + rng.gen_range(0..=1)).min(ENDLESS_MAX_PROPS_PER_KIND),
+        crates: (2 + depth / 3 + rng.gen_range(0..=1)).min(ENDLESS_MAX_PROPS_PER_KIND),
+    }
+}
+
+/// 菁英頭目（最終頭目強度）之後繼續隨深度變強，最多到 `ENDLESS_MAX_BOSS_MULTIPLIER`
+fn endless_boss_multiplier(depth: usize) -> f32 {
+    let extra_depth = depth.saturating_sub(ENDLESS_ELITE_BOSS_DEPTH) as f32;
+    (1.0 + extra_depth * ENDLESS_BOSS_GROWTH_PER_DEPTH).min(ENDLESS_MAX_BOSS_MULTIPLIER)
+}
+
+fn endless_boss_loot(depth: usize) -> Vec<PickupEffect> {
+    let (kind, family) = match depth {
+        0 => (WeaponKind::Level2, WeaponFamily::Dagger),
+        1 => (WeaponKind::Level3, WeaponFamily::Spear),
+        2 => (WeaponKind::Level4, WeaponFamily::Axe),
+        _ => (WeaponKind::Level5, WeaponFamily::Hammer),
+    };
+
+    let mut loot = vec![PickupEffect::EquipWeapon { kind, family }];
+    match depth {
+        0 => loot.push(PickupEffect::EquipShield(ShieldKind::Level1)),
+        1 => loot.push(PickupEffect::EquipRangedWeapon(RangedWeaponKind::Bow)),
+        2 => loot.push(PickupEffect::EquipRangedWeapon(
+            RangedWeaponKind::MagicStaff,
+        )),
+        3 => loot.push(PickupEffect::EquipShield(ShieldKind::Level2)),
+        _ => {
+            // 頭目越強掉越多藥水
+            let potions =
+                (1 + (depth - 4) / ENDLESS_BOSS_POTION_INTERVAL).min(ENDLESS_MAX_BOSS_POTIONS);
+            loot.extend(std::iter::repeat_n(
+                PickupEffect::Heal(ITEM_HEALTH_POTION_HEAL_AMOUNT),
+                potions,
+            ));
+        }
+    }
+    loot
+}
@@ -0,0 +1,447 @@
+use crate::components::{
+    BossAttack, BossPhase, PickupEffect, Resistances, StatusEffectKind, StatusEffectSpec,
+};
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// 敵人使用哪一套 AI，決定生成時掛上的行為元件
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub enum EnemyBehaviorKind {
+    /// 巡邏並在玩家靠近時追擊、近戰接觸傷害
+    Chaser,
+    /// 蓄力後直線衝撞
+    Charger {
+        windup_seconds: f32,
+        charge_seconds: f32,
+        cooldown_seconds: f32,
+    },
+    /// 在自己的走道上來回移動並吐出蛛網
+    WebShooter,
+    /// 保持距離施放法術彈
+    SpellCaster,
+}
+
+impl EnemyBehaviorKind {
+    pub fn deals_contact_damage(&self) -> bool {
+        matches!(
+            self,
+            EnemyBehaviorKind::Chaser | EnemyBehaviorKind::Charger { .. }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct EnemyBaseStats {
+    pub health: i32,
+    pub attack: i32,
+    pub defense: i32,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct EnemyMovement {
+    pub patrol_range: f32,
+    pub patrol_speed: f32,
+    pub chase_speed: f32,
+    #[serde(default)]
+    pub alert_radius: f32,
+    #[serde(default)]
+    pub leash_radius: f32,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct EnemyAttackProfile {
+    pub radius: f32,
+    pub cooldown_seconds: f32,
+}
+
+/// 依玩家目前的能力值決定強度（用於頭目）
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct EnemyPlayerScaling {
+    pub multiplier: f32,
+    pub final_level_multiplier: f32,
+}
+
+/// 掛在敵人身上的附屬圖像，例如法師的法杖
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemyAccessory {
+    pub sprite: String,
+    pub offset: [f32; 3],
+    pub scale: f32,
+}
+
+/// 重生補回的敵人要挑哪一條地板走道
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum FloorSpawnPreference {
+    LeftMost,
+    RightMost,
+    Center,
+}
+
+/// 玩家重生時若場上沒有這種敵人，會依這份設定補回一隻
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct EnemyRespawnSlot {
+    pub lane: FloorSpawnPreference,
+    /// 找不到夠寬的走道時，改用相對入口的位置（以格為單位）
+    #[serde(default)]
+    pub fallback_offset_tiles: [f32; 2],
+    /// 走道至少要有幾格寬
+    #[serde(default = "default_min_lane_tiles")]
+    pub min_lane_tiles: f32,
+}
+
+fn default_min_lane_tiles() -> f32 {
+    2.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeightedDrop {
+    /// None 代表這一格不掉落任何東西
+    pub effect: Option<PickupEffect>,
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub enum EnemyDropTable {
+    /// 從一般道具池隨機挑一個
+    #[default]
+    Random,
+    Weighted(Vec<WeightedDrop>),
+}
+
+impl EnemyDropTable {
+    pub fn roll<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        random_pickup: impl FnOnce(&mut R) -> PickupEffect,
+    ) -> Option<PickupEffect> {
+        match self {
+            EnemyDropTable::Random => Some(random_pickup(rng)),
+            EnemyDropTable::Weighted(entries) => entries
+                .choose_weighted(rng, |entry| entry.weight)
+                .ok()
+                .and_then(|entry| entry.effect.clone()),
+        }
+    }
+}
+
+/// 一種敵人的完整資料定義，由 `assets/enemies/` 載入
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemyArchetype {
+    pub id: String,
+    pub display_name: String,
+    pub sprite: String,
+    pub scale: f32,
+    pub stats: EnemyBaseStats,
+    pub experience: u32,
+    pub behavior: EnemyBehaviorKind,
+    pub movement: EnemyMovement,
+    pub attack: EnemyAttackProfile,
+    #[serde(default)]
+    pub drops: EnemyDropTable,
+    /// 擊倒後會觸發關卡獎勵與出口傳送門
+    #[serde(default)]
+    pub boss: bool,
+    #[serde(default)]
+    pub player_scaling: Option<EnemyPlayerScaling>,
+    #[serde(default)]
+    pub accessory: Option<EnemyAccessory>,
+    #[serde(default)]
+    pub resistances: Resistances,
+    /// 擊退重量，越重越難推動
+    #[serde(default = "default_knockback_weight")]
+    pub weight: f32,
+    /// 攻擊命中時附加的狀態異常
+    #[serde(default)]
+    pub inflicts: Vec<StatusEffectSpec>,
+    #[serde(default)]
+    pub immunities: Vec<StatusEffectKind>,
+    /// 頭目的階段與招式腳本，依生命比例由高到低排列
+    #[serde(default)]
+    pub phases: Vec<BossPhase>,
+    /// 沒有設定的敵人不會在玩家重生時補回
+    #[serde(default)]
+    pub respawn: Option<EnemyRespawnSlot>,
+}
+
+fn default_knockback_weight() -> f32 {
+    1.0
+}
+
+impl EnemyArchetype {
+    /// 用於實體名稱，例如 "Wizard Boss" -> "WizardBoss"
+    pub fn name_tag(&self) -> String {
+        self.display_name.split_whitespace().collect()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("archetype id must not be empty".to_string());
+        }
+        if self.stats.health <= 0 {
+            return Err(format!("{} must have positive health", self.id));
+        }
+        if self.attack.cooldown_seconds <= 0.0 {
+            return Err(format!("{} must have a positive attack cooldown", self.id));
+        }
+        if matches!(&self.drops, EnemyDropTable::Weighted(entries) if entries.iter().all(|entry| entry.weight == 0))
+        {
+            return Err(format!("{} drop table has no positive weights", self.id));
+        }
+        if self.weight <= 0.0 {
+            return Err(format!("{} must have a positive weight", self.id));
+        }
+        if self.respawn.is_some_and(|slot| slot.min_lane_tiles < 0.0) {
+            return Err(format!(
+                "{} respawn lane width must not be negative",
+                self.id
+            ));
+        }
+        let resistances = self.resistances;
+        if [
+            resistances.physical,
+            resistances.magic,
+            resistances.poison,
+            resistances.fire,
+        ]
+        .iter()
+        .any(|value| !(0.0..=1.0).contains(value))
+        {
+            return Err(format!("{} resistances must be between 0 and 1", self.id));
+        }
+        if self
+            .inflicts
+            .iter()
+            .any(|spec| spec.duration_seconds <= 0.0 || spec.magnitude < 0.0)
+        {
+            return Err(format!(
+                "{} status effects need a positive duration and a non-negative magnitude",
+                self.id
+            ));
+        }
+        self.validate_phases()
+    }
+
+    fn validate_phases(&self) -> Result<(), String> {
+        if self
+            .phases
+            .first()
+            .is_some_and(|phase| phase.health_fraction < 1.0)
+        {
+            return Err(format!(
+                "{} first boss phase must start at full health",
+                self.id
+            ));
+        }
+        if self
+            .phases
+            .windows(2)
+            .any(|pair| pair[1].health_fraction >= pair[0].health_fraction)
+        {
+            return Err(format!(
+                "{} boss phases must be ordered by descending health fraction",
+                self.id
+            ));
+        }
+        for phase in &self.phases {
+            if !(phase.health_fraction > 0.0 && phase.health_fraction <= 1.0) {
+                return Err(format!(
+                    "{} phase {} health fraction must be between 0 and 1",
+                    self.id, phase.name
+                ));
+            }
+            if phase.pattern.is_empty() {
+                return Err(format!(
+                    "{} phase {} has an empty pattern",
+                    self.id, phase.name
+                ));
+            }
+            if phase.cooldown_seconds <= 0.0
+                || phase.telegraph_seconds < 0.0
+                || phase.speed_multiplier <= 0.0
+                || phase.damage_multiplier <= 0.0
+            {
+                return Err(format!(
+                    "{} phase {} needs a positive cooldown and multipliers",
+                    self.id, phase.name
+                ));
+            }
+            let empty_attack = phase.pattern.iter().any(|attack| match attack {
+                BossAttack::Spread { count, .. }
+                | BossAttack::HomingOrb { count, .. }
+                | BossAttack::Summon { count, .. } => *count == 0,
+                BossAttack::Bolt | BossAttack::Blink { .. } => false,
+            });
+            if empty_attack {
+                return Err(format!(
+                    "{} phase {} has an attack with zero count",
+                    self.id, phase.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 腳本中召喚的敵人 id
+    fn summoned_ids(&self) -> impl Iterator<Item = &str> {
+        self.phases
+            .iter()
+            .flat_map(|phase| &phase.pattern)
+            .filter_map(|attack| match attack {
+                BossAttack::Summon { archetype_id, .. } => Some(archetype_id.as_str()),
+                _ => None,
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EnemyArchetypeFile {
+    archetypes: Vec<EnemyArchetype>,
+}
+
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct EnemyArchetypeSet {
+    pub archetypes: Vec<EnemyArchetype>,
+}
+
+impl EnemyArchetypeSet {
+    /// 解析並驗證敵人資料檔的內容
+    pub fn from_json(bytes: &[u8]) -> Result<Self, EnemyDataError> {
+        let file: EnemyArchetypeFile =
+            serde_json::from_slice(bytes).map_err(EnemyDataError::Parse)?;
+
+        let mut seen = std::collections::HashSet::new();
+        for archetype in &file.archetypes {
+            archetype.validate().map_err(EnemyDataError::Invalid)?;
+            if !seen.insert(archetype.id.as_str()) {
+                return Err(EnemyDataError::Duplicate(archetype.id.clone()));
+            }
+        }
+
+        for archetype in &file.archetypes {
+            if let Some(missing) = archetype.summoned_ids().find(|id| !seen.contains(id)) {
+                return Err(EnemyDataError::Invalid(format!(
+                    "{} summons unknown archetype {missing}",
+                    archetype.id
+                )));
+            }
+        }
+
+        Ok(Self {
+            archetypes: file.archetypes,
+        })
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct EnemyArchetypeSetHandle(pub Handle<EnemyArchetypeSet>);
+
+/// 以 id 查詢敵人資料的登錄表
+#[derive(Resource, Debug, Clone, Default)]
+pub struct EnemyArchetypeRegistry {
+    archetypes: HashMap<String, EnemyArchetype>,
+    /// 有重生設定的敵人，依資料檔中的順序
+    respawn_roster: Vec<String>,
+    /// 敵人資料最後一次載入失敗的原因，成功載入後清除
+    load_error: Option<String>,
+}
+
+impl EnemyArchetypeRegistry {
+    pub fn get(&self, id: &str) -> Option<&EnemyArchetype> {
+        self.archetypes.get(id)
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        !self.archetypes.is_empty()
+    }
+
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
+    pub fn set_load_error(&mut self, error: Option<String>) {
+        self.load_error = error;
+    }
+
+    pub fn len(&self) -> usize {
+        self.archetypes.len()
+    }
+
+    pub fn is_boss(&self, id: &str) -> bool {
+        self.get(id).is_some_and(|archetype| archetype.boss)
+    }
+
+    /// 玩家重生時要補回的敵人與各自的重生設定
+    pub fn respawn_roster(&self) -> impl Iterator<Item = (&EnemyArchetype, EnemyRespawnSlot)> {
+        self.respawn_roster.iter().filter_map(|id| {
+            let archetype = self.get(id)?;
+            Some((archetype, archetype.respawn?))
+        })
+    }
+
+    pub fn replace(&mut self, archetypes: Vec<EnemyArchetype>) {
+        self.respawn_roster = archetypes
+            .iter()
+            .filter(|archetype| archetype.respawn.is_some())
+            .map(|archetype| archetype.id.clone())
+            .collect();
+        self.archetypes = archetypes
+            .into_iter()
+            .map(|archetype| (archetype.id.clone(), archetype))
+            .collect();
+    }
+}
+
+#[derive(Default)]
+pub struct EnemyArchetypeLoader;
+
+#[derive(Debug)]
+pub enum EnemyDataError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    Invalid(String),
+    Duplicate(String),
+}
+
+impl fmt::Display for EnemyDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnemyDataError::Io(error) => write!(f, "failed to read enemy data: {error}"),
+            EnemyDataError::Parse(error) => write!(f, "failed to parse enemy data: {error}"),
+            EnemyDataError::Invalid(message) => write!(f, "invalid enemy archetype: {message}"),
+            EnemyDataError::Duplicate(id) => write!(f, "duplicate enemy archetype id: {id}"),
+        }
+    }
+}
+
+impl std::error::Error for EnemyDataError {}
+
+impl AssetLoader for EnemyArchetypeLoader {
+    type Asset = EnemyArchetypeSet;
+    type Settings = ();
+    type Error = EnemyDataError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(EnemyDataError::Io)?;
+
+        EnemyArchetypeSet::from_json(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["enemies.json"]
+    }
+}
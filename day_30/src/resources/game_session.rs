@@ -1,38 +1,60 @@
+use super::input_bindings::InputAction;
 use bevy::prelude::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum GamePhase {
+    #[default]
     MainMenu,
     Playing,
     Paused,
 }
 
-impl Default for GamePhase {
-    fn default() -> Self {
-        GamePhase::MainMenu
-    }
+/// 只在 `GamePhase::Playing` 時執行的遊戲邏輯，選單開著時整組都不會跑
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
+/// 存檔欄位選單的用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveSlotPickerMode {
+    Save,
+    Load { from_main_menu: bool },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SaveSlotPicker {
+    pub root: Entity,
+    pub mode: SaveSlotPickerMode,
+}
+
+/// 操作設定選單，`awaiting` 為正在等待新按鍵的動作
+#[derive(Debug, Clone, Copy)]
+pub struct ControlsMenu {
+    pub root: Entity,
+    pub awaiting: Option<InputAction>,
 }
 
 #[derive(Resource, Debug, Default)]
 pub struct GameSession {
-    phase: GamePhase,
+    playtime_seconds: f64,
     pub main_menu_root: Option<Entity>,
     pub pause_menu_root: Option<Entity>,
+    pub save_slot_picker: Option<SaveSlotPicker>,
+    pub controls_menu: Option<ControlsMenu>,
 }
 
 impl GameSession {
     pub const SAVE_DIRECTORY: &'static str = "saves";
-    pub const SAVE_SLOT_FILE: &'static str = "saves/slot1.json";
 
-    pub fn phase(&self) -> GamePhase {
-        self.phase
+    /// 本次遊玩累積的時間（暫停與主選單不計）
+    pub fn playtime_seconds(&self) -> f64 {
+        self.playtime_seconds
     }
 
-    pub fn set_phase(&mut self, phase: GamePhase) {
-        self.phase = phase;
+    pub fn set_playtime_seconds(&mut self, seconds: f64) {
+        self.playtime_seconds = seconds.max(0.0);
     }
 
-    pub fn is_playing(&self) -> bool {
-        matches!(self.phase, GamePhase::Playing)
+    pub fn add_playtime(&mut self, seconds: f64) {
+        self.playtime_seconds += seconds;
     }
 }
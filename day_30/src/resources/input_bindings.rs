@@ -0,0 +1,278 @@
+use crate::constants::{
+    GAMEPAD_ATTACK_BUTTON, GAMEPAD_BLOCK_BUTTON, GAMEPAD_DODGE_BUTTON, GAMEPAD_FIRE_BUTTON,
+    GAMEPAD_INTERACT_BUTTON, GAMEPAD_PAUSE_BUTTON,
+};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// 遊戲邏輯讀取的輸入動作，實際按鍵由 `InputBindings` 決定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Attack,
+    Fire,
+    Dodge,
+    Block,
+    Interact,
+    Pause,
+}
+
+impl InputAction {
+    /// 操作設定選單的排列順序
+    pub const ALL: [InputAction; 10] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Attack,
+        InputAction::Fire,
+        InputAction::Dodge,
+        InputAction::Block,
+        InputAction::Interact,
+        InputAction::Pause,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Move Up",
+            InputAction::MoveDown => "Move Down",
+            InputAction::MoveLeft => "Move Left",
+            InputAction::MoveRight => "Move Right",
+            InputAction::Attack => "Attack",
+            InputAction::Fire => "Fire",
+            InputAction::Dodge => "Dodge",
+            InputAction::Block => "Block",
+            InputAction::Interact => "Interact",
+            InputAction::Pause => "Pause",
+        }
+    }
+}
+
+/// 單一動作綁定的鍵盤按鍵與手把按鈕
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionBinding {
+    pub keys: Vec<KeyCode>,
+    pub gamepad: Option<GamepadButton>,
+}
+
+impl ActionBinding {
+    fn new(keys: &[KeyCode], gamepad: GamepadButton) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            gamepad: Some(gamepad),
+        }
+    }
+}
+
+/// 目前的按鍵綁定，會寫入設定檔
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputBindings {
+    actions: BTreeMap<InputAction, ActionBinding>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let actions = InputAction::ALL
+            .into_iter()
+            .map(|action| (action, Self::default_binding(action)))
+            .collect();
+        Self { actions }
+    }
+}
+
+impl InputBindings {
+    pub fn default_binding(action: InputAction) -> ActionBinding {
+        match action {
+            InputAction::MoveUp => {
+                ActionBinding::new(&[KeyCode::KeyW, KeyCode::ArrowUp], GamepadButton::DPadUp)
+            }
+            InputAction::MoveDown => ActionBinding::new(
+                &[KeyCode::KeyS, KeyCode::ArrowDown],
+                GamepadButton::DPadDown,
+            ),
+            InputAction::MoveLeft => ActionBinding::new(
+                &[KeyCode::KeyA, KeyCode::ArrowLeft],
+                GamepadButton::DPadLeft,
+            ),
+            InputAction::MoveRight => ActionBinding::new(
+                &[KeyCode::KeyD, KeyCode::ArrowRight],
+                GamepadButton::DPadRight,
+            ),
+            InputAction::Attack => {
+                ActionBinding::new(&[KeyCode::Space, KeyCode::KeyJ], GAMEPAD_ATTACK_BUTTON)
+            }
+            InputAction::Fire => ActionBinding::new(&[KeyCode::KeyF], GAMEPAD_FIRE_BUTTON),
+            InputAction::Dodge => {
+                ActionBinding::new(&[KeyCode::ShiftLeft, KeyCode::KeyK], GAMEPAD_DODGE_BUTTON)
+            }
+            InputAction::Block => {
+                ActionBinding::new(&[KeyCode::KeyQ, KeyCode::KeyL], GAMEPAD_BLOCK_BUTTON)
+            }
+            InputAction::Interact => ActionBinding::new(&[KeyCode::KeyE], GAMEPAD_INTERACT_BUTTON),
+            InputAction::Pause => ActionBinding::new(&[KeyCode::Escape], GAMEPAD_PAUSE_BUTTON),
+        }
+    }
+
+    pub fn binding(&self, action: InputAction) -> &ActionBinding {
+        self.actions
+            .get(&action)
+            .expect("every action has a binding")
+    }
+
+    /// 把動作改綁到單一按鍵；其他動作若用了同一個鍵會被移除，
+    /// 因此沒有按鍵可用時改用這個動作原本的按鍵
+    pub fn rebind_key(&mut self, action: InputAction, key: KeyCode) {
+        let Some(binding) = self.actions.get_mut(&action) else {
+            return;
+        };
+        let mut previous = std::mem::replace(&mut binding.keys, vec![key]);
+        previous.retain(|existing| *existing != key);
+
+        for (other, binding) in self.actions.iter_mut() {
+            if *other == action || !binding.keys.contains(&key) {
+                continue;
+            }
+            binding.keys.retain(|existing| *existing != key);
+            if binding.keys.is_empty() {
+                binding.keys = previous.clone();
+            }
+        }
+    }
+
+    /// 把動作改綁到手把按鈕，其他動作若用了同一個按鈕會換成這個動作原本的按鈕
+    pub fn rebind_gamepad(&mut self, action: InputAction, button: GamepadButton) {
+        let Some(binding) = self.actions.get_mut(&action) else {
+            return;
+        };
+        let previous = binding.gamepad.replace(button);
+
+        for (other, binding) in self.actions.iter_mut() {
+            if *other != action && binding.gamepad == Some(button) {
+                binding.gamepad = previous;
+            }
+        }
+    }
+
+    /// 設定檔缺少的動作（例如新版本加入的動作）補上預設綁定
+    fn fill_missing(mut self) -> Self {
+        for action in InputAction::ALL {
+            self.actions
+                .entry(action)
+                .or_insert_with(|| Self::default_binding(action));
+        }
+        self
+    }
+}
+
+/// 這個影格各動作的狀態，由鍵盤與手把依照綁定換算而來
+#[derive(Resource, Debug, Default)]
+pub struct ActionState {
+    pressed: HashSet<InputAction>,
+    just_pressed: HashSet<InputAction>,
+    move_axis: Vec2,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    /// 類比搖桿的移動輸入，沒有推動時為零
+    pub fn move_axis(&self) -> Vec2 {
+        self.move_axis
+    }
+
+    pub fn clear(&mut self) {
+        self.pressed.clear();
+        self.just_pressed.clear();
+        self.move_axis = Vec2::ZERO;
+    }
+
+    pub fn set(&mut self, action: InputAction, pressed: bool, just_pressed: bool) {
+        if pressed {
+            self.pressed.insert(action);
+        }
+        if just_pressed {
+            self.just_pressed.insert(action);
+        }
+    }
+
+    pub fn set_move_axis(&mut self, axis: Vec2) {
+        self.move_axis = axis;
+    }
+}
+
+/// 設定檔內容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSettings {
+    pub version: u32,
+    pub bindings: InputBindings,
+}
+
+impl GameSettings {
+    pub const CURRENT_VERSION: u32 = 1;
+}
+
+/// 設定檔的位置，測試時會改指向暫存資料夾
+#[derive(Resource, Debug, Clone)]
+pub struct SettingsStorage {
+    pub path: PathBuf,
+}
+
+impl SettingsStorage {
+    pub const DEFAULT_PATH: &'static str = "settings.json";
+
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 讀取按鍵綁定，沒有設定檔時回傳預設值
+    pub fn load_bindings(&self) -> Result<InputBindings, String> {
+        if !self.path.exists() {
+            return Ok(InputBindings::default());
+        }
+
+        let raw = fs::read_to_string(&self.path)
+            .map_err(|error| format!("failed to read settings file: {error}"))?;
+        let settings: GameSettings = serde_json::from_str(&raw)
+            .map_err(|error| format!("failed to parse settings file: {error}"))?;
+        Ok(settings.bindings.fill_missing())
+    }
+
+    pub fn write_bindings(&self, bindings: &InputBindings) -> Result<(), String> {
+        if let Some(directory) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(directory)
+                .map_err(|error| format!("failed to create settings directory: {error}"))?;
+        }
+
+        let settings = GameSettings {
+            version: GameSettings::CURRENT_VERSION,
+            bindings: bindings.clone(),
+        };
+        let serialized = serde_json::to_string_pretty(&settings)
+            .map_err(|error| format!("failed to serialize settings: {error}"))?;
+        fs::write(&self.path, serialized)
+            .map_err(|error| format!("failed to write settings file: {error}"))
+    }
+}
+
+impl Default for SettingsStorage {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_PATH)
+    }
+}
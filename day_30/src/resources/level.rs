@@ -1,37 +1,173 @@
+use crate::components::PickupEffect;
 use crate::components::world::{CompoundRoomType, RoomRect};
+use crate::constants::{
+    ENCOUNTER_DEFAULT_TELEGRAPH_SECONDS, ENCOUNTER_DEFAULT_TRIGGER_DEPTH_TILES,
+    LEVEL_MIN_ROOM_SPAN_TILES,
+};
+use crate::resources::EndlessRun;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone)]
+/// 單一關卡的資料定義，由 `assets/levels/` 底下的檔案載入
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LevelDefinition {
+    /// 由關卡清單的順序決定，資料檔不需要填寫
+    #[serde(default)]
     pub index: usize,
-    pub name: &'static str,
+    pub name: String,
     pub layout: RoomLayout,
     pub enemy_counts: EnemyCounts,
     pub prop_plan: PropPlan,
     pub seed: u64,
+    #[serde(default)]
+    pub boss_loot: Vec<PickupEffect>,
+    /// 套用在頭目能力上的額外倍率，無盡模式隨深度提高
+    #[serde(default = "default_boss_stat_multiplier")]
+    pub boss_stat_multiplier: f32,
+    /// 玩家走進房間後鎖門並分波生成敵人，沒有設定時敵人一開始就全部生成
+    #[serde(default)]
+    pub encounter: Option<EncounterPlan>,
 }
 
 impl LevelDefinition {
     pub fn enemy_total(&self) -> usize {
         self.enemy_counts.total()
     }
+
+    /// 檢查資料是否能被關卡生成流程使用，回傳第一個遇到的問題
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("level name must not be empty".to_string());
+        }
+
+        match &self.layout {
+            RoomLayout::Rectangle { width, height } => {
+                if *width < LEVEL_MIN_ROOM_SPAN_TILES || *height < LEVEL_MIN_ROOM_SPAN_TILES {
+                    return Err(format!(
+                        "rectangle layout must be at least {}x{} tiles (got {}x{})",
+                        LEVEL_MIN_ROOM_SPAN_TILES, LEVEL_MIN_ROOM_SPAN_TILES, width, height
+                    ));
+                }
+            }
+            RoomLayout::Compound { rectangles, .. } => {
+                if rectangles.len() != 2 {
+                    return Err(format!(
+                        "compound layout needs exactly 2 rectangles (got {})",
+                        rectangles.len()
+                    ));
+                }
+
+                for rect in rectangles {
+                    if rect.width < LEVEL_MIN_ROOM_SPAN_TILES
+                        || rect.height < LEVEL_MIN_ROOM_SPAN_TILES
+                    {
+                        return Err(format!(
+                            "compound rectangle at ({}, {}) must be at least {}x{} tiles",
+                            rect.x, rect.y, LEVEL_MIN_ROOM_SPAN_TILES, LEVEL_MIN_ROOM_SPAN_TILES
+                        ));
+                    }
+                }
+            }
+        }
+
+        if self.enemy_counts.iter().any(|(id, _)| id.trim().is_empty()) {
+            return Err("enemy_counts contains an empty archetype id".to_string());
+        }
+
+        if self.boss_stat_multiplier <= 0.0 {
+            return Err("boss_stat_multiplier must be positive".to_string());
+        }
+
+        if let Some(encounter) = &self.encounter {
+            encounter.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 鎖門遭遇戰：玩家踏上房間地板、離入口門超過 `trigger_depth_tiles` 格時觸發
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterPlan {
+    #[serde(default = "default_trigger_depth_tiles")]
+    pub trigger_depth_tiles: f32,
+    /// 敵人出現前預兆標記停留的秒數
+    #[serde(default = "default_encounter_telegraph_seconds")]
+    pub telegraph_seconds: f32,
+    pub waves: Vec<EncounterWave>,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct EnemyCounts {
-    pub slimes: usize,
-    pub cyclops: usize,
-    pub spiders: usize,
-    pub boss_wizards: usize,
+/// 前一波全滅（或觸發遭遇戰）後等待 `delay_seconds` 才出現的一波敵人
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterWave {
+    pub enemies: EnemyCounts,
+    #[serde(default)]
+    pub delay_seconds: f32,
 }
 
+impl EncounterPlan {
+    fn validate(&self) -> Result<(), String> {
+        if self.waves.is_empty() {
+            return Err("encounter needs at least one wave".to_string());
+        }
+        if self.trigger_depth_tiles < 0.0 || self.telegraph_seconds < 0.0 {
+            return Err(
+                "encounter trigger depth and telegraph time must not be negative".to_string(),
+            );
+        }
+
+        for (index, wave) in self.waves.iter().enumerate() {
+            if wave.enemies.total() == 0 {
+                return Err(format!("encounter wave {} has no enemies", index + 1));
+            }
+            if wave.enemies.iter().any(|(id, _)| id.trim().is_empty()) {
+                return Err(format!(
+                    "encounter wave {} contains an empty archetype id",
+                    index + 1
+                ));
+            }
+            if wave.delay_seconds < 0.0 {
+                return Err(format!("encounter wave {} has a negative delay", index + 1));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn default_boss_stat_multiplier() -> f32 {
+    1.0
+}
+
+fn default_trigger_depth_tiles() -> f32 {
+    ENCOUNTER_DEFAULT_TRIGGER_DEPTH_TILES
+}
+
+fn default_encounter_telegraph_seconds() -> f32 {
+    ENCOUNTER_DEFAULT_TELEGRAPH_SECONDS
+}
+
+/// 每種敵人（以 archetype id 表示）在關卡中的數量
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnemyCounts(BTreeMap<String, usize>);
+
 impl EnemyCounts {
     pub fn total(&self) -> usize {
-        self.slimes + self.cyclops + self.spiders + self.boss_wizards
+        self.0.values().sum()
+    }
+
+    pub fn set(&mut self, id: impl Into<String>, count: usize) {
+        self.0.insert(id.into(), count);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.0.iter().map(|(id, count)| (id.as_str(), *count))
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PropPlan {
     pub trees: usize,
     pub rocks: usize,
@@ -44,7 +180,7 @@ impl PropPlan {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RoomLayout {
     Rectangle {
         width: usize,
@@ -56,139 +192,13 @@ pub enum RoomLayout {
     },
 }
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Default)]
 pub struct LevelState {
     current_index: usize,
     definitions: Vec<LevelDefinition>,
-}
-
-impl Default for LevelState {
-    fn default() -> Self {
-        Self {
-            current_index: 0,
-            definitions: vec![
-                LevelDefinition {
-                    index: 0,
-                    name: "Verdant Approach",
-                    layout: RoomLayout::Rectangle {
-                        width: 14,
-                        height: 10,
-                    },
-                    enemy_counts: EnemyCounts {
-                        slimes: 6,
-                        cyclops: 0,
-                        spiders: 0,
-                        boss_wizards: 1,
-                    },
-                    prop_plan: PropPlan {
-                        trees: 4,
-                        rocks: 3,
-                        crates: 2,
-                    },
-                    seed: 11,
-                },
-                LevelDefinition {
-                    index: 1,
-                    name: "Crimson Concourse",
-                    layout: RoomLayout::Compound {
-                        room_type: CompoundRoomType::LShape,
-                        rectangles: vec![
-                            RoomRect {
-                                x: -6,
-                                y: -7,
-                                width: 9,
-                                height: 13,
-                            },
-                            RoomRect {
-                                x: 0,
-                                y: -3,
-                                width: 10,
-                                height: 9,
-                            },
-                        ],
-                    },
-                    enemy_counts: EnemyCounts {
-                        slimes: 6,
-                        cyclops: 4,
-                        spiders: 2,
-                        boss_wizards: 1,
-                    },
-                    prop_plan: PropPlan {
-                        trees: 3,
-                        rocks: 4,
-                        crates: 3,
-                    },
-                    seed: 27,
-                },
-                LevelDefinition {
-                    index: 2,
-                    name: "Saffron Crossroads",
-                    layout: RoomLayout::Compound {
-                        room_type: CompoundRoomType::TShape,
-                        rectangles: vec![
-                            RoomRect {
-                                x: -7,
-                                y: 1,
-                                width: 14,
-                                height: 6,
-                            },
-                            RoomRect {
-                                x: -3,
-                                y: -7,
-                                width: 6,
-                                height: 12,
-                            },
-                        ],
-                    },
-                    enemy_counts: EnemyCounts {
-                        slimes: 8,
-                        cyclops: 6,
-                        spiders: 3,
-                        boss_wizards: 1,
-                    },
-                    prop_plan: PropPlan {
-                        trees: 4,
-                        rocks: 5,
-                        crates: 4,
-                    },
-                    seed: 56,
-                },
-                LevelDefinition {
-                    index: 3,
-                    name: "Azure Sanctum",
-                    layout: RoomLayout::Compound {
-                        room_type: CompoundRoomType::Cross,
-                        rectangles: vec![
-                            RoomRect {
-                                x: -8,
-                                y: -2,
-                                width: 16,
-                                height: 6,
-                            },
-                            RoomRect {
-                                x: -3,
-                                y: -9,
-                                width: 6,
-                                height: 16,
-                            },
-                        ],
-                    },
-                    enemy_counts: EnemyCounts {
-                        slimes: 8,
-                        cyclops: 8,
-                        spiders: 4,
-                        boss_wizards: 1,
-                    },
-                    prop_plan: PropPlan {
-                        trees: 5,
-                        rocks: 5,
-                        crates: 4,
-                    },
-                    seed: 91,
-                },
-            ],
-        }
-    }
+    endless: Option<EndlessRun>,
+    /// 關卡資料最後一次載入失敗的原因，成功載入後清除
+    load_error: Option<String>,
 }
 
 impl LevelState {
@@ -197,23 +207,80 @@ impl LevelState {
     }
 
     pub fn definition_count(&self) -> usize {
-        self.definitions.len()
+        self.active_definitions().len()
     }
 
     pub fn definition(&self, index: usize) -> &LevelDefinition {
-        &self.definitions[index]
+        &self.active_definitions()[index]
+    }
+
+    pub fn get_definition(&self, index: usize) -> Option<&LevelDefinition> {
+        self.active_definitions().get(index)
     }
 
     pub fn next_index(&self) -> Option<usize> {
-        if self.current_index + 1 < self.definitions.len() {
+        // 無盡模式永遠有下一層
+        if self.endless.is_some() || self.current_index + 1 < self.definitions.len() {
             Some(self.current_index + 1)
         } else {
             None
         }
     }
 
+    pub fn is_loaded(&self) -> bool {
+        !self.active_definitions().is_empty()
+    }
+
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
+    pub fn set_load_error(&mut self, error: Option<String>) {
+        self.load_error = error;
+    }
+
+    pub fn is_endless(&self) -> bool {
+        self.endless.is_some()
+    }
+
+    pub fn endless_run_seed(&self) -> Option<u64> {
+        self.endless.as_ref().map(EndlessRun::run_seed)
+    }
+
+    /// 開始新的無盡模式，從第一層開始產生
+    pub fn start_endless(&mut self, run_seed: u64) {
+        self.endless = Some(EndlessRun::new(run_seed));
+        self.current_index = 0;
+    }
+
+    /// 回到資料檔定義的關卡流程
+    pub fn start_campaign(&mut self) {
+        self.endless = None;
+        self.set_current_index(0);
+    }
+
+    /// 換上新的關卡清單（例如資料檔重新載入），並保留目前所在的關卡
+    pub fn replace_definitions(&mut self, definitions: Vec<LevelDefinition>) {
+        self.definitions = definitions;
+        if self.endless.is_none() {
+            self.set_current_index(self.current_index);
+        }
+    }
+
     pub fn set_current_index(&mut self, index: usize) {
-        self.current_index = index.min(self.definitions.len().saturating_sub(1));
+        if let Some(run) = self.endless.as_mut() {
+            run.ensure_depth(index);
+            self.current_index = index;
+        } else {
+            self.current_index = index.min(self.definitions.len().saturating_sub(1));
+        }
+    }
+
+    fn active_definitions(&self) -> &[LevelDefinition] {
+        match &self.endless {
+            Some(run) => run.levels(),
+            None => &self.definitions,
+        }
     }
 }
 
@@ -0,0 +1,138 @@
+use super::level::LevelDefinition;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fmt;
+
+/// 關卡清單檔的內容：依序列出每一關的資料檔（相對於清單檔所在的資料夾）
+#[derive(Debug, Deserialize)]
+struct LevelCampaignManifest {
+    levels: Vec<String>,
+}
+
+/// 從 `assets/levels/` 載入並驗證過的完整關卡清單
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct LevelCampaign {
+    pub definitions: Vec<LevelDefinition>,
+}
+
+/// 保存關卡清單的 handle，避免資產被卸載，也用來辨識熱重載事件
+#[derive(Resource, Debug, Clone)]
+pub struct LevelCampaignHandle(pub Handle<LevelCampaign>);
+
+#[derive(Default)]
+pub struct LevelCampaignLoader;
+
+#[derive(Debug)]
+pub enum LevelDataError {
+    Io(std::io::Error),
+    ReadLevel {
+        path: String,
+        message: String,
+    },
+    Parse {
+        path: String,
+        error: serde_json::Error,
+    },
+    Invalid {
+        path: String,
+        message: String,
+    },
+    Empty,
+}
+
+impl fmt::Display for LevelDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelDataError::Io(error) => write!(f, "failed to read level campaign: {error}"),
+            LevelDataError::ReadLevel { path, message } => {
+                write!(f, "failed to read level file {path}: {message}")
+            }
+            LevelDataError::Parse { path, error } => {
+                write!(f, "failed to parse {path}: {error}")
+            }
+            LevelDataError::Invalid { path, message } => {
+                write!(f, "invalid level definition in {path}: {message}")
+            }
+            LevelDataError::Empty => write!(f, "level campaign does not list any levels"),
+        }
+    }
+}
+
+impl std::error::Error for LevelDataError {}
+
+impl AssetLoader for LevelCampaignLoader {
+    type Asset = LevelCampaign;
+    type Settings = ();
+    type Error = LevelDataError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let manifest_path = load_context.path().display().to_string();
+
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(LevelDataError::Io)?;
+
+        let manifest: LevelCampaignManifest =
+            serde_json::from_slice(&bytes).map_err(|error| LevelDataError::Parse {
+                path: manifest_path.clone(),
+                error,
+            })?;
+
+        if manifest.levels.is_empty() {
+            return Err(LevelDataError::Empty);
+        }
+
+        let mut definitions = Vec::with_capacity(manifest.levels.len());
+
+        for (index, entry) in manifest.levels.iter().enumerate() {
+            let level_path = load_context
+                .asset_path()
+                .resolve_embed(entry)
+                .map_err(|error| LevelDataError::ReadLevel {
+                    path: entry.clone(),
+                    message: error.to_string(),
+                })?;
+            let level_label = level_path.to_string();
+
+            // 透過 LoadContext 讀取，讓關卡檔被記錄為相依檔案，修改後會觸發熱重載
+            let level_bytes = load_context
+                .read_asset_bytes(level_path)
+                .await
+                .map_err(|error| LevelDataError::ReadLevel {
+                    path: level_label.clone(),
+                    message: error.to_string(),
+                })?;
+
+            let mut definition: LevelDefinition =
+                serde_json::from_slice(&level_bytes).map_err(|error| LevelDataError::Parse {
+                    path: level_label.clone(),
+                    error,
+                })?;
+
+            definition.index = index;
+            definition
+                .validate()
+                .map_err(|message| LevelDataError::Invalid {
+                    path: level_label,
+                    message,
+                })?;
+
+            definitions.push(definition);
+        }
+
+        Ok(LevelCampaign { definitions })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["campaign.json"]
+    }
+}
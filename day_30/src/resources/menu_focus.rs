@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+/// 以手把方向鍵瀏覽選單時，目前選到的按鈕
+#[derive(Resource, Debug, Default)]
+pub struct GamepadMenuFocus {
+    /// 目前瀏覽中的選單根節點，換成別的選單時重新從第一個按鈕開始
+    pub root: Option<Entity>,
+    pub index: usize,
+    pub focused: Option<Entity>,
+    /// 上一個影格以手把按下的按鈕，下一個影格放開
+    pub pressed: Option<Entity>,
+}
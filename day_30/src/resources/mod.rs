@@ -1,23 +1,45 @@
 pub mod camera_shake;
+pub mod combat_text;
+pub mod encounter;
+pub mod endless_level;
+pub mod enemy_archetypes;
 pub mod entrance_location;
 pub mod environment_assets;
 pub mod game_session;
+pub mod input_bindings;
 pub mod level;
+pub mod level_campaign;
 pub mod level_exit_assets;
+pub mod menu_focus;
+pub mod navigation;
 pub mod player_death_state;
 pub mod room_assets;
 pub mod save_data;
+pub mod save_migration;
 pub mod sound_effects;
+pub mod tile_map;
 pub mod world_bounds;
+pub mod world_snapshot;
 
 pub use camera_shake::*;
+pub use combat_text::*;
+pub use encounter::*;
+pub use endless_level::*;
+pub use enemy_archetypes::*;
 pub use entrance_location::*;
 pub use environment_assets::*;
 pub use game_session::*;
+pub use input_bindings::*;
 pub use level::*;
+pub use level_campaign::*;
 pub use level_exit_assets::*;
+pub use menu_focus::*;
+pub use navigation::*;
 pub use player_death_state::*;
 pub use room_assets::*;
 pub use save_data::*;
+pub use save_migration::*;
 pub use sound_effects::*;
+pub use tile_map::*;
 pub use world_bounds::*;
+pub use world_snapshot::*;
@@ -0,0 +1,187 @@
+use crate::constants::{NAV_BODY_HALF_WIDTH_TILES, NAV_MAX_SEARCH_NODES};
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// 直走與斜走一格的成本（放大十倍以整數計算）
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+const NEIGHBORS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// 以瓷磚為單位的行走網格，關卡的地板、牆、門或擋路擺設改變時重建
+#[derive(Resource, Debug, Clone, Default)]
+pub struct NavigationGrid {
+    tile_size: f32,
+    walkable: HashSet<(i32, i32)>,
+}
+
+impl NavigationGrid {
+    pub fn new(tile_size: f32, walkable: HashSet<(i32, i32)>) -> Self {
+        Self {
+            tile_size,
+            walkable,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.walkable.is_empty()
+    }
+
+    pub fn cell(&self, point: Vec2) -> (i32, i32) {
+        (
+            (point.x / self.tile_size).round() as i32,
+            (point.y / self.tile_size).round() as i32,
+        )
+    }
+
+    pub fn cell_center(&self, (x, y): (i32, i32)) -> Vec2 {
+        Vec2::new(x as f32, y as f32) * self.tile_size
+    }
+
+    pub fn is_walkable(&self, cell: (i32, i32)) -> bool {
+        self.walkable.contains(&cell)
+    }
+
+    /// 抄捷徑時預留的身體半寬，避免擦過牆角
+    pub fn body_half_width(&self) -> f32 {
+        self.tile_size * NAV_BODY_HALF_WIDTH_TILES
+    }
+
+    /// 兩點之間的直線是否只經過可行走的格子
+    pub fn has_clear_line(&self, from: Vec2, to: Vec2) -> bool {
+        let steps = ((to - from).length() / (self.tile_size * 0.25))
+            .ceil()
+            .max(1.0) as usize;
+        (0..=steps).all(|step| {
+            let point = from.lerp(to, step as f32 / steps as f32);
+            self.is_walkable(self.cell(point))
+        })
+    }
+
+    /// 敵人身體寬度走得過的直線：中線與左右兩側平移 `half_width` 的線都要暢通
+    pub fn has_clear_corridor(&self, from: Vec2, to: Vec2, half_width: f32) -> bool {
+        let side = (to - from).perp().normalize_or_zero() * half_width;
+        self.has_clear_line(from, to)
+            && self.has_clear_line(from + side, to + side)
+            && self.has_clear_line(from - side, to - side)
+    }
+
+    /// 以 A* 找出從 `from` 走到 `to` 的路徑點，已去掉可以直線走過的轉折，最後一點為 `to`
+    pub fn find_path(&self, from: Vec2, to: Vec2) -> Option<Vec<Vec2>> {
+        let start = self.nearest_walkable(self.cell(from))?;
+        let goal = self.cell(to);
+        if !self.is_walkable(goal) {
+            return None;
+        }
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        let mut best_cost: HashMap<(i32, i32), u32> = HashMap::new();
+        best_cost.insert(start, 0);
+        open.push(Reverse((octile_distance(start, goal), start)));
+
+        let mut expanded = 0;
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                let mut cells = vec![goal];
+                let mut cursor = goal;
+                while let Some(previous) = came_from.get(&cursor) {
+                    cursor = *previous;
+                    cells.push(cursor);
+                }
+                cells.reverse();
+                return Some(self.smooth(from, &cells[1..], to));
+            }
+
+            expanded += 1;
+            if expanded > NAV_MAX_SEARCH_NODES {
+                return None;
+            }
+
+            let current_cost = best_cost[&current];
+            for (dx, dy) in NEIGHBORS {
+                let next = (current.0 + dx, current.1 + dy);
+                if !self.is_walkable(next) {
+                    continue;
+                }
+
+                let diagonal = dx != 0 && dy != 0;
+                // 斜走時兩側都要能走，避免切過牆角
+                if diagonal
+                    && (!self.is_walkable((current.0 + dx, current.1))
+                        || !self.is_walkable((current.0, current.1 + dy)))
+                {
+                    continue;
+                }
+
+                let step = if diagonal {
+                    DIAGONAL_COST
+                } else {
+                    STRAIGHT_COST
+                };
+                let cost = current_cost + step;
+                if best_cost.get(&next).is_some_and(|known| *known <= cost) {
+                    continue;
+                }
+
+                best_cost.insert(next, cost);
+                came_from.insert(next, current);
+                open.push(Reverse((cost + octile_distance(next, goal), next)));
+            }
+        }
+
+        None
+    }
+
+    /// 起點可能被擠到牆邊，改從相鄰可行走的格子出發
+    fn nearest_walkable(&self, cell: (i32, i32)) -> Option<(i32, i32)> {
+        if self.is_walkable(cell) {
+            return Some(cell);
+        }
+        NEIGHBORS
+            .iter()
+            .map(|(dx, dy)| (cell.0 + dx, cell.1 + dy))
+            .find(|neighbor| self.is_walkable(*neighbor))
+    }
+
+    /// 只保留無法直線抵達的轉折點
+    fn smooth(&self, from: Vec2, cells: &[(i32, i32)], to: Vec2) -> Vec<Vec2> {
+        let mut points: Vec<Vec2> = cells.iter().map(|cell| self.cell_center(*cell)).collect();
+        match points.last_mut() {
+            Some(last) => *last = to,
+            None => points.push(to),
+        }
+
+        let mut smoothed = Vec::new();
+        let mut anchor = from;
+        let mut index = 0;
+        while index < points.len() {
+            let mut farthest = index;
+            while farthest + 1 < points.len()
+                && self.has_clear_corridor(anchor, points[farthest + 1], self.body_half_width())
+            {
+                farthest += 1;
+            }
+            anchor = points[farthest];
+            smoothed.push(anchor);
+            index = farthest + 1;
+        }
+        smoothed
+    }
+}
+
+fn octile_distance(a: (i32, i32), b: (i32, i32)) -> u32 {
+    let dx = a.0.abs_diff(b.0);
+    let dy = a.1.abs_diff(b.1);
+    STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
+}
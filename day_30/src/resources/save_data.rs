@@ -1,5 +1,9 @@
-use crate::components::{ShieldKind, WeaponKind};
+use crate::components::{RangedWeaponKind, ShieldKind, WeaponFamily, WeaponKind};
+use crate::resources::{GameSession, LevelSnapshot, MigratedSave, SaveLoadError, migrate_save};
+use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSaveData {
@@ -10,11 +14,24 @@ pub struct GameSaveData {
     pub player_level: usize,
     pub player_experience: u32,
     pub equipped_weapon: Option<WeaponKind>,
+    /// 近戰武器的種類，與階級分開記錄
+    pub equipped_weapon_family: Option<WeaponFamily>,
     pub equipped_shield: Option<ShieldKind>,
+    pub equipped_ranged_weapon: Option<RangedWeaponKind>,
+    /// 遠程武器剩餘的箭矢
+    pub ranged_ammo: u32,
+    /// 無盡模式的 run seed，關卡流程模式下為 None
+    pub endless_run_seed: Option<u64>,
+    /// 存檔時間（Unix 秒），由 v1 升級上來的存檔為 0
+    pub saved_at_unix_seconds: u64,
+    pub level_name: String,
+    pub playtime_seconds: f64,
+    /// 存檔當下的關卡狀態；沒有時讀檔會重新生成整個關卡
+    pub world: Option<LevelSnapshot>,
 }
 
 impl GameSaveData {
-    pub const CURRENT_VERSION: u32 = 1;
+    pub const CURRENT_VERSION: u32 = 11;
 
     pub fn new() -> Self {
         Self {
@@ -25,7 +42,139 @@ impl GameSaveData {
             player_level: 0,
             player_experience: 0,
             equipped_weapon: None,
+            equipped_weapon_family: None,
             equipped_shield: None,
+            equipped_ranged_weapon: None,
+            ranged_ammo: 0,
+            endless_run_seed: None,
+            saved_at_unix_seconds: 0,
+            level_name: String::new(),
+            playtime_seconds: 0.0,
+            world: None,
         }
     }
+
+    /// 存檔選單上顯示的關卡名稱，舊存檔沒有記錄名稱時改用關卡編號
+    pub fn display_level_name(&self) -> String {
+        if self.level_name.is_empty() {
+            format!("Level {}", self.level_index + 1)
+        } else {
+            self.level_name.clone()
+        }
+    }
+}
+
+/// 讀取單一存檔欄位的結果
+#[derive(Debug, Clone)]
+pub enum SaveSlotState {
+    Empty,
+    Occupied(Box<GameSaveData>),
+    Unreadable(SaveLoadError),
+}
+
+/// 存檔寫入的位置，測試時會改指向暫存資料夾
+#[derive(Resource, Debug, Clone)]
+pub struct SaveStorage {
+    pub directory: PathBuf,
+}
+
+impl SaveStorage {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// 第 `slot` 個欄位（從 0 開始）的存檔路徑，第一格沿用舊版的 slot1.json
+    pub fn slot_file(&self, slot: usize) -> PathBuf {
+        self.directory.join(format!("slot{}.json", slot + 1))
+    }
+
+    /// 升級前的原始存檔備份，檔名帶有原本的版本號
+    pub fn backup_file(&self, slot: usize, version: u32) -> PathBuf {
+        self.directory
+            .join(format!("slot{}.v{}.backup.json", slot + 1, version))
+    }
+
+    /// 只讀取不寫回，舊版存檔會在記憶體中升級（供存檔選單預覽）
+    pub fn read_slot(&self, slot: usize) -> SaveSlotState {
+        match self.read_and_migrate(slot) {
+            Ok(Some((migrated, _))) => SaveSlotState::Occupied(Box::new(migrated.data)),
+            Ok(None) => SaveSlotState::Empty,
+            Err(error) => SaveSlotState::Unreadable(error),
+        }
+    }
+
+    /// 讀取存檔並在需要升級時先備份原檔，再把升級後的內容寫回欄位
+    pub fn load_slot(&self, slot: usize) -> SaveSlotState {
+        let (migrated, raw) = match self.read_and_migrate(slot) {
+            Ok(Some(loaded)) => loaded,
+            Ok(None) => return SaveSlotState::Empty,
+            Err(error) => return SaveSlotState::Unreadable(error),
+        };
+
+        if migrated.was_migrated() {
+            let backup = self.backup_file(slot, migrated.original_version);
+            if let Err(error) = fs::write(&backup, raw) {
+                return SaveSlotState::Unreadable(SaveLoadError::Io(format!(
+                    "failed to back up save before upgrading: {error}"
+                )));
+            }
+            if let Err(error) = self.write_slot(slot, &migrated.data) {
+                return SaveSlotState::Unreadable(SaveLoadError::Io(error));
+            }
+            dev_info!(
+                "Upgraded save slot {} from v{} to v{} (backup: {})",
+                slot + 1,
+                migrated.original_version,
+                GameSaveData::CURRENT_VERSION,
+                backup.display()
+            );
+        }
+
+        SaveSlotState::Occupied(Box::new(migrated.data))
+    }
+
+    fn read_and_migrate(
+        &self,
+        slot: usize,
+    ) -> Result<Option<(MigratedSave, String)>, SaveLoadError> {
+        let path = self.slot_file(slot);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let raw =
+            fs::read_to_string(&path).map_err(|error| SaveLoadError::Io(error.to_string()))?;
+        let migrated = migrate_save(&raw)?;
+        Ok(Some((migrated, raw)))
+    }
+
+    pub fn write_slot(&self, slot: usize, data: &GameSaveData) -> Result<PathBuf, String> {
+        fs::create_dir_all(&self.directory)
+            .map_err(|error| format!("failed to create save directory: {error}"))?;
+
+        let serialized = serde_json::to_string_pretty(data)
+            .map_err(|error| format!("failed to serialize save data: {error}"))?;
+
+        let path = self.slot_file(slot);
+        fs::write(&path, serialized)
+            .map_err(|error| format!("failed to write save file: {error}"))?;
+        Ok(path)
+    }
+
+    pub fn delete_slot(&self, slot: usize) -> Result<(), String> {
+        let path = self.slot_file(slot);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        fs::remove_file(&path).map_err(|error| format!("failed to delete save file: {error}"))
+    }
+}
+
+impl Default for SaveStorage {
+    fn default() -> Self {
+        Self::new(GameSession::SAVE_DIRECTORY)
+    }
 }
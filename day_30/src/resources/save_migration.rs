@@ -0,0 +1,300 @@
+use super::save_data::GameSaveData;
+use crate::components::{WeaponFamily, WeaponKind};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// 把 `from` 版本的存檔 JSON 原地升級到下一個版本
+type SaveMigration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+/// 依序排列的升級步驟，第 n 項負責把 n + 1 版升到 n + 2 版
+const SAVE_MIGRATIONS: &[SaveMigration] = &[
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+    migrate_v8_to_v9,
+    migrate_v9_to_v10,
+    migrate_v10_to_v11,
+];
+
+/// 舊版的中毒沒有期限，升級後給予與蜘蛛網相同的持續時間
+const MIGRATED_POISON_SECONDS: f64 = 8.0;
+
+/// 讀取存檔失敗的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveLoadError {
+    Io(String),
+    Parse(String),
+    MissingVersion,
+    NewerVersion { found: u32, supported: u32 },
+    Migration { from: u32, message: String },
+}
+
+impl fmt::Display for SaveLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveLoadError::Io(message) => write!(f, "failed to read save file: {message}"),
+            SaveLoadError::Parse(message) => write!(f, "failed to parse save data: {message}"),
+            SaveLoadError::MissingVersion => write!(f, "save data has no version number"),
+            SaveLoadError::NewerVersion { found, supported } => write!(
+                f,
+                "save was made by a newer version of the game (v{found}, supported up to v{supported})"
+            ),
+            SaveLoadError::Migration { from, message } => {
+                write!(f, "failed to upgrade save from v{from}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveLoadError {}
+
+/// 升級後的存檔，以及升級前的原始版本
+#[derive(Debug, Clone)]
+pub struct MigratedSave {
+    pub data: GameSaveData,
+    pub original_version: u32,
+}
+
+impl MigratedSave {
+    pub fn was_migrated(&self) -> bool {
+        self.original_version != GameSaveData::CURRENT_VERSION
+    }
+}
+
+/// 解析存檔內容，必要時逐版升級到 `GameSaveData::CURRENT_VERSION`
+pub fn migrate_save(raw: &str) -> Result<MigratedSave, SaveLoadError> {
+    let value: Value =
+        serde_json::from_str(raw).map_err(|error| SaveLoadError::Parse(error.to_string()))?;
+    let Value::Object(mut fields) = value else {
+        return Err(SaveLoadError::Parse(
+            "save data is not a JSON object".to_string(),
+        ));
+    };
+
+    let original_version = fields
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .filter(|version| *version > 0)
+        .ok_or(SaveLoadError::MissingVersion)?;
+
+    if original_version > GameSaveData::CURRENT_VERSION {
+        return Err(SaveLoadError::NewerVersion {
+            found: original_version,
+            supported: GameSaveData::CURRENT_VERSION,
+        });
+    }
+
+    for version in original_version..GameSaveData::CURRENT_VERSION {
+        let migration = SAVE_MIGRATIONS[(version - 1) as usize];
+        migration(&mut fields).map_err(|message| SaveLoadError::Migration {
+            from: version,
+            message,
+        })?;
+        fields.insert("version".to_string(), Value::from(version + 1));
+    }
+
+    let data = serde_json::from_value(Value::Object(fields))
+        .map_err(|error| SaveLoadError::Parse(error.to_string()))?;
+
+    Ok(MigratedSave {
+        data,
+        original_version,
+    })
+}
+
+/// v2 加入存檔選單用的時間、關卡名稱與遊玩時間
+fn migrate_v1_to_v2(fields: &mut Map<String, Value>) -> Result<(), String> {
+    if !fields.contains_key("level_index") {
+        return Err("missing level_index".to_string());
+    }
+
+    fields.entry("endless_run_seed").or_insert(Value::Null);
+    fields
+        .entry("saved_at_unix_seconds")
+        .or_insert(Value::from(0u64));
+    fields
+        .entry("level_name")
+        .or_insert(Value::from(String::new()));
+    fields.entry("playtime_seconds").or_insert(Value::from(0.0));
+    Ok(())
+}
+
+/// v3 加入關卡即時狀態；舊存檔沒有快照，讀檔時照舊重建整個關卡
+fn migrate_v2_to_v3(fields: &mut Map<String, Value>) -> Result<(), String> {
+    fields.entry("world").or_insert(Value::Null);
+    Ok(())
+}
+
+/// v4 加入遠程武器欄位；舊存檔沒有遠程武器
+fn migrate_v3_to_v4(fields: &mut Map<String, Value>) -> Result<(), String> {
+    fields
+        .entry("equipped_ranged_weapon")
+        .or_insert(Value::Null);
+    fields.entry("ranged_ammo").or_insert(Value::from(0u32));
+    Ok(())
+}
+
+/// v5 把玩家的中毒改為通用的狀態異常清單
+fn migrate_v4_to_v5(fields: &mut Map<String, Value>) -> Result<(), String> {
+    let Some(Value::Object(world)) = fields.get_mut("world") else {
+        return Ok(());
+    };
+    let Some(Value::Object(player)) = world.get_mut("player") else {
+        return Err("world snapshot has no player".to_string());
+    };
+
+    let mut status_effects = Vec::new();
+    if let Some(Value::Object(poison)) = player.remove("poison") {
+        let damage_per_tick = poison
+            .get("damage_per_tick")
+            .and_then(Value::as_f64)
+            .ok_or("poison snapshot has no damage_per_tick")?;
+        let elapsed_seconds = poison
+            .get("elapsed_seconds")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        status_effects.push(serde_json::json!({
+            "kind": "Poison",
+            "magnitude": damage_per_tick,
+            "stacks": 1,
+            "remaining_seconds": MIGRATED_POISON_SECONDS,
+            "tick_elapsed_seconds": elapsed_seconds,
+        }));
+    }
+
+    player
+        .entry("status_effects")
+        .or_insert(Value::Array(status_effects));
+    Ok(())
+}
+
+/// v6 的敵人快照加入狀態異常清單
+fn migrate_v5_to_v6(fields: &mut Map<String, Value>) -> Result<(), String> {
+    upgrade_enemy_snapshots(fields, |enemy| {
+        enemy
+            .entry("status_effects")
+            .or_insert(Value::Array(Vec::new()));
+    })
+}
+
+/// v7 把近戰武器的種類從階級分開記錄；在這之前每一階都是劍，
+/// 關卡裡還沒撿的武器也直接記成劍，v11 才不會把它們換成該階級預設的種類
+fn migrate_v6_to_v7(fields: &mut Map<String, Value>) -> Result<(), String> {
+    let family = match fields.get("equipped_weapon") {
+        Some(weapon) if !weapon.is_null() => {
+            serde_json::to_value(WeaponFamily::Sword).map_err(|error| error.to_string())?
+        }
+        _ => Value::Null,
+    };
+    fields.entry("equipped_weapon_family").or_insert(family);
+    upgrade_weapon_drops(fields, |_| WeaponFamily::Sword)
+}
+
+/// v8 的敵人快照加入 2D 巡邏路徑
+fn migrate_v7_to_v8(fields: &mut Map<String, Value>) -> Result<(), String> {
+    upgrade_enemy_snapshots(fields, |enemy| {
+        enemy
+            .entry("patrol_route")
+            .or_insert(Value::Array(Vec::new()));
+    })
+}
+
+/// v9 的敵人快照加入最後看到玩家的位置，供新的 Investigating 狀態使用
+fn migrate_v8_to_v9(fields: &mut Map<String, Value>) -> Result<(), String> {
+    upgrade_enemy_snapshots(fields, |enemy| {
+        enemy.entry("last_known_position").or_insert(Value::Null);
+    })
+}
+
+/// v10 的關卡快照加入遭遇戰是否已完成
+fn migrate_v9_to_v10(fields: &mut Map<String, Value>) -> Result<(), String> {
+    if let Some(Value::Object(world)) = fields.get_mut("world") {
+        world
+            .entry("encounter_cleared")
+            .or_insert(Value::Bool(false));
+    }
+    Ok(())
+}
+
+/// v11 的武器掉落物記錄種類；v7 到 v10 撿起時使用該階級預設的種類
+fn migrate_v10_to_v11(fields: &mut Map<String, Value>) -> Result<(), String> {
+    upgrade_weapon_drops(fields, |kind| kind.default_family())
+}
+
+/// 把地上與寶箱裡只記錄階級的武器掉落物補上種類
+fn upgrade_weapon_drops(
+    fields: &mut Map<String, Value>,
+    family_for: fn(WeaponKind) -> WeaponFamily,
+) -> Result<(), String> {
+    let Some(Value::Object(world)) = fields.get_mut("world") else {
+        return Ok(());
+    };
+
+    let mut effects = Vec::new();
+    for (key, value) in world.iter_mut() {
+        match (key.as_str(), value) {
+            ("pickups", Value::Array(pickups)) => effects.extend(
+                pickups
+                    .iter_mut()
+                    .filter_map(|pickup| pickup.get_mut("effect")),
+            ),
+            ("chests", Value::Array(chests)) => effects.extend(
+                chests
+                    .iter_mut()
+                    .filter_map(|chest| chest.get_mut("contents"))
+                    .filter_map(|contents| contents.get_mut("Item")),
+            ),
+            _ => {}
+        }
+    }
+
+    for effect in effects {
+        let Some(weapon) = effect.get_mut("EquipWeapon") else {
+            continue;
+        };
+        if weapon.is_object() {
+            continue;
+        }
+        let kind: WeaponKind = serde_json::from_value(weapon.clone())
+            .map_err(|error| format!("unknown weapon drop: {error}"))?;
+        *weapon = serde_json::json!({ "kind": kind, "family": family_for(kind) });
+    }
+    Ok(())
+}
+
+/// 對關卡快照裡的每個敵人（包含寶箱怪）套用同一個升級步驟
+fn upgrade_enemy_snapshots(
+    fields: &mut Map<String, Value>,
+    upgrade: fn(&mut Map<String, Value>),
+) -> Result<(), String> {
+    let Some(Value::Object(world)) = fields.get_mut("world") else {
+        return Ok(());
+    };
+
+    let mut enemies = Vec::new();
+    for (key, value) in world.iter_mut() {
+        match (key.as_str(), value) {
+            ("enemies", Value::Array(list)) => enemies.extend(list.iter_mut()),
+            ("chests", Value::Array(chests)) => enemies.extend(
+                chests
+                    .iter_mut()
+                    .filter_map(|chest| chest.get_mut("mimic"))
+                    .filter(|mimic| !mimic.is_null()),
+            ),
+            _ => {}
+        }
+    }
+
+    for enemy in enemies {
+        let Value::Object(enemy) = enemy else {
+            return Err("enemy snapshot is not an object".to_string());
+        };
+        upgrade(enemy);
+    }
+    Ok(())
+}
@@ -0,0 +1,318 @@
+use crate::components::world::RoomTileType;
+use crate::constants::{ENVIRONMENT_PROP_COLLISION_RADIUS, PLAYER_SCALE, ROOM_TILE_SIZE};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// 貼齊牆面時容許的誤差，避免浮點誤差讓碰撞箱卡在牆上
+const COLLISION_EPSILON: f32 = 0.01;
+const MAX_DEPENETRATION_PASSES: usize = 4;
+
+/// 格子上實際生效的瓷磚
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapTile {
+    pub entity: Entity,
+    pub tile_type: RoomTileType,
+}
+
+/// 關卡的瓷磚索引：換關時建立一次，之後只隨門的開關與擋路擺設更新
+#[derive(Resource, Debug, Clone)]
+pub struct TileMap {
+    tile_size: f32,
+    tiles: HashMap<(i32, i32), MapTile>,
+    blocking_props: HashMap<(i32, i32), Vec2>,
+}
+
+impl Default for TileMap {
+    fn default() -> Self {
+        Self {
+            tile_size: ROOM_TILE_SIZE * PLAYER_SCALE,
+            tiles: HashMap::new(),
+            blocking_props: HashMap::new(),
+        }
+    }
+}
+
+impl TileMap {
+    /// 同一格疊了多張瓷磚時（例如走廊地板蓋在牆上）取優先度最高的那張
+    pub fn from_tiles(
+        tile_size: f32,
+        tiles: impl IntoIterator<Item = (Entity, Vec2, RoomTileType)>,
+    ) -> Self {
+        let mut map = Self {
+            tile_size,
+            tiles: HashMap::new(),
+            blocking_props: HashMap::new(),
+        };
+
+        for (entity, position, tile_type) in tiles {
+            let cell = map.cell(position);
+            let replace = map.tiles.get(&cell).is_none_or(|existing| {
+                let existing_priority = tile_priority(existing.tile_type);
+                let new_priority = tile_priority(tile_type);
+                // 優先級相同時保留較小的 entity index，避免每次建立結果不同
+                new_priority > existing_priority
+                    || (new_priority == existing_priority
+                        && entity.index() < existing.entity.index())
+            });
+            if replace {
+                map.tiles.insert(cell, MapTile { entity, tile_type });
+            }
+        }
+
+        map
+    }
+
+    pub fn tile_size(&self) -> f32 {
+        self.tile_size
+    }
+
+    pub fn cell(&self, point: Vec2) -> (i32, i32) {
+        (
+            (point.x / self.tile_size).round() as i32,
+            (point.y / self.tile_size).round() as i32,
+        )
+    }
+
+    pub fn cell_center(&self, (x, y): (i32, i32)) -> Vec2 {
+        Vec2::new(x as f32, y as f32) * self.tile_size
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = ((i32, i32), MapTile)> + '_ {
+        self.tiles.iter().map(|(cell, tile)| (*cell, *tile))
+    }
+
+    /// 牆或關閉的門
+    pub fn is_solid(&self, cell: (i32, i32)) -> bool {
+        self.tiles
+            .get(&cell)
+            .is_some_and(|tile| is_solid_tile(tile.tile_type))
+    }
+
+    /// 有地板、沒有牆也沒有擋路擺設的格子
+    pub fn is_walkable(&self, cell: (i32, i32)) -> bool {
+        self.tiles.contains_key(&cell)
+            && !self.is_solid(cell)
+            && !self.blocking_props.contains_key(&cell)
+    }
+
+    pub fn walkable_cells(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.tiles
+            .keys()
+            .copied()
+            .filter(|cell| self.is_walkable(*cell))
+    }
+
+    /// 這個位置是否會擋下飛行物（擺設不擋）
+    pub fn blocks_projectile(&self, point: Vec2) -> bool {
+        self.is_solid(self.cell(point))
+    }
+
+    /// 沿著線段每四分之一格取樣，回傳第一個碰到牆或關閉的門的位置
+    pub fn raycast(&self, from: Vec2, to: Vec2) -> Option<Vec2> {
+        let steps = ((to - from).length() / (self.tile_size * 0.25))
+            .ceil()
+            .max(1.0) as usize;
+        (0..=steps)
+            .map(|step| from.lerp(to, step as f32 / steps as f32))
+            .find(|point| self.blocks_projectile(*point))
+    }
+
+    /// 兩點之間沒有牆或關閉的門擋住視線
+    pub fn has_line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        self.raycast(from, to).is_none()
+    }
+
+    /// `radius` 範圍內距離最近的牆或關閉的門的中心
+    pub fn nearest_solid(&self, point: Vec2, radius: f32) -> Option<Vec2> {
+        let (cx, cy) = self.cell(point);
+        let reach = (radius / self.tile_size).ceil() as i32;
+        (cx - reach..=cx + reach)
+            .flat_map(|x| (cy - reach..=cy + reach).map(move |y| (x, y)))
+            .filter(|cell| self.is_solid(*cell))
+            .map(|cell| self.cell_center(cell))
+            .filter(|center| center.distance(point) < radius)
+            .min_by(|a, b| a.distance(point).total_cmp(&b.distance(point)))
+    }
+
+    /// 以碰撞箱移動 `displacement`：先走 X 再走 Y，撞到牆只停下該軸，因此會沿著牆滑動；
+    /// 每一小步不超過碰撞箱半寬，高速或低影格率時也不會穿牆
+    pub fn move_and_slide(&self, position: Vec2, displacement: Vec2, half_size: Vec2) -> Vec2 {
+        let max_step = half_size.min_element().max(1.0);
+        let steps = (displacement.abs().max_element() / max_step)
+            .ceil()
+            .max(1.0) as usize;
+        let step = displacement / steps as f32;
+
+        let mut position = position;
+        for _ in 0..steps {
+            position.x = self.sweep_axis(position, half_size, step.x, 0);
+            position.y = self.sweep_axis(position, half_size, step.y, 1);
+            position = self.push_out_of_props(position, half_size);
+        }
+        position
+    }
+
+    /// 把已經卡進牆裡（例如門在身上關起來）的碰撞箱推出去，一次可處理好幾面牆與擺設
+    pub fn depenetrate(&self, position: Vec2, half_size: Vec2) -> Vec2 {
+        let mut position = position;
+        for _ in 0..MAX_DEPENETRATION_PASSES {
+            // 每次沿著重疊最淺的方向推出一格
+            let push = self
+                .solids_overlapping(position, half_size)
+                .map(|center| self.minimum_push(position, half_size, center))
+                .min_by(|a, b| a.length_squared().total_cmp(&b.length_squared()));
+            match push {
+                Some(push) => position += push,
+                None => break,
+            }
+        }
+        self.push_out_of_props(position, half_size)
+    }
+
+    /// 沿單一軸移動，碰到原本不重疊的牆就貼齊牆面停下
+    fn sweep_axis(&self, position: Vec2, half_size: Vec2, motion: f32, axis: usize) -> f32 {
+        let start = position[axis];
+        if motion == 0.0 {
+            return start;
+        }
+
+        let tile_half = self.tile_size * 0.5;
+        let mut moved = position;
+        moved[axis] = start + motion;
+
+        let mut end = moved[axis];
+        for center in self.solids_overlapping(moved, half_size) {
+            let reach = tile_half + half_size[axis];
+            let gap = (center[axis] - start) * motion.signum();
+            // 出發時就已經重疊的牆交給 `depenetrate` 處理
+            if gap < reach - COLLISION_EPSILON {
+                continue;
+            }
+            let contact = center[axis] - reach * motion.signum();
+            end = if motion > 0.0 {
+                end.min(contact)
+            } else {
+                end.max(contact)
+            };
+        }
+        end
+    }
+
+    fn solids_overlapping(
+        &self,
+        position: Vec2,
+        half_size: Vec2,
+    ) -> impl Iterator<Item = Vec2> + '_ {
+        let reach = half_size + Vec2::splat(self.tile_size * 0.5);
+        let (min_x, min_y) = self.cell(position - half_size);
+        let (max_x, max_y) = self.cell(position + half_size);
+        (min_x - 1..=max_x + 1)
+            .flat_map(move |x| (min_y - 1..=max_y + 1).map(move |y| (x, y)))
+            .filter(|cell| self.is_solid(*cell))
+            .map(|cell| self.cell_center(cell))
+            .filter(move |center| {
+                let offset = (position - *center).abs();
+                offset.x < reach.x - COLLISION_EPSILON && offset.y < reach.y - COLLISION_EPSILON
+            })
+    }
+
+    fn minimum_push(&self, position: Vec2, half_size: Vec2, center: Vec2) -> Vec2 {
+        let reach = half_size + Vec2::splat(self.tile_size * 0.5);
+        let offset = position - center;
+        let overlap = reach - offset.abs();
+        if overlap.x < overlap.y {
+            Vec2::new(overlap.x * offset.x.signum(), 0.0)
+        } else {
+            Vec2::new(0.0, overlap.y * offset.y.signum())
+        }
+    }
+
+    /// 擋路擺設是圓形碰撞，沿著最近點的法線推開
+    fn push_out_of_props(&self, position: Vec2, half_size: Vec2) -> Vec2 {
+        let radius = ENVIRONMENT_PROP_COLLISION_RADIUS;
+        let (min_x, min_y) = self.cell(position - half_size - Vec2::splat(radius));
+        let (max_x, max_y) = self.cell(position + half_size + Vec2::splat(radius));
+
+        let mut position = position;
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                let Some(center) = self.blocking_props.get(&(x, y)) else {
+                    continue;
+                };
+                let closest = center.clamp(position - half_size, position + half_size);
+                let offset = closest - *center;
+                let distance = offset.length();
+                if distance >= radius {
+                    continue;
+                }
+                let normal = if distance > COLLISION_EPSILON {
+                    offset / distance
+                } else {
+                    (position - *center).normalize_or(Vec2::Y)
+                };
+                position += normal * (radius - distance);
+            }
+        }
+        position
+    }
+
+    /// 門開關時更新它所在的格子
+    pub fn set_door_open(&mut self, door: Entity, position: Vec2, is_open: bool) {
+        let cell = self.cell(position);
+        if let Some(tile) = self.tiles.get_mut(&cell).filter(|tile| tile.entity == door) {
+            tile.tile_type = if is_open {
+                RoomTileType::DoorOpen
+            } else {
+                RoomTileType::DoorClosed
+            };
+        }
+    }
+
+    pub fn set_prop_blocking(&mut self, position: Vec2, blocks_movement: bool) {
+        let cell = self.cell(position);
+        if blocks_movement {
+            self.blocking_props.insert(cell, position);
+        } else {
+            self.blocking_props.remove(&cell);
+        }
+    }
+}
+
+fn is_solid_tile(tile_type: RoomTileType) -> bool {
+    match tile_type {
+        RoomTileType::Floor | RoomTileType::FloorOutdoor | RoomTileType::DoorOpen => false,
+        RoomTileType::DoorClosed
+        | RoomTileType::WallNInnerCornerW
+        | RoomTileType::WallNInnerMid
+        | RoomTileType::WallNInnerCornerE
+        | RoomTileType::WallSInnerCapL
+        | RoomTileType::WallSInnerMid
+        | RoomTileType::WallSInnerCapR
+        | RoomTileType::WallSOuterCapL
+        | RoomTileType::WallSOuterMid
+        | RoomTileType::WallSOuterCapR
+        | RoomTileType::WallESide
+        | RoomTileType::WallWSide => true,
+    }
+}
+
+/// 走廊地板蓋過牆，牆與門蓋過室外地板
+fn tile_priority(tile_type: RoomTileType) -> u8 {
+    match tile_type {
+        RoomTileType::FloorOutdoor => 0,
+        RoomTileType::DoorOpen => 2,
+        RoomTileType::Floor => 4,
+        RoomTileType::DoorClosed
+        | RoomTileType::WallNInnerCornerW
+        | RoomTileType::WallNInnerMid
+        | RoomTileType::WallNInnerCornerE
+        | RoomTileType::WallSInnerCapL
+        | RoomTileType::WallSInnerMid
+        | RoomTileType::WallSInnerCapR
+        | RoomTileType::WallSOuterCapL
+        | RoomTileType::WallSOuterMid
+        | RoomTileType::WallSOuterCapR
+        | RoomTileType::WallESide
+        | RoomTileType::WallWSide => 3,
+    }
+}
@@ -0,0 +1,93 @@
+use crate::components::{
+    ChestContents, ChestState, EnemyBehaviorState, PickupEffect, StatusEffectKind,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 存檔當下關卡內的即時狀態，讀檔時在關卡重建後套用
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelSnapshot {
+    pub level_index: usize,
+    pub player: PlayerSnapshot,
+    pub enemies: Vec<EnemySnapshot>,
+    pub chests: Vec<ChestSnapshot>,
+    pub pickups: Vec<PickupSnapshot>,
+    pub doors: Vec<DoorSnapshot>,
+    pub rewards: RewardsSnapshot,
+    /// 遭遇戰進行到一半時不存檔，讀檔後玩家再次走進房間會從第一波重來
+    pub encounter_cleared: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub position: [f32; 3],
+    pub stamina: Option<f32>,
+    pub status_effects: Vec<StatusEffectSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusEffectSnapshot {
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub stacks: u32,
+    pub remaining_seconds: f32,
+    /// 距離下一跳持續傷害已經過的時間
+    #[serde(default)]
+    pub tick_elapsed_seconds: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemySnapshot {
+    pub archetype_id: String,
+    pub name: Option<String>,
+    pub position: [f32; 3],
+    pub health: i32,
+    pub max_health: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub ai_state: EnemyBehaviorState,
+    /// 跟丟玩家前最後看到的位置
+    pub last_known_position: Option<[f32; 2]>,
+    pub patrol_origin: [f32; 3],
+    pub patrol_range: f32,
+    pub patrol_direction: f32,
+    /// 2D 巡邏路徑點，沒有時只在水平範圍內來回
+    pub patrol_route: Vec<[f32; 2]>,
+    /// 燃燒、緩速、冰凍等尚未結束的狀態異常
+    pub status_effects: Vec<StatusEffectSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChestSnapshot {
+    pub name: Option<String>,
+    pub position: [f32; 3],
+    pub contents: ChestContents,
+    pub state: ChestState,
+    /// 已甦醒且仍存活的寶箱怪
+    pub mimic: Option<EnemySnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickupSnapshot {
+    pub position: [f32; 3],
+    pub effect: PickupEffect,
+}
+
+/// 門依位置對應到重建後的門
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoorSnapshot {
+    pub position: [f32; 3],
+    pub is_open: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RewardsSnapshot {
+    pub portal_anchor: Option<[f32; 3]>,
+    pub target_level: Option<usize>,
+    pub rewards_spawned: bool,
+    pub rewards_available: bool,
+}
+
+/// 讀檔後等待關卡生成完成再套用的快照
+#[derive(Resource, Debug, Default)]
+pub struct PendingWorldRestore(pub Option<LevelSnapshot>);
@@ -1,20 +1,58 @@
 use crate::components::*;
 use crate::constants::*;
-use crate::systems::EnemyHitEvent;
+use crate::resources::{ActionState, InputAction};
+use crate::systems::damage::DamageRequest;
+use crate::systems::gamepad::gamepad_aim;
 use bevy::prelude::*;
 use std::f32::consts::PI;
 
 #[derive(Event)]
 pub struct AttackInputEvent;
 
-#[derive(Event)]
-pub struct PlayerMeleeAttackEvent;
+/// 玩家開始一次近戰揮擊，帶有這次揮擊的參數
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerMeleeAttackEvent {
+    pub swing: MeleeSwingSpec,
+}
+
+/// 武器閒置時依照揮擊參數開始揮動並扣除體力，成功時回傳 true
+fn start_melee_swing(
+    spec: MeleeSwingSpec,
+    weapon_query: &mut Query<&mut WeaponSwing, With<Weapon>>,
+    stamina: &mut Stamina,
+    melee_events: &mut EventWriter<PlayerMeleeAttackEvent>,
+) -> bool {
+    let mut ready_swings: Vec<Mut<WeaponSwing>> = weapon_query
+        .iter_mut()
+        .filter(|swing| swing.timer.finished())
+        .collect();
+
+    if ready_swings.is_empty() {
+        return false;
+    }
+
+    if !stamina.spend(spec.stamina_cost) {
+        dev_info!("Not enough stamina; attack canceled");
+        return false;
+    }
+
+    for swing in &mut ready_swings {
+        swing.start(&spec);
+    }
+
+    melee_events.write(PlayerMeleeAttackEvent { swing: spec });
+    true
+}
 
+/// 按下攻擊時打出目前的連擊段數，並開始計算蓄力；舉盾時無法攻擊
 pub fn attack_input_system(
     mut attack_events: EventReader<AttackInputEvent>,
     mut weapon_query: Query<&mut WeaponSwing, With<Weapon>>,
     mut melee_events: EventWriter<PlayerMeleeAttackEvent>,
-    mut stamina_query: Query<&mut Stamina, (With<Player>, Without<PlayerDead>)>,
+    mut player_query: Query<
+        (&mut Stamina, &mut MeleeCombo, &EquippedWeapon),
+        (With<Player>, Without<PlayerDead>, Without<Blocking>),
+    >,
 ) {
     let mut requested = false;
     for _ in attack_events.read() {
@@ -25,35 +63,75 @@ pub fn attack_input_system(
         return;
     }
 
-    let Some(mut stamina) = stamina_query.iter_mut().next() else {
+    let Some((mut stamina, mut combo, weapon)) = player_query.iter_mut().next() else {
         return;
     };
 
-    let mut started_attack = false;
-    let mut spent_stamina = false;
+    combo.charge_seconds = Some(0.0);
 
-    for mut swing in &mut weapon_query {
-        if !swing.timer.finished() {
-            continue;
-        }
+    let spec = MeleeSwingSpec::combo_step(combo.step, weapon.family);
+    if start_melee_swing(spec, &mut weapon_query, &mut stamina, &mut melee_events) {
+        combo.advance(spec.duration_seconds);
+    }
+}
 
-        if !spent_stamina {
-            if !stamina.spend(PLAYER_ATTACK_STAMINA_COST) {
-                dev_info!("Not enough stamina; attack canceled");
-                return;
-            }
+/// 推進連擊的接續時間；按住攻擊鍵蓄力，蓄滿後放開施放重擊；舉盾時放棄蓄力
+pub fn melee_combo_system(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut weapon_query: Query<&mut WeaponSwing, With<Weapon>>,
+    mut melee_events: EventWriter<PlayerMeleeAttackEvent>,
+    mut player_query: Query<
+        (
+            &mut Stamina,
+            &mut MeleeCombo,
+            &EquippedWeapon,
+            Has<Blocking>,
+        ),
+        (With<Player>, Without<PlayerDead>),
+    >,
+) {
+    let Some((mut stamina, mut combo, weapon, blocking)) = player_query.iter_mut().next() else {
+        return;
+    };
 
-            spent_stamina = true;
+    combo.window.tick(time.delta());
+    if combo.window.finished() && combo.step != 0 {
+        combo.step = 0;
+    }
+
+    if blocking {
+        combo.charge_seconds = None;
+        return;
+    }
+
+    let Some(held_seconds) = combo.charge_seconds else {
+        return;
+    };
+
+    if actions.pressed(InputAction::Attack) {
+        let was_charged = combo.is_charged();
+        combo.charge_seconds = Some(held_seconds + time.delta_secs());
+        if !was_charged && combo.is_charged() {
+            dev_info!("Heavy attack charged");
         }
+        return;
+    }
 
-        swing.timer.reset();
-        swing.from_angle = -PI / 4.0; // -45 degrees
-        swing.to_angle = PI / 4.0; // +45 degrees
-        started_attack = true;
+    let charged = combo.is_charged();
+    combo.charge_seconds = None;
+    if !charged {
+        return;
     }
 
-    if started_attack {
-        melee_events.write(PlayerMeleeAttackEvent);
+    // 重擊會打斷連擊，下一次輕攻擊從第一段開始
+    if start_melee_swing(
+        MeleeSwingSpec::heavy(weapon.family),
+        &mut weapon_query,
+        &mut stamina,
+        &mut melee_events,
+    ) {
+        combo.reset();
     }
 }
 
@@ -89,6 +167,7 @@ pub fn update_weapon_offset_system(
 }
 
 pub fn update_attack_reticle_system(
+    gamepads: Query<&Gamepad>,
     mut reticle_query: Query<(&mut AttackReticle, &mut Transform), Without<Player>>,
     player_query: Query<
         (&Transform, &PlayerFacing),
@@ -103,7 +182,13 @@ pub fn update_attack_reticle_system(
         return;
     };
 
-    let raw_direction = facing.direction;
+    // 右搖桿有推動時以它瞄準，否則跟隨面向
+    let aim = gamepad_aim(&gamepads);
+    let raw_direction = if aim.length_squared() > INPUT_DEADZONE * INPUT_DEADZONE {
+        aim
+    } else {
+        facing.direction
+    };
     if raw_direction.length_squared() > INPUT_DEADZONE * INPUT_DEADZONE {
         let x_abs = raw_direction.x.abs();
         let y_abs = raw_direction.y.abs();
@@ -131,6 +216,7 @@ pub fn update_attack_reticle_system(
     transform.translation.z = player_position.z + ATTACK_RETICLE_Z_OFFSET;
 }
 
+/// 依武器的揮擊樣式播放動畫：橫掃、刺出或重砸
 pub fn update_weapon_swing_animation_system(
     mut weapon_query: Query<(&mut WeaponSwing, &mut Transform, &WeaponOffset), With<Weapon>>,
     time: Res<Time>,
@@ -147,10 +233,19 @@ pub fn update_weapon_swing_animation_system(
             }
 
             let progress = swing.timer.elapsed_secs() / swing.timer.duration().as_secs_f32();
-            let current_angle = lerp_angle(swing.from_angle, swing.to_angle, progress);
+            let (angle_progress, thrust) = match swing.style {
+                WeaponSwingStyle::Arc => (progress, 0.0),
+                // 刺擊角度幾乎不變，武器往前伸出再收回
+                WeaponSwingStyle::Thrust => (progress, (progress * PI).sin()),
+                // 重砸先慢後快
+                WeaponSwingStyle::Slam => (progress * progress, 0.0),
+            };
+            let current_angle = lerp_angle(swing.from_angle, swing.to_angle, angle_progress);
             let adjusted_angle = offset.base_angle + current_angle;
 
             transform.rotation = Quat::from_rotation_z(adjusted_angle);
+            transform.translation.x =
+                offset.position.x + thrust * swing.reach * WEAPON_THRUST_REACH_FRACTION;
         } else {
             transform.rotation = Quat::from_rotation_z(offset.base_angle);
         }
@@ -164,21 +259,18 @@ fn lerp_angle(from: f32, to: f32, t: f32) -> f32 {
 
 pub fn player_melee_attack_system(
     mut attack_events: EventReader<PlayerMeleeAttackEvent>,
-    player_query: Query<&Attack, (With<Player>, Without<PlayerDead>)>,
+    player_query: Query<(Entity, &Attack), (With<Player>, Without<PlayerDead>)>,
     reticle_query: Query<(&Transform, &AttackReticle), Without<Player>>,
-    mut enemy_query: Query<(Entity, &Transform, &mut Health, Option<&Defense>), With<Enemy>>,
-    mut hit_events: EventWriter<EnemyHitEvent>,
+    enemy_query: Query<(Entity, &Transform, &Health), With<Enemy>>,
+    mut damage_requests: EventWriter<DamageRequest>,
 ) {
-    let mut attack_count = 0;
-    for _ in attack_events.read() {
-        attack_count += 1;
-    }
+    let swings: Vec<MeleeSwingSpec> = attack_events.read().map(|event| event.swing).collect();
 
-    if attack_count == 0 {
+    if swings.is_empty() {
         return;
     }
 
-    let Some(attack) = player_query.iter().next() else {
+    let Some((player_entity, attack)) = player_query.iter().next() else {
         return;
     };
 
@@ -192,42 +284,92 @@ pub fn player_melee_attack_system(
     }
 
     let attack_center = reticle_transform.translation.truncate();
-    let total_attack = attack.value() * attack_count as i32;
 
-    for (enemy_entity, enemy_transform, mut health, defense) in &mut enemy_query {
-        if health.current <= 0 {
-            continue;
-        }
+    for swing in swings {
+        let damage = swing.damage(attack.value());
+        let mut struck: Vec<(Entity, Vec2)> = Vec::new();
 
-        let to_enemy = enemy_transform.translation.truncate() - attack_center;
-        let distance = to_enemy.length();
+        for (enemy_entity, enemy_transform, health) in &enemy_query {
+            if health.current <= 0 {
+                continue;
+            }
 
-        if distance > PLAYER_ATTACK_RADIUS || distance == 0.0 {
-            continue;
-        }
+            let enemy_position = enemy_transform.translation.truncate();
+            let to_enemy = enemy_position - attack_center;
+            let distance = to_enemy.length();
 
-        let direction_to_enemy = to_enemy / distance;
+            if distance > swing.radius || distance == 0.0 {
+                continue;
+            }
 
-        if facing_direction.dot(direction_to_enemy) < PLAYER_ATTACK_FACING_COS_THRESHOLD {
-            continue;
-        }
+            let direction_to_enemy = to_enemy / distance;
 
-        let defense_value = defense.map(|value| value.value());
-        let damage = compute_damage(total_attack, defense_value);
-        let new_health = (health.current - damage).max(0);
-        if new_health != health.current {
-            health.current = new_health;
-            dev_info!(
-                "Player attack dealt {} damage; enemy HP now {}",
-                damage,
-                health.current
-            );
-            hit_events.write(EnemyHitEvent {
-                entity: enemy_entity,
-                position: enemy_transform.translation,
+            if facing_direction.dot(direction_to_enemy) < swing.facing_cos_threshold {
+                continue;
+            }
+
+            damage_requests.write(melee_hit(
+                &swing,
+                player_entity,
+                enemy_entity,
                 damage,
-                remaining_health: health.current,
-            });
+                direction_to_enemy,
+            ));
+            struck.push((enemy_entity, enemy_position));
+        }
+
+        // 劈砍：被打中的敵人附近的其他敵人也會受到部分傷害
+        let WeaponSpecial::Cleave {
+            radius,
+            damage_multiplier,
+        } = swing.special
+        else {
+            continue;
+        };
+
+        let splash_damage = (damage as f32 * damage_multiplier).round() as i32;
+        for (enemy_entity, enemy_transform, health) in &enemy_query {
+            if health.current <= 0 || struck.iter().any(|(entity, _)| *entity == enemy_entity) {
+                continue;
+            }
+
+            let enemy_position = enemy_transform.translation.truncate();
+            if !struck
+                .iter()
+                .any(|(_, position)| position.distance(enemy_position) <= radius)
+            {
+                continue;
+            }
+
+            damage_requests.write(melee_hit(
+                &swing,
+                player_entity,
+                enemy_entity,
+                splash_damage,
+                (enemy_position - attack_center).normalize_or_zero(),
+            ));
         }
     }
 }
+
+/// 一次近戰命中的傷害請求，附上武器的穿透與硬直效果
+fn melee_hit(
+    swing: &MeleeSwingSpec,
+    player: Entity,
+    enemy: Entity,
+    damage: i32,
+    direction: Vec2,
+) -> DamageRequest {
+    let mut request = DamageRequest::new(enemy, damage, DamageType::Physical)
+        .with_source(player)
+        .with_knockback(direction * PLAYER_ATTACK_KNOCKBACK * swing.knockback_multiplier)
+        .with_critical(swing.heavy);
+
+    match swing.special {
+        WeaponSpecial::Pierce => request = request.with_armor_piercing(true),
+        WeaponSpecial::Stun { seconds } => request = request.with_stun(seconds),
+        WeaponSpecial::None | WeaponSpecial::Cleave { .. } => {}
+    }
+
+    request
+}
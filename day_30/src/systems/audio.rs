@@ -1,6 +1,6 @@
 use crate::components::MainThemeMusic;
 use crate::constants::{GAMEPLAY_MUSIC_VOLUME, MENU_MUSIC_VOLUME};
-use crate::resources::{BackgroundMusicState, GamePhase, GameSession, SoundEffects};
+use crate::resources::{BackgroundMusicState, GamePhase, SoundEffects};
 use crate::systems::{
     BossWizardSpellCastEvent, DoorStateChangedEvent, EnemyAttackHitEvent, MenuClickEvent,
     PlayerLevelUpEvent, PlayerMeleeAttackEvent, PlayerPickupEvent, PlayerPoisonDamageEvent,
@@ -11,7 +11,7 @@ use bevy::prelude::*;
 pub fn initialize_audio(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    session: Res<GameSession>,
+    phase: Res<State<GamePhase>>,
 ) {
     let background_music = asset_server.load("sounds/bgm/Space-Cadet.ogg");
 
@@ -27,7 +27,7 @@ pub fn initialize_audio(
         ui_click: asset_server.load("sounds/ui/click.ogg"),
     });
 
-    let initial_phase = session.phase();
+    let initial_phase = *phase.get();
     commands.insert_resource(BackgroundMusicState {
         current_phase: Some(initial_phase),
     });
@@ -152,7 +152,7 @@ pub fn play_boss_wizard_spell_sound(
 }
 
 pub fn update_background_music_volume(
-    session: Res<GameSession>,
+    phase: Res<State<GamePhase>>,
     mut state: ResMut<BackgroundMusicState>,
     mut music_query: Query<&mut AudioSink, With<MainThemeMusic>>,
 ) {
@@ -160,8 +160,8 @@ pub fn update_background_music_volume(
         return;
     };
 
-    let target_phase = session.phase();
-    if state.current_phase == Some(target_phase) && !session.is_changed() {
+    let target_phase = *phase.get();
+    if state.current_phase == Some(target_phase) && !phase.is_changed() {
         return;
     }
 
@@ -0,0 +1,80 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::{ActionState, InputAction};
+use bevy::prelude::*;
+
+/// 玩家成功格擋反擊；`attacker` 為近戰敵人時會被打出硬直
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlayerParryEvent {
+    pub attacker: Option<Entity>,
+}
+
+/// 按住格擋鍵舉盾，持續消耗耐力；放開、耐力耗盡或翻滾時放下
+pub fn block_input_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Stamina,
+            Option<&EquippedShield>,
+            Option<&mut Blocking>,
+            Has<DodgeRoll>,
+        ),
+        (With<Player>, Without<PlayerDead>),
+    >,
+) {
+    let Some((entity, mut stamina, shield, blocking, dodging)) = player_query.iter_mut().next()
+    else {
+        return;
+    };
+
+    let Some(shield) = shield else {
+        if blocking.is_some() {
+            commands.entity(entity).remove::<Blocking>();
+        }
+        return;
+    };
+
+    let wants_block = actions.pressed(InputAction::Block) && !dodging;
+
+    match blocking {
+        Some(mut blocking) => {
+            let drain = shield.kind.block_stamina_drain() * time.delta_secs();
+            stamina.current = (stamina.current - drain).max(0.0);
+            blocking.elapsed_seconds += time.delta_secs();
+
+            if !wants_block || stamina.current <= 0.0 {
+                commands.entity(entity).remove::<Blocking>();
+            }
+        }
+        None => {
+            if wants_block && stamina.current > 0.0 {
+                commands.entity(entity).insert(Blocking::default());
+            }
+        }
+    }
+}
+
+/// 被格擋反擊的近戰敵人進入較長的硬直
+pub fn parry_stagger_system(
+    mut commands: Commands,
+    mut events: EventReader<PlayerParryEvent>,
+    enemy_query: Query<(), (With<Enemy>, Without<EnemyDeathEffect>)>,
+) {
+    for event in events.read() {
+        let Some(attacker) = event.attacker else {
+            continue;
+        };
+
+        if enemy_query.get(attacker).is_err() {
+            continue;
+        }
+
+        commands
+            .entity(attacker)
+            .insert(HitStun::new(PLAYER_PARRY_STAGGER_SECONDS));
+        dev_info!("Parry staggered an enemy");
+    }
+}
@@ -0,0 +1,294 @@
+use super::enemy::{
+    BossWizardSpellCastEvent, EnemyPlacement, EnemySpawnStats, spawn_enemy_from_archetype,
+    spawn_wizard_projectile,
+};
+use crate::components::level::LevelEntity;
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::{EnemyArchetypeRegistry, TileMap};
+use bevy::prelude::*;
+
+/// 頭目生命降到門檻，進入下一個階段
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BossPhaseChangedEvent {
+    pub boss: Entity,
+    pub phase: usize,
+    pub enraged: bool,
+}
+
+/// 依生命比例切換頭目階段；狂暴階段會持續染紅（受擊閃爍結束後再染回來）
+pub fn boss_phase_system(
+    mut commands: Commands,
+    mut phase_events: EventWriter<BossPhaseChangedEvent>,
+    mut boss_query: Query<
+        (
+            Entity,
+            &Health,
+            &mut BossScript,
+            &mut Sprite,
+            Has<EnemyHitFlash>,
+        ),
+        Without<EnemyDeathEffect>,
+    >,
+) {
+    for (entity, health, mut script, mut sprite, flashing) in &mut boss_query {
+        if health.current <= 0 {
+            continue;
+        }
+
+        let fraction = health.current as f32 / health.max.max(1) as f32;
+        let target = script.phase_for_health(fraction);
+        if target != script.phase {
+            if let Some(telegraph) = script.enter_phase(target) {
+                commands.entity(telegraph).despawn();
+            }
+            phase_events.write(BossPhaseChangedEvent {
+                boss: entity,
+                phase: target,
+                enraged: script.enraged(),
+            });
+            dev_info!("Boss entered phase: {}", script.current_phase().name);
+        }
+
+        if script.enraged() && !flashing {
+            let alpha = sprite.color.alpha();
+            sprite.color = BOSS_ENRAGED_TINT.with_alpha(alpha);
+        }
+    }
+}
+
+/// 依腳本施放頭目招式：冷卻結束時先出現預兆光圈並播放施法音效，預兆結束才真正出招
+pub fn boss_pattern_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    mut spell_events: EventWriter<BossWizardSpellCastEvent>,
+    player_query: Query<&Transform, (With<Player>, Without<PlayerDead>, Without<BossScript>)>,
+    mut boss_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut BossScript,
+            &mut EnemyPatrol,
+            &EnemyAlert,
+            &EnemyAttack,
+            &Attack,
+            Option<&OnHitStatusEffects>,
+        ),
+        (Without<Player>, Without<EnemyDeathEffect>, Without<HitStun>),
+    >,
+    minion_query: Query<&BossMinion, Without<EnemyDeathEffect>>,
+    tile_map: Res<TileMap>,
+) {
+    let player_position = player_query
+        .iter()
+        .next()
+        .map(|transform| transform.translation);
+
+    for (entity, mut transform, mut script, mut patrol, alert, attack, attack_stat, on_hit) in
+        &mut boss_query
+    {
+        let origin = transform.translation;
+        let Some(player_pos) = player_position.filter(|position| {
+            position.truncate().distance(origin.truncate()) <= alert.trigger_radius
+                && tile_map.has_line_of_sight(origin.truncate(), position.truncate())
+        }) else {
+            // 玩家離開警戒範圍或躲到牆後時中斷施法
+            if let Some((_, telegraph)) = script.telegraph.take() {
+                commands.entity(telegraph).despawn();
+            }
+            continue;
+        };
+
+        if let Some((mut timer, telegraph)) = script.telegraph.take() {
+            timer.tick(time.delta());
+            if !timer.finished() {
+                script.telegraph = Some((timer, telegraph));
+                continue;
+            }
+            commands.entity(telegraph).despawn();
+
+            let direction = (player_pos - origin).truncate().normalize_or_zero();
+            let damage = (attack_stat.value() as f32 * script.current_phase().damage_multiplier)
+                .round() as i32;
+
+            match script.next_attack().clone() {
+                BossAttack::Bolt => {
+                    spawn_wizard_projectile(&mut commands, origin, direction, damage, on_hit);
+                }
+                BossAttack::Spread {
+                    count,
+                    angle_degrees,
+                } => {
+                    for aim in fan_directions(direction, count, angle_degrees) {
+                        spawn_wizard_projectile(&mut commands, origin, aim, damage, on_hit);
+                    }
+                }
+                BossAttack::HomingOrb {
+                    count,
+                    turn_rate_degrees,
+                } => {
+                    for aim in fan_directions(direction, count, WIZARD_BOSS_ORB_FAN_DEGREES) {
+                        let Some(orb) =
+                            spawn_wizard_projectile(&mut commands, origin, aim, damage, on_hit)
+                        else {
+                            continue;
+                        };
+                        commands.entity(orb).insert((
+                            Projectile::new(
+                                aim * WIZARD_BOSS_ORB_SPEED,
+                                WIZARD_BOSS_ORB_SIZE * 0.5,
+                                WIZARD_BOSS_ORB_LIFETIME,
+                            ),
+                            Sprite {
+                                color: WIZARD_BOSS_ORB_COLOR,
+                                custom_size: Some(Vec2::splat(WIZARD_BOSS_ORB_SIZE)),
+                                ..Default::default()
+                            },
+                            HomingProjectile {
+                                turn_rate: turn_rate_degrees.to_radians(),
+                            },
+                            Name::new("BossHomingOrb"),
+                        ));
+                    }
+                }
+                BossAttack::Blink { distance } => {
+                    let side = if (player_pos.x - origin.x).abs() > f32::EPSILON {
+                        (player_pos.x - origin.x).signum()
+                    } else {
+                        patrol.direction
+                    };
+                    // 優先瞬移到玩家背後，落點走不過去時改到玩家前方
+                    let landing = [
+                        player_pos.x + side * distance,
+                        player_pos.x - side * distance,
+                    ]
+                    .into_iter()
+                    .find(|x| tile_map.is_walkable(tile_map.cell(Vec2::new(*x, patrol.origin.y))));
+
+                    if let Some(x) = landing {
+                        transform.translation.x = x;
+                        patrol.origin.x = x;
+                        patrol.direction = if player_pos.x < x { -1.0 } else { 1.0 };
+                        dev_info!("Boss blinked across the arena");
+                    }
+                }
+                BossAttack::Summon {
+                    archetype_id,
+                    count,
+                } => {
+                    let Some(archetype) = archetypes.get(&archetype_id) else {
+                        script.advance();
+                        continue;
+                    };
+                    let alive = minion_query
+                        .iter()
+                        .filter(|minion| minion.boss == entity)
+                        .count();
+                    let slots = (count as usize).min(BOSS_MAX_MINIONS.saturating_sub(alive));
+
+                    for index in 0..slots {
+                        // 左右交錯排開
+                        let side = if index % 2 == 0 { 1.0 } else { -1.0 };
+                        let offset = BOSS_SUMMON_OFFSET * (1 + index / 2) as f32 * side;
+                        let mut position = Vec3::new(origin.x + offset, origin.y, origin.z);
+                        if !tile_map.is_walkable(tile_map.cell(position.truncate())) {
+                            position = origin;
+                        }
+
+                        let minion = spawn_enemy_from_archetype(
+                            &mut commands,
+                            &asset_server,
+                            archetype,
+                            EnemyPlacement::at(position, archetype.movement.patrol_range, side),
+                            EnemySpawnStats::from_archetype(archetype),
+                        );
+                        commands.entity(minion).insert((
+                            LevelEntity,
+                            BossMinion { boss: entity },
+                            Name::new(format!("{}Minion", archetype.name_tag())),
+                        ));
+                    }
+                }
+            }
+
+            script.advance();
+            continue;
+        }
+
+        script.cooldown.tick(time.delta());
+        let distance = player_pos.truncate().distance(origin.truncate());
+        if !script.cooldown.finished() || distance > attack.radius {
+            continue;
+        }
+
+        let telegraph = commands
+            .spawn((
+                BossTelegraph,
+                Sprite {
+                    color: script.next_attack().telegraph_color(),
+                    custom_size: Some(Vec2::splat(BOSS_TELEGRAPH_SIZE)),
+                    ..Default::default()
+                },
+                Transform::from_xyz(0.0, 0.0, -0.1),
+                Name::new("BossTelegraph"),
+            ))
+            .id();
+        commands.entity(entity).add_child(telegraph);
+        script.telegraph = Some((
+            Timer::from_seconds(script.current_phase().telegraph_seconds, TimerMode::Once),
+            telegraph,
+        ));
+        spell_events.write(BossWizardSpellCastEvent);
+    }
+}
+
+/// 預兆光圈隨著蓄力逐漸放大
+pub fn boss_telegraph_pulse_system(
+    boss_query: Query<&BossScript>,
+    mut telegraph_query: Query<&mut Transform, With<BossTelegraph>>,
+) {
+    for script in &boss_query {
+        let Some((timer, telegraph)) = &script.telegraph else {
+            continue;
+        };
+        if let Ok(mut transform) = telegraph_query.get_mut(*telegraph) {
+            let scale = 1.0 + timer.fraction() * BOSS_TELEGRAPH_PULSE_SCALE;
+            transform.scale = Vec3::splat(scale);
+        }
+    }
+}
+
+/// 頭目倒下時收起預兆光圈，召喚出的手下也跟著倒下
+pub fn boss_defeat_cleanup_system(
+    mut commands: Commands,
+    mut fallen_bosses: Query<&mut BossScript, With<EnemyDeathEffect>>,
+    living_bosses: Query<(), (With<BossScript>, Without<EnemyDeathEffect>)>,
+    mut minion_query: Query<(&BossMinion, &mut Health), Without<EnemyDeathEffect>>,
+) {
+    for mut script in &mut fallen_bosses {
+        if let Some((_, telegraph)) = script.telegraph.take() {
+            commands.entity(telegraph).despawn();
+        }
+    }
+
+    for (minion, mut health) in &mut minion_query {
+        if living_bosses.get(minion.boss).is_err() {
+            health.current = 0;
+        }
+    }
+}
+
+/// 以 `direction` 為中心，在 `spread_degrees` 內平均排開 `count` 個方向
+fn fan_directions(direction: Vec2, count: u32, spread_degrees: f32) -> impl Iterator<Item = Vec2> {
+    let spread = spread_degrees.to_radians();
+    (0..count).map(move |index| {
+        let t = if count > 1 {
+            index as f32 / (count - 1) as f32 - 0.5
+        } else {
+            0.0
+        };
+        Vec2::from_angle(t * spread).rotate(direction)
+    })
+}
@@ -1,6 +1,9 @@
 use crate::components::*;
 use crate::constants::*;
-use crate::systems::equipment::{ShieldEquipEvent, WeaponEquipEvent};
+use crate::resources::EnemyArchetypeRegistry;
+use crate::systems::enemy::{EnemyPlacement, EnemySpawnStats, insert_enemy_archetype};
+use crate::systems::equipment::{RangedWeaponEquipEvent, ShieldEquipEvent, WeaponEquipEvent};
+use crate::systems::health::PlayerHealedEvent;
 use crate::systems::items::{PlayerPickupEvent, pickup_visual_for_effect};
 use bevy::prelude::*;
 
@@ -14,6 +17,7 @@ pub fn chest_interaction_system(
     mut events: EventReader<ChestInteractionEvent>,
     mut chests: Query<(Entity, &Transform, &mut Chest, &mut Sprite)>,
     asset_server: Res<AssetServer>,
+    archetypes: Res<EnemyArchetypeRegistry>,
 ) {
     for event in events.read() {
         let Ok((entity, transform, mut chest, mut sprite)) = chests.get_mut(event.chest) else {
@@ -29,63 +33,31 @@ pub fn chest_interaction_system(
                 chest.state = ChestState::RevealingItem;
                 sprite.image = asset_server.load("items/chests/chest_open.png");
 
-                commands.entity(entity).insert(ChestItemReveal::new(
-                    CHEST_ITEM_REVEAL_SECONDS,
-                    effect.clone(),
-                ));
-
-                let (sprite_path, _) = pickup_visual_for_effect(&effect);
-                let item_visual = commands
-                    .spawn((
-                        ChestItemVisual,
-                        Sprite::from_image(asset_server.load(sprite_path)),
-                        Transform::from_translation(Vec3::new(0.0, CHEST_ITEM_DISPLAY_OFFSET, 0.1))
-                            .with_scale(Vec3::splat(CHEST_ITEM_DISPLAY_SCALE)),
-                        Name::new("ChestItemVisual"),
-                    ))
-                    .id();
-
-                commands.entity(entity).add_child(item_visual);
+                attach_chest_item_reveal(&mut commands, &asset_server, entity, effect.clone());
                 dev_info!("Chest opened, revealing item: {:?}", effect);
             }
             ChestContents::Mimic => {
+                let Some(archetype) = archetypes.get(CHEST_MIMIC_ARCHETYPE_ID) else {
+                    warn!(
+                        "Enemy archetype '{}' is missing, mimic chest stays closed",
+                        CHEST_MIMIC_ARCHETYPE_ID
+                    );
+                    continue;
+                };
+
                 chest.state = ChestState::MimicAwakened;
-                sprite.image = asset_server.load("items/chests/chest_mimic.png");
+                sprite.image = asset_server.load(archetype.sprite.clone());
 
                 let origin = transform.translation;
-                commands.entity(entity).insert((
-                    Enemy,
-                    Mimic,
-                    Health::new(CHEST_MIMIC_HEALTH),
-                    Attack::new(CHEST_MIMIC_ATTACK),
-                    Defense::new(CHEST_MIMIC_DEFENSE),
-                    EnemyAIState {
-                        state: EnemyBehaviorState::Chasing,
-                    },
-                    EnemyPatrol {
-                        origin,
-                        range: CHEST_MIMIC_PATROL_RANGE,
-                        direction: 1.0,
-                    },
-                    EnemyAlert {
-                        trigger_radius: CHEST_MIMIC_TRIGGER_RADIUS,
-                        leash_radius: CHEST_MIMIC_LEASH_RADIUS,
-                    },
-                    EnemySpeeds {
-                        patrol: 0.0,
-                        chase: CHEST_MIMIC_CHASE_SPEED,
-                    },
-                    EnemyAttack {
-                        radius: CHEST_MIMIC_ATTACK_RADIUS,
-                        cooldown: {
-                            let mut timer = Timer::from_seconds(
-                                CHEST_MIMIC_ATTACK_COOLDOWN,
-                                TimerMode::Repeating,
-                            );
-                            timer.set_elapsed(timer.duration());
-                            timer
-                        },
-                    },
+                let mut mimic = commands.entity(entity);
+                insert_enemy_archetype(
+                    &mut mimic,
+                    archetype,
+                    EnemyPlacement::at(origin, archetype.movement.patrol_range, 1.0),
+                    EnemySpawnStats::from_archetype(archetype),
+                );
+                mimic.insert((
+                    EnemyAIState::new(EnemyBehaviorState::Chasing),
                     Name::new("ChestMimic"),
                 ));
                 dev_info!("Chest was actually a mimic! It began chasing the player");
@@ -94,6 +66,30 @@ pub fn chest_interaction_system(
     }
 }
 
+/// 在寶箱上方顯示道具，並在倒數結束後交給玩家
+pub fn attach_chest_item_reveal(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    chest: Entity,
+    effect: PickupEffect,
+) {
+    let (sprite_path, _) = pickup_visual_for_effect(&effect);
+    let item_visual = commands
+        .spawn((
+            ChestItemVisual,
+            Sprite::from_image(asset_server.load(sprite_path)),
+            Transform::from_translation(Vec3::new(0.0, CHEST_ITEM_DISPLAY_OFFSET, 0.1))
+                .with_scale(Vec3::splat(CHEST_ITEM_DISPLAY_SCALE)),
+            Name::new("ChestItemVisual"),
+        ))
+        .id();
+
+    commands
+        .entity(chest)
+        .insert(ChestItemReveal::new(CHEST_ITEM_REVEAL_SECONDS, effect))
+        .add_child(item_visual);
+}
+
 pub fn chest_item_reveal_system(
     mut commands: Commands,
     time: Res<Time>,
@@ -105,14 +101,20 @@ pub fn chest_item_reveal_system(
         Option<&Children>,
     )>,
     mut player_query: Query<
-        (Entity, &mut Health, Option<Mut<Stamina>>, Option<&Poisoned>),
+        (
+            &mut Health,
+            Option<Mut<Stamina>>,
+            Option<Mut<StatusEffects>>,
+        ),
         (With<Player>, Without<PlayerDead>),
     >,
     item_visuals: Query<Entity, With<ChestItemVisual>>,
     asset_server: Res<AssetServer>,
     mut shield_events: EventWriter<ShieldEquipEvent>,
     mut weapon_events: EventWriter<WeaponEquipEvent>,
+    mut ranged_weapon_events: EventWriter<RangedWeaponEquipEvent>,
     mut pickup_events: EventWriter<PlayerPickupEvent>,
+    mut healed_events: EventWriter<PlayerHealedEvent>,
 ) {
     let delta = time.delta();
 
@@ -126,22 +128,20 @@ pub fn chest_item_reveal_system(
             continue;
         }
 
-        let Some((player_entity, mut health, stamina, poison_state)) =
-            player_query.iter_mut().next()
-        else {
+        let Some((mut health, stamina, status_effects)) = player_query.iter_mut().next() else {
             continue;
         };
 
         apply_pickup_effect(
-            &mut commands,
-            player_entity,
             &mut health,
             stamina,
-            poison_state,
+            status_effects,
             &reveal.effect,
             &mut shield_events,
             &mut weapon_events,
+            &mut ranged_weapon_events,
             &mut pickup_events,
+            &mut healed_events,
         );
 
         chest.state = ChestState::Empty;
@@ -161,21 +161,26 @@ pub fn chest_item_reveal_system(
 }
 
 fn apply_pickup_effect(
-    commands: &mut Commands,
-    player_entity: Entity,
     health: &mut Health,
     stamina: Option<Mut<Stamina>>,
-    poison_state: Option<&Poisoned>,
+    status_effects: Option<Mut<StatusEffects>>,
     effect: &PickupEffect,
     shield_events: &mut EventWriter<ShieldEquipEvent>,
     weapon_events: &mut EventWriter<WeaponEquipEvent>,
+    ranged_weapon_events: &mut EventWriter<RangedWeaponEquipEvent>,
     pickup_events: &mut EventWriter<PlayerPickupEvent>,
+    healed_events: &mut EventWriter<PlayerHealedEvent>,
 ) {
     pickup_events.write(PlayerPickupEvent);
     match effect {
         PickupEffect::Heal(amount) => {
             let before = health.current;
             health.current = (health.current + amount).min(health.max);
+            if health.current > before {
+                healed_events.write(PlayerHealedEvent {
+                    amount: health.current - before,
+                });
+            }
             dev_info!("Chest item: HP restored {} -> {}", before, health.current);
         }
         PickupEffect::RestoreStamina(amount) => {
@@ -192,8 +197,7 @@ fn apply_pickup_effect(
             }
         }
         PickupEffect::CurePoison => {
-            if poison_state.is_some() {
-                commands.entity(player_entity).remove::<Poisoned>();
+            if status_effects.is_some_and(|mut effects| effects.remove(StatusEffectKind::Poison)) {
                 dev_info!("Chest item: Poison cleansed");
             } else {
                 dev_info!("Chest item: Player currently is not poisoned");
@@ -203,9 +207,20 @@ fn apply_pickup_effect(
             shield_events.write(ShieldEquipEvent { kind: *kind });
             dev_info!("Chest item: Equipped {:?} to boost defense", kind);
         }
-        PickupEffect::EquipWeapon(kind) => {
-            weapon_events.write(WeaponEquipEvent { kind: *kind });
-            dev_info!("Chest item: Equipped {:?} to boost attack", kind);
+        PickupEffect::EquipWeapon { kind, family } => {
+            weapon_events.write(WeaponEquipEvent {
+                kind: *kind,
+                family: *family,
+            });
+            dev_info!(
+                "Chest item: Equipped {:?} {:?} to boost attack",
+                family,
+                kind
+            );
+        }
+        PickupEffect::EquipRangedWeapon(kind) => {
+            ranged_weapon_events.write(RangedWeaponEquipEvent { kind: *kind });
+            dev_info!("Chest item: Equipped {:?} as ranged weapon", kind);
         }
     }
 }
@@ -0,0 +1,227 @@
+use crate::components::*;
+use crate::constants::PLAYER_STATS_FONT_PATH;
+use crate::resources::CombatTextPool;
+use crate::systems::boss::BossPhaseChangedEvent;
+use crate::systems::damage::DamageAppliedEvent;
+use crate::systems::enemy::EnemyDefeatedEvent;
+use crate::systems::health::PlayerHealedEvent;
+use bevy::prelude::*;
+use rand::prelude::*;
+
+const COMBAT_TEXT_LIFETIME: f32 = 0.8;
+const COMBAT_TEXT_RISE_SPEED: f32 = 70.0;
+const COMBAT_TEXT_FONT_SIZE: f32 = 22.0;
+const COMBAT_TEXT_HEIGHT_OFFSET: f32 = 36.0;
+const COMBAT_TEXT_JITTER: f32 = 10.0;
+const COMBAT_TEXT_Z: f32 = 40.0;
+
+/// 要在世界座標顯示一段戰鬥文字
+#[derive(Event, Debug, Clone)]
+pub struct CombatTextRequest {
+    pub position: Vec3,
+    pub text: String,
+    pub kind: CombatTextKind,
+}
+
+pub fn setup_combat_text_pool(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CombatTextPool::new(
+        asset_server.load(PLAYER_STATS_FONT_PATH),
+    ));
+}
+
+/// 每次結算的傷害都顯示數字：敵人受傷、玩家受傷與持續傷害各用不同顏色
+pub fn queue_damage_combat_text(
+    mut events: EventReader<DamageAppliedEvent>,
+    target_query: Query<(&Transform, Has<Player>)>,
+    mut requests: EventWriter<CombatTextRequest>,
+) {
+    for event in events.read() {
+        let Ok((transform, is_player)) = target_query.get(event.target) else {
+            continue;
+        };
+
+        let kind = match event.damage_type {
+            DamageType::Poison => CombatTextKind::Poison,
+            DamageType::Fire => CombatTextKind::Burn,
+            DamageType::Physical | DamageType::Magic if is_player => CombatTextKind::DamageTaken,
+            DamageType::Physical | DamageType::Magic if event.critical => {
+                CombatTextKind::CriticalHit
+            }
+            DamageType::Physical | DamageType::Magic => CombatTextKind::DamageDealt,
+        };
+
+        let text = if kind == CombatTextKind::CriticalHit {
+            format!("{}!", event.damage)
+        } else {
+            event.damage.to_string()
+        };
+
+        requests.write(CombatTextRequest {
+            position: transform.translation,
+            text,
+            kind,
+        });
+    }
+}
+
+pub fn queue_heal_combat_text(
+    mut events: EventReader<PlayerHealedEvent>,
+    player_query: Query<&Transform, With<Player>>,
+    mut requests: EventWriter<CombatTextRequest>,
+) {
+    let Some(transform) = player_query.iter().next() else {
+        events.clear();
+        return;
+    };
+
+    for event in events.read() {
+        requests.write(CombatTextRequest {
+            position: transform.translation,
+            text: format!("+{}", event.amount),
+            kind: CombatTextKind::Heal,
+        });
+    }
+}
+
+pub fn queue_experience_combat_text(
+    mut events: EventReader<EnemyDefeatedEvent>,
+    mut requests: EventWriter<CombatTextRequest>,
+) {
+    for event in events.read() {
+        if event.experience == 0 {
+            continue;
+        }
+
+        requests.write(CombatTextRequest {
+            position: event.position + Vec3::new(0.0, COMBAT_TEXT_HEIGHT_OFFSET * 0.5, 0.0),
+            text: format!("+{} XP", event.experience),
+            kind: CombatTextKind::Experience,
+        });
+    }
+}
+
+/// 頭目換階段時在頭上顯示階段名稱
+pub fn queue_boss_phase_combat_text(
+    mut events: EventReader<BossPhaseChangedEvent>,
+    boss_query: Query<(&Transform, &BossScript)>,
+    mut requests: EventWriter<CombatTextRequest>,
+) {
+    for event in events.read() {
+        let Ok((transform, script)) = boss_query.get(event.boss) else {
+            continue;
+        };
+        let Some(phase) = script.phases.get(event.phase) else {
+            continue;
+        };
+
+        requests.write(CombatTextRequest {
+            position: transform.translation + Vec3::new(0.0, COMBAT_TEXT_HEIGHT_OFFSET, 0.0),
+            text: phase.name.clone(),
+            kind: CombatTextKind::BossPhase,
+        });
+    }
+}
+
+/// 從實體池取出文字並重新開始播放；池滿時覆蓋最舊的文字
+pub fn spawn_combat_text_system(
+    mut commands: Commands,
+    mut requests: EventReader<CombatTextRequest>,
+    mut pool: ResMut<CombatTextPool>,
+    mut text_query: Query<(
+        &mut CombatText,
+        &mut Text2d,
+        &mut TextFont,
+        &mut TextColor,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    let mut rng = thread_rng();
+
+    for request in requests.read() {
+        let jitter = rng.gen_range(-COMBAT_TEXT_JITTER..=COMBAT_TEXT_JITTER);
+        let translation = Vec3::new(
+            request.position.x + jitter,
+            request.position.y + COMBAT_TEXT_HEIGHT_OFFSET,
+            COMBAT_TEXT_Z,
+        );
+        let font = TextFont {
+            font: pool.font.clone(),
+            font_size: COMBAT_TEXT_FONT_SIZE * request.kind.font_scale(),
+            ..Default::default()
+        };
+        let combat_text = CombatText {
+            kind: request.kind,
+            timer: Timer::from_seconds(COMBAT_TEXT_LIFETIME, TimerMode::Once),
+            active: true,
+        };
+
+        let reused = pool.acquire();
+        if let Some(Ok((
+            mut text,
+            mut content,
+            mut text_font,
+            mut color,
+            mut transform,
+            mut visibility,
+        ))) = reused.map(|entity| text_query.get_mut(entity))
+        {
+            *text = combat_text;
+            content.0.clone_from(&request.text);
+            *text_font = font;
+            color.0 = request.kind.color();
+            transform.translation = translation;
+            *visibility = Visibility::Visible;
+            continue;
+        }
+
+        let entity = commands
+            .spawn((
+                combat_text,
+                Text2d::new(request.text.clone()),
+                font,
+                TextColor(request.kind.color()),
+                Transform::from_translation(translation),
+                Visibility::Visible,
+                Name::new("CombatText"),
+            ))
+            .id();
+
+        match reused {
+            Some(missing) => pool.replace(missing, entity),
+            None => pool.register(entity),
+        }
+    }
+}
+
+pub fn update_combat_text_system(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut CombatText,
+        &mut Transform,
+        &mut TextColor,
+        &mut Visibility,
+    )>,
+) {
+    let delta = time.delta_secs();
+
+    for (mut text, mut transform, mut color, mut visibility) in &mut query {
+        if !text.active {
+            continue;
+        }
+
+        text.timer.tick(time.delta());
+        transform.translation.y += COMBAT_TEXT_RISE_SPEED * delta;
+
+        let duration = text.timer.duration().as_secs_f32().max(f32::EPSILON);
+        let progress = (text.timer.elapsed_secs() / duration).clamp(0.0, 1.0);
+        // 前半段維持不透明，後半段才淡出
+        let alpha = (1.0 - (progress - 0.5).max(0.0) * 2.0).clamp(0.0, 1.0);
+        color.0 = text.kind.color().with_alpha(alpha);
+
+        if text.timer.finished() {
+            text.active = false;
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
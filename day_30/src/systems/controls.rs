@@ -0,0 +1,377 @@
+use crate::components::{ControlsMenuAction, ControlsMenuButton, ControlsMenuRoot};
+use crate::constants::{
+    CONTROLS_BINDING_BUTTON_WIDTH, CONTROLS_PANEL_WIDTH, MENU_BUTTON_FONT_SIZE, MENU_BUTTON_HEIGHT,
+    MENU_FONT_PATH, MENU_OVERLAY_COLOR, MENU_TITLE_FONT_SIZE, SAVE_SLOT_BUTTON_WIDTH,
+    SAVE_SLOT_INFO_FONT_SIZE,
+};
+use crate::resources::{
+    ActionBinding, ActionState, ControlsMenu, GameSession, InputAction, InputBindings,
+    SettingsStorage,
+};
+use crate::systems::game_session::MenuClickEvent;
+use crate::systems::gamepad::gamepad_movement;
+use bevy::prelude::*;
+
+/// 開啟操作設定選單
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OpenControlsMenuEvent;
+
+/// 按鍵綁定改變後送出，讓開著的選單重新整理
+#[derive(Event, Debug, Clone, Copy)]
+pub struct InputBindingsChangedEvent;
+
+/// 啟動時讀取設定檔中的按鍵綁定，讀不到時沿用預設值
+pub fn load_input_settings(storage: Res<SettingsStorage>, mut bindings: ResMut<InputBindings>) {
+    match storage.load_bindings() {
+        Ok(loaded) => *bindings = loaded,
+        Err(error) => {
+            error!("Failed to load settings, using default controls: {error}");
+            *bindings = InputBindings::default();
+        }
+    }
+}
+
+/// 依照目前綁定把鍵盤與手把輸入換算成動作
+pub fn update_action_state(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    mut actions: ResMut<ActionState>,
+) {
+    actions.clear();
+
+    for action in InputAction::ALL {
+        let binding = bindings.binding(action);
+        let pressed = keyboard_input.any_pressed(binding.keys.iter().copied())
+            || binding
+                .gamepad
+                .is_some_and(|button| gamepads.iter().any(|gamepad| gamepad.pressed(button)));
+        let just_pressed = keyboard_input.any_just_pressed(binding.keys.iter().copied())
+            || binding
+                .gamepad
+                .is_some_and(|button| gamepads.iter().any(|gamepad| gamepad.just_pressed(button)));
+        actions.set(action, pressed, just_pressed);
+    }
+
+    actions.set_move_axis(gamepad_movement(&gamepads));
+}
+
+pub fn update_controls_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bindings: Res<InputBindings>,
+    mut session: ResMut<GameSession>,
+    mut open_events: EventReader<OpenControlsMenuEvent>,
+    mut changed_events: EventReader<InputBindingsChangedEvent>,
+) {
+    let mut requested = false;
+    for _ in open_events.read() {
+        requested = true;
+    }
+
+    let mut changed = false;
+    for _ in changed_events.read() {
+        changed = true;
+    }
+
+    let awaiting = match session.controls_menu {
+        Some(menu) if requested || changed => menu.awaiting,
+        None if requested => None,
+        _ => return,
+    };
+
+    close_controls_menu(&mut commands, session.as_mut());
+    let root = build_controls_menu(&mut commands, &asset_server, &bindings, awaiting);
+    session.controls_menu = Some(ControlsMenu { root, awaiting });
+}
+
+pub fn handle_controls_menu_interactions(
+    mut commands: Commands,
+    mut interactions: Query<
+        (&Interaction, &ControlsMenuButton, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut session: ResMut<GameSession>,
+    mut bindings: ResMut<InputBindings>,
+    storage: Res<SettingsStorage>,
+    mut changed_events: EventWriter<InputBindingsChangedEvent>,
+    mut click_events: EventWriter<MenuClickEvent>,
+) {
+    for (interaction, button, mut background) in &mut interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                click_events.write(MenuClickEvent);
+                match button.action {
+                    ControlsMenuAction::Rebind(action) => {
+                        // 再點一次正在等待的按鈕就取消
+                        if let Some(menu) = session.controls_menu.as_mut() {
+                            menu.awaiting = if menu.awaiting == Some(action) {
+                                None
+                            } else {
+                                Some(action)
+                            };
+                            changed_events.write(InputBindingsChangedEvent);
+                        }
+                    }
+                    ControlsMenuAction::ResetDefaults => {
+                        *bindings = InputBindings::default();
+                        save_bindings(&storage, &bindings);
+                        changed_events.write(InputBindingsChangedEvent);
+                    }
+                    ControlsMenuAction::Back => {
+                        close_controls_menu(&mut commands, session.as_mut());
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                background.0 = Color::srgba(0.35, 0.28, 0.25, 0.9);
+            }
+            Interaction::None => {
+                background.0 = Color::srgba(0.22, 0.18, 0.15, 0.85);
+            }
+        }
+    }
+}
+
+/// 等待新按鍵時，把下一個按下的鍵盤按鍵或手把按鈕綁到該動作；
+/// 任何按鍵（包含 Esc）都可以綁定，取消改用滑鼠點選
+pub fn capture_rebind_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut session: ResMut<GameSession>,
+    mut bindings: ResMut<InputBindings>,
+    storage: Res<SettingsStorage>,
+    mut changed_events: EventWriter<InputBindingsChangedEvent>,
+) {
+    let Some(menu) = session.controls_menu.as_mut() else {
+        return;
+    };
+    let Some(action) = menu.awaiting else {
+        return;
+    };
+
+    let key = keyboard_input.get_just_pressed().next().copied();
+    let button = gamepads
+        .iter()
+        .find_map(|gamepad| gamepad.get_just_pressed().next().copied());
+
+    match (key, button) {
+        (Some(key), _) => bindings.rebind_key(action, key),
+        (None, Some(button)) => bindings.rebind_gamepad(action, button),
+        (None, None) => return,
+    }
+
+    menu.awaiting = None;
+    save_bindings(&storage, &bindings);
+    changed_events.write(InputBindingsChangedEvent);
+}
+
+pub fn close_controls_menu(commands: &mut Commands, session: &mut GameSession) {
+    if let Some(menu) = session.controls_menu.take() {
+        commands.entity(menu.root).despawn();
+    }
+}
+
+fn save_bindings(storage: &SettingsStorage, bindings: &InputBindings) {
+    match storage.write_bindings(bindings) {
+        Ok(()) => dev_info!("Saved controls to {}", storage.path.display()),
+        Err(error) => error!("Failed to save controls: {error}"),
+    }
+}
+
+fn build_controls_menu(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    bindings: &InputBindings,
+    awaiting: Option<InputAction>,
+) -> Entity {
+    let font = asset_server.load(MENU_FONT_PATH);
+
+    commands
+        .spawn((
+            ControlsMenuRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(
+                MENU_OVERLAY_COLOR[0],
+                MENU_OVERLAY_COLOR[1],
+                MENU_OVERLAY_COLOR[2],
+                MENU_OVERLAY_COLOR[3],
+            )),
+            GlobalZIndex(130),
+            Name::new("ControlsMenuRoot"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(CONTROLS_PANEL_WIDTH),
+                        padding: UiRect::all(Val::Px(24.0)),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Stretch,
+                        justify_content: JustifyContent::Center,
+                        row_gap: Val::Px(10.0),
+                        ..Default::default()
+                    },
+                    Name::new("ControlsMenuPanel"),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new("Controls"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: MENU_TITLE_FONT_SIZE * 0.8,
+                            ..Default::default()
+                        },
+                        TextColor(Color::srgb(0.95, 0.93, 0.9)),
+                        Name::new("ControlsMenuTitle"),
+                    ));
+
+                    for action in InputAction::ALL {
+                        let label = if awaiting == Some(action) {
+                            "Press a key or button... (click to cancel)".to_string()
+                        } else {
+                            binding_label(bindings.binding(action))
+                        };
+                        spawn_binding_row(panel, &font, action, &label);
+                    }
+
+                    panel
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(10.0),
+                            ..Default::default()
+                        })
+                        .with_children(|row| {
+                            spawn_controls_button(
+                                row,
+                                &font,
+                                ControlsMenuAction::ResetDefaults,
+                                "Defaults",
+                                SAVE_SLOT_BUTTON_WIDTH,
+                            );
+                            spawn_controls_button(
+                                row,
+                                &font,
+                                ControlsMenuAction::Back,
+                                "Back",
+                                SAVE_SLOT_BUTTON_WIDTH,
+                            );
+                        });
+                });
+        })
+        .id()
+}
+
+fn spawn_binding_row(
+    panel: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    action: InputAction,
+    label: &str,
+) {
+    panel
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                ..Default::default()
+            },
+            Name::new(format!("ControlsRow_{:?}", action)),
+        ))
+        .with_children(|row| {
+            row.spawn((
+                Text::new(action.label()),
+                TextFont {
+                    font: font.clone(),
+                    font_size: SAVE_SLOT_INFO_FONT_SIZE,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.95, 0.93, 0.9)),
+                Node {
+                    flex_grow: 1.0,
+                    ..Default::default()
+                },
+            ));
+
+            spawn_controls_button(
+                row,
+                font,
+                ControlsMenuAction::Rebind(action),
+                label,
+                CONTROLS_BINDING_BUTTON_WIDTH,
+            );
+        });
+}
+
+fn spawn_controls_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    action: ControlsMenuAction,
+    label: &str,
+    width: f32,
+) {
+    parent
+        .spawn((
+            Button,
+            ControlsMenuButton { action },
+            Node {
+                width: Val::Px(width),
+                height: Val::Px(MENU_BUTTON_HEIGHT * 0.8),
+                padding: UiRect::all(Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..Default::default()
+            },
+            BorderColor(Color::srgba(0.65, 0.6, 0.5, 0.6)),
+            BackgroundColor(Color::srgba(0.22, 0.18, 0.15, 0.85)),
+            Name::new(format!("ControlsButton_{:?}", action)),
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                TextFont {
+                    font: font.clone(),
+                    font_size: MENU_BUTTON_FONT_SIZE * 0.6,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.95, 0.93, 0.9)),
+            ));
+        });
+}
+
+/// 選單上顯示的綁定，例如 "W / Up  |  DPadUp"
+pub fn binding_label(binding: &ActionBinding) -> String {
+    let keys = if binding.keys.is_empty() {
+        "Unbound".to_string()
+    } else {
+        binding
+            .keys
+            .iter()
+            .map(|key| key_label(*key))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    };
+
+    match binding.gamepad {
+        Some(button) => format!("{keys}  |  {button:?}"),
+        None => keys,
+    }
+}
+
+fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    ["Key", "Digit", "Arrow"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .filter(|rest| !rest.is_empty())
+        .map(str::to_string)
+        .unwrap_or(name)
+}
@@ -0,0 +1,256 @@
+use crate::components::*;
+use crate::constants::CRITICAL_HIT_MULTIPLIER;
+use crate::systems::block::PlayerParryEvent;
+use crate::systems::enemy::{EnemyAttackHitEvent, EnemyHitEvent};
+use crate::systems::health::{PlayerDamagedEvent, PlayerDiedEvent};
+use crate::systems::player_status::PlayerPoisonDamageEvent;
+use bevy::prelude::*;
+
+/// 對目標造成傷害的請求，實際扣血一律交給 `resolve_damage_requests`
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageRequest {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    /// 減免前的攻擊力
+    pub amount: i32,
+    pub damage_type: DamageType,
+    /// 擊退方向乘上力道，零代表不擊退
+    pub knockback: Vec2,
+    pub critical: bool,
+    /// 無視目標防禦
+    pub armor_piercing: bool,
+    /// 命中後的硬直秒數，比預設短時不生效
+    pub stun_seconds: f32,
+}
+
+impl DamageRequest {
+    pub fn new(target: Entity, amount: i32, damage_type: DamageType) -> Self {
+        Self {
+            source: None,
+            target,
+            amount,
+            damage_type,
+            knockback: Vec2::ZERO,
+            critical: false,
+            armor_piercing: false,
+            stun_seconds: 0.0,
+        }
+    }
+
+    pub fn with_source(mut self, source: Entity) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn with_knockback(mut self, knockback: Vec2) -> Self {
+        self.knockback = knockback;
+        self
+    }
+
+    pub fn with_critical(mut self, critical: bool) -> Self {
+        self.critical = critical;
+        self
+    }
+
+    pub fn with_armor_piercing(mut self, armor_piercing: bool) -> Self {
+        self.armor_piercing = armor_piercing;
+        self
+    }
+
+    pub fn with_stun(mut self, seconds: f32) -> Self {
+        self.stun_seconds = seconds;
+        self
+    }
+}
+
+/// 傷害結算後送出，擊退、浮動數字等回饋都從這裡取得結果
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageAppliedEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub knockback: Vec2,
+    pub critical: bool,
+    pub stun_seconds: f32,
+    pub remaining_health: i32,
+}
+
+/// 依序結算這個影格的所有傷害請求：暴擊、防禦、抗性、無敵時間與死亡
+pub fn resolve_damage_requests(
+    mut commands: Commands,
+    mut requests: EventReader<DamageRequest>,
+    mut target_query: Query<(
+        &Transform,
+        &mut Health,
+        Option<&Defense>,
+        Option<&Resistances>,
+        Has<Invulnerable>,
+        Has<Player>,
+        Has<Enemy>,
+        Has<PlayerDead>,
+        Option<(&Blocking, &EquippedShield, &PlayerFacing)>,
+    )>,
+    mut applied_events: EventWriter<DamageAppliedEvent>,
+    mut parry_events: EventWriter<PlayerParryEvent>,
+    mut enemy_hit_events: EventWriter<EnemyHitEvent>,
+    mut player_damaged_events: EventWriter<PlayerDamagedEvent>,
+    mut poison_damage_events: EventWriter<PlayerPoisonDamageEvent>,
+    mut enemy_attack_events: EventWriter<EnemyAttackHitEvent>,
+    mut death_events: EventWriter<PlayerDiedEvent>,
+) {
+    for request in requests.read() {
+        let Ok((
+            transform,
+            mut health,
+            defense,
+            resistances,
+            invulnerable,
+            is_player,
+            is_enemy,
+            already_dead,
+            guard,
+        )) = target_query.get_mut(request.target)
+        else {
+            continue;
+        };
+
+        if health.current <= 0 || already_dead {
+            continue;
+        }
+
+        if invulnerable && !request.damage_type.is_damage_over_time() {
+            continue;
+        }
+
+        let outcome = guard.map_or(BlockOutcome::Unblocked, |(blocking, shield, facing)| {
+            blocking.outcome(shield.kind, facing.direction, request.knockback)
+        });
+
+        if outcome == BlockOutcome::Parried {
+            dev_info!("Player parried {:?} damage", request.damage_type);
+            parry_events.write(PlayerParryEvent {
+                attacker: request.source,
+            });
+            continue;
+        }
+
+        let mut damage = mitigate_damage(
+            request,
+            defense.map(|value| value.value()),
+            resistances.copied().unwrap_or_default(),
+        );
+        if damage <= 0 {
+            continue;
+        }
+
+        let mut knockback = request.knockback;
+        if let (BlockOutcome::Blocked, Some((_, shield, _))) = (outcome, guard) {
+            damage =
+                ((damage as f32 * (1.0 - shield.kind.block_reduction())).round() as i32).max(1);
+            knockback *= shield.kind.block_knockback_scale();
+        }
+
+        health.current = (health.current - damage).max(0);
+
+        applied_events.write(DamageAppliedEvent {
+            source: request.source,
+            target: request.target,
+            damage,
+            damage_type: request.damage_type,
+            knockback,
+            critical: request.critical,
+            stun_seconds: request.stun_seconds,
+            remaining_health: health.current,
+        });
+
+        if is_enemy {
+            dev_info!(
+                "Enemy took {} {:?} damage; HP now {}",
+                damage,
+                request.damage_type,
+                health.current
+            );
+            enemy_hit_events.write(EnemyHitEvent {
+                entity: request.target,
+                position: transform.translation,
+                damage,
+                remaining_health: health.current,
+            });
+        }
+
+        if is_player {
+            dev_info!(
+                "Player took {} {:?} damage; HP now {}",
+                damage,
+                request.damage_type,
+                health.current
+            );
+            player_damaged_events.write(PlayerDamagedEvent {
+                damage,
+                remaining_health: health.current,
+            });
+            match request.damage_type {
+                DamageType::Poison => {
+                    poison_damage_events.write(PlayerPoisonDamageEvent);
+                }
+                DamageType::Physical | DamageType::Magic => {
+                    enemy_attack_events.write(EnemyAttackHitEvent);
+                }
+                DamageType::Fire => {}
+            }
+
+            if health.current == 0 {
+                commands.entity(request.target).insert(PlayerDead);
+                death_events.write(PlayerDiedEvent);
+                dev_info!("Player died!");
+            }
+        }
+    }
+}
+
+/// 套用暴擊、防禦與抗性後的實際傷害；完全免疫時為 0，否則至少 1 點
+pub fn mitigate_damage(
+    request: &DamageRequest,
+    defense: Option<i32>,
+    resistances: Resistances,
+) -> i32 {
+    let amount = if request.critical {
+        (request.amount as f32 * CRITICAL_HIT_MULTIPLIER).round() as i32
+    } else {
+        request.amount
+    };
+
+    let defense = if request.armor_piercing {
+        None
+    } else {
+        defense
+    };
+
+    let (defense, resistance) = match request.damage_type {
+        DamageType::Physical => (defense, resistances.physical),
+        DamageType::Magic => (defense, resistances.magic),
+        DamageType::Poison => (None, resistances.poison),
+        DamageType::Fire => (None, resistances.fire),
+    };
+
+    let resistance = resistance.clamp(0.0, 1.0);
+    if resistance >= 1.0 {
+        return 0;
+    }
+
+    let mitigated = compute_damage(amount, defense) as f32 * (1.0 - resistance);
+    (mitigated.round() as i32).max(1)
+}
+
+pub fn tick_invulnerability_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in &mut query {
+        if invulnerable.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::TileMap;
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+#[derive(Event, Clone, Copy)]
+pub struct DodgeInputEvent;
+
+/// 消耗耐力開始翻滾，方向取移動輸入，沒有輸入時朝面向翻滾
+pub fn dodge_input_system(
+    mut commands: Commands,
+    mut dodge_events: EventReader<DodgeInputEvent>,
+    mut player_query: Query<
+        (Entity, &mut Stamina, &InputVector, &PlayerFacing),
+        (
+            With<Player>,
+            Without<PlayerDead>,
+            Without<DodgeRoll>,
+            Without<HitStun>,
+        ),
+    >,
+) {
+    let mut requested = false;
+    for _ in dodge_events.read() {
+        requested = true;
+    }
+
+    if !requested {
+        return;
+    }
+
+    let Some((entity, mut stamina, input_vector, facing)) = player_query.iter_mut().next() else {
+        return;
+    };
+
+    let direction = if input_vector.0 != Vec2::ZERO {
+        input_vector.0
+    } else {
+        facing.direction
+    }
+    .normalize_or_zero();
+
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    if !stamina.spend(PLAYER_DODGE_STAMINA_COST) {
+        dev_info!("Not enough stamina; dodge canceled");
+        return;
+    }
+
+    commands.entity(entity).insert((
+        DodgeRoll::new(direction, PLAYER_DODGE_SECONDS),
+        Invulnerable::new(PLAYER_DODGE_INVULNERABLE_SECONDS),
+    ));
+}
+
+/// 推動翻滾中的玩家並播放旋轉動畫；撞到牆時沿著牆滑開
+pub fn dodge_roll_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    tile_map: Res<TileMap>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Sprite,
+            &mut DodgeRoll,
+            &BoxCollider,
+        ),
+        (With<Player>, Without<PlayerDead>),
+    >,
+) {
+    let Some((entity, mut transform, mut sprite, mut roll, collider)) =
+        player_query.iter_mut().next()
+    else {
+        return;
+    };
+
+    let displacement = roll.direction * PLAYER_DODGE_SPEED * time.delta_secs();
+    let position = tile_map.move_and_slide(
+        transform.translation.truncate(),
+        displacement,
+        collider.half_size,
+    );
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+
+    if roll.timer.tick(time.delta()).finished() {
+        transform.rotation = Quat::IDENTITY;
+        sprite.color = Color::WHITE;
+        commands.entity(entity).remove::<DodgeRoll>();
+        return;
+    }
+
+    // 朝翻滾方向轉一整圈，半透明表示無敵
+    let spin = if roll.direction.x < 0.0 { 1.0 } else { -1.0 };
+    transform.rotation = Quat::from_rotation_z(spin * TAU * roll.timer.fraction());
+    sprite.color = Color::srgba(1.0, 1.0, 1.0, PLAYER_DODGE_ALPHA);
+}
@@ -1,3 +1,4 @@
+use crate::components::level::DoorLocked;
 use crate::components::player::{Player, PlayerDead};
 use crate::components::world::{Door, RoomTile, RoomTileType};
 use crate::constants::*;
@@ -16,7 +17,7 @@ pub struct DoorStateChangedEvent {
 pub fn door_interaction_system(
     mut door_query: Query<
         (Entity, &mut Door, &mut RoomTile, &Transform, &mut Sprite),
-        Without<Player>,
+        (Without<Player>, Without<DoorLocked>),
     >,
     player_query: Query<&Transform, (With<Player>, Without<PlayerDead>)>,
     mut door_events: EventReader<DoorInteractionEvent>,
@@ -1,6 +1,6 @@
 use crate::components::{DeathParticle, Enemy, EnemyHitFlash, HitSpark};
 use crate::resources::CameraShake;
-use crate::systems::EnemyHitEvent;
+use crate::systems::{BossPhaseChangedEvent, EnemyHitEvent};
 use bevy::prelude::*;
 use rand::prelude::*;
 
@@ -171,3 +171,18 @@ pub fn trigger_camera_shake_on_enemy_hit(
         shake.trigger(amplitude, duration);
     }
 }
+
+/// 頭目換階段時震動鏡頭，進入狂暴更強烈
+pub fn trigger_camera_shake_on_boss_phase(
+    mut events: EventReader<BossPhaseChangedEvent>,
+    mut shake: ResMut<CameraShake>,
+) {
+    for event in events.read() {
+        let (amplitude, duration) = if event.enraged {
+            (16.0, 0.6)
+        } else {
+            (10.0, 0.4)
+        };
+        shake.trigger(amplitude, duration);
+    }
+}
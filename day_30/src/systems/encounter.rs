@@ -0,0 +1,242 @@
+use crate::components::EnemyDeathEffect;
+use crate::components::level::{DoorLocked, EncounterEnemy, EncounterSpawnTelegraph, LevelEntity};
+use crate::components::player::{Player, PlayerDead};
+use crate::components::world::{Door, RoomTile, RoomTileType};
+use crate::constants::*;
+use crate::resources::{Encounter, EncounterStage, EnemyArchetypeRegistry, RoomAssets, TileMap};
+use crate::systems::health::PlayerRespawnedEvent;
+use crate::systems::{
+    DoorStateChangedEvent, EnemyPlacement, EnemySpawnStats, spawn_enemy_from_archetype,
+};
+use bevy::prelude::*;
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+type DoorData = (
+    Entity,
+    &'static mut Door,
+    &'static mut RoomTile,
+    &'static mut Sprite,
+);
+
+/// 玩家走進遭遇戰房間時關上並鎖住所有門
+pub fn encounter_trigger_system(
+    mut commands: Commands,
+    mut encounter: ResMut<Encounter>,
+    tile_map: Res<TileMap>,
+    player_query: Query<&Transform, (With<Player>, Without<PlayerDead>)>,
+    mut door_query: Query<DoorData, With<LevelEntity>>,
+    room_assets: Res<RoomAssets>,
+    mut door_state_events: EventWriter<DoorStateChangedEvent>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    if !encounter.should_trigger(tile_map.cell(player_transform.translation.truncate())) {
+        return;
+    }
+
+    for (entity, mut door, mut tile, mut sprite) in &mut door_query {
+        commands.entity(entity).insert(DoorLocked);
+        door.is_open = false;
+        tile.tile_type = RoomTileType::DoorClosed;
+        sprite.image = room_assets.door_closed.clone();
+    }
+
+    encounter.begin();
+    door_state_events.write(DoorStateChangedEvent { is_open: false });
+    dev_info!("🔒 Encounter started; the doors are sealed");
+}
+
+/// 上一波全滅後倒數下一波並放出預兆標記；最後一波也倒下時結束遭遇戰
+pub fn encounter_wave_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut encounter: ResMut<Encounter>,
+    tile_map: Res<TileMap>,
+    player_query: Query<&Transform, (With<Player>, Without<PlayerDead>)>,
+    living_query: Query<(), (With<EncounterEnemy>, Without<EnemyDeathEffect>)>,
+    telegraph_query: Query<(), With<EncounterSpawnTelegraph>>,
+) {
+    if encounter.stage() != EncounterStage::Active
+        || !living_query.is_empty()
+        || !telegraph_query.is_empty()
+    {
+        return;
+    }
+
+    if !encounter.has_remaining_waves() {
+        encounter.clear();
+        return;
+    }
+
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+
+    let Some(wave) = encounter.tick_next_wave(time.delta()) else {
+        return;
+    };
+
+    // 找不到離玩家夠遠的格子時退而求其次，連一格都沒有就留到下一個影格再試
+    let player_position = player_transform.translation.truncate();
+    let min_distance = tile_map.tile_size() * ENCOUNTER_SPAWN_MIN_DISTANCE_TILES;
+    let mut positions =
+        encounter_spawn_positions(&tile_map, &encounter, player_position, min_distance);
+    if positions.is_empty() {
+        positions = encounter_spawn_positions(&tile_map, &encounter, player_position, 0.0);
+    }
+    if positions.is_empty() {
+        warn!("Encounter room has no free floor to spawn a wave yet");
+        return;
+    }
+    encounter.advance_wave();
+
+    let archetype_ids = wave
+        .enemies
+        .iter()
+        .flat_map(|(id, count)| std::iter::repeat_n(id.to_string(), count));
+    for (archetype_id, position) in archetype_ids.zip(positions.iter().cycle()) {
+        commands.spawn((
+            LevelEntity,
+            EncounterSpawnTelegraph {
+                archetype_id,
+                timer: Timer::from_seconds(encounter.telegraph_seconds(), TimerMode::Once),
+            },
+            Sprite {
+                color: ENCOUNTER_TELEGRAPH_COLOR,
+                custom_size: Some(Vec2::splat(ENCOUNTER_TELEGRAPH_SIZE)),
+                ..Default::default()
+            },
+            Transform::from_xyz(position.x, position.y, 8.0),
+            Name::new("EncounterTelegraph"),
+        ));
+    }
+}
+
+/// 預兆標記逐漸放大，時間到時換成敵人
+pub fn encounter_telegraph_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    mut telegraph_query: Query<(Entity, &mut EncounterSpawnTelegraph, &mut Transform)>,
+) {
+    for (entity, mut telegraph, mut transform) in &mut telegraph_query {
+        telegraph.timer.tick(time.delta());
+        let scale = 1.0 + telegraph.timer.fraction() * ENCOUNTER_TELEGRAPH_PULSE_SCALE;
+        transform.scale = Vec3::splat(scale);
+        if !telegraph.timer.finished() {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        let Some(archetype) = archetypes.get(&telegraph.archetype_id) else {
+            warn!(
+                "Encounter wave uses unknown archetype '{}'; skipping",
+                telegraph.archetype_id
+            );
+            continue;
+        };
+
+        let position = Vec3::new(transform.translation.x, transform.translation.y, 9.0);
+        let enemy = spawn_enemy_from_archetype(
+            &mut commands,
+            &asset_server,
+            archetype,
+            EnemyPlacement::at(position, archetype.movement.patrol_range, 1.0),
+            EnemySpawnStats::from_archetype(archetype),
+        );
+        commands.entity(enemy).insert((
+            LevelEntity,
+            EncounterEnemy,
+            Name::new(format!("Encounter{}", archetype.name_tag())),
+        ));
+    }
+}
+
+/// 遭遇戰結束時解鎖並打開門
+pub fn encounter_unlock_system(
+    mut commands: Commands,
+    encounter: Res<Encounter>,
+    mut door_query: Query<DoorData, With<DoorLocked>>,
+    room_assets: Res<RoomAssets>,
+    mut door_state_events: EventWriter<DoorStateChangedEvent>,
+) {
+    if encounter.stage() != EncounterStage::Cleared || door_query.is_empty() {
+        return;
+    }
+
+    unlock_doors(
+        &mut commands,
+        &mut door_query,
+        &room_assets,
+        &mut door_state_events,
+    );
+    dev_info!("🔓 Encounter cleared; the doors are unlocked");
+}
+
+/// 玩家重生時遭遇戰從頭來過：清掉這一戰的敵人與預兆標記並解鎖房門
+pub fn reset_encounter_on_player_respawn(
+    mut commands: Commands,
+    mut respawn_events: EventReader<PlayerRespawnedEvent>,
+    mut encounter: ResMut<Encounter>,
+    wave_query: Query<Entity, Or<(With<EncounterEnemy>, With<EncounterSpawnTelegraph>)>>,
+    mut door_query: Query<DoorData, With<DoorLocked>>,
+    room_assets: Res<RoomAssets>,
+    mut door_state_events: EventWriter<DoorStateChangedEvent>,
+) {
+    if respawn_events.read().count() == 0 || encounter.stage() != EncounterStage::Active {
+        return;
+    }
+
+    for entity in &wave_query {
+        commands.entity(entity).despawn();
+    }
+    encounter.reset();
+    unlock_doors(
+        &mut commands,
+        &mut door_query,
+        &room_assets,
+        &mut door_state_events,
+    );
+    dev_info!("🔓 Player respawned; the encounter is reset");
+}
+
+fn unlock_doors(
+    commands: &mut Commands,
+    door_query: &mut Query<DoorData, With<DoorLocked>>,
+    room_assets: &RoomAssets,
+    door_state_events: &mut EventWriter<DoorStateChangedEvent>,
+) {
+    for (entity, mut door, mut tile, mut sprite) in door_query.iter_mut() {
+        commands.entity(entity).remove::<DoorLocked>();
+        door.is_open = true;
+        tile.tile_type = RoomTileType::DoorOpen;
+        sprite.image = room_assets.door_open.clone();
+    }
+
+    door_state_events.write(DoorStateChangedEvent { is_open: true });
+}
+
+/// 遭遇戰範圍內、離玩家至少 `min_distance` 的可行走格子，依這一波的種子打亂順序
+fn encounter_spawn_positions(
+    tile_map: &TileMap,
+    encounter: &Encounter,
+    player_position: Vec2,
+    min_distance: f32,
+) -> Vec<Vec2> {
+    let mut cells: Vec<(i32, i32)> = tile_map
+        .walkable_cells()
+        .filter(|cell| {
+            encounter.contains(*cell)
+                && tile_map.cell_center(*cell).distance(player_position) >= min_distance
+        })
+        .collect();
+    // HashMap 的走訪順序不固定，先排序再打亂
+    cells.sort_by_key(|(x, y)| (*y, *x));
+    cells.shuffle(&mut StdRng::seed_from_u64(encounter.wave_seed()));
+    cells
+        .into_iter()
+        .map(|cell| tile_map.cell_center(cell))
+        .collect()
+}
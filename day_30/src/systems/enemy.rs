@@ -1,17 +1,30 @@
-use super::health::{PlayerDamagedEvent, PlayerRespawnedEvent};
+use super::block::PlayerParryEvent;
+use super::damage::DamageRequest;
+use super::health::PlayerRespawnedEvent;
 use super::items::{random_pickup_effect, spawn_pickup_entity};
-use crate::components::level::LevelEntity;
+use super::navigation::steer_along_path;
+use super::projectile::projectile_bundle;
+use super::status_effects::{StatusEffectRequest, inflict_status_effects};
+use crate::components::level::EncounterEnemy;
 use crate::components::*;
 use crate::constants::*;
-use crate::resources::{EntranceLocation, GameSession};
+use crate::resources::{
+    EnemyArchetype, EnemyArchetypeRegistry, EnemyArchetypeSet, EnemyArchetypeSetHandle,
+    EnemyBehaviorKind, EnemyRespawnSlot, EntranceLocation, FloorSpawnPreference, NavigationGrid,
+    TileMap,
+};
+use bevy::asset::AssetLoadFailedEvent;
 use bevy::prelude::*;
 use rand::thread_rng;
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
-#[derive(Event, Clone, Copy, Debug)]
+#[derive(Event, Clone, Debug)]
 pub struct EnemyDefeatedEvent {
+    pub position: Vec3,
     pub experience: u32,
-    pub enemy_name: &'static str,
+    pub enemy_name: String,
+    pub is_boss: bool,
 }
 
 #[derive(Event, Clone, Copy)]
@@ -28,385 +41,351 @@ pub struct EnemyHitEvent {
 #[derive(Event, Clone, Copy)]
 pub struct BossWizardSpellCastEvent;
 
-pub fn spawn_slime(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    floor_query: Query<(&Transform, &RoomTile), Without<Enemy>>,
-    existing_slimes: Query<Entity, With<Slime>>,
-    entrance_location: Option<Res<EntranceLocation>>,
-) {
-    if !existing_slimes.is_empty() {
-        return;
+/// 生成敵人時實際使用的能力值，頭目可能依玩家能力調整過
+#[derive(Debug, Clone, Copy)]
+pub struct EnemySpawnStats {
+    pub health: i32,
+    pub attack: i32,
+    pub defense: i32,
+}
+
+impl EnemySpawnStats {
+    pub fn from_archetype(archetype: &EnemyArchetype) -> Self {
+        Self {
+            health: archetype.stats.health,
+            attack: archetype.stats.attack,
+            defense: archetype.stats.defense,
+        }
     }
 
-    spawn_slime_internal(
-        &mut commands,
-        asset_server.as_ref(),
-        &floor_query,
-        entrance_location.as_deref(),
-    );
+    pub fn scaled(self, multiplier: f32) -> Self {
+        Self {
+            health: ((self.health as f32) * multiplier).ceil() as i32,
+            attack: ((self.attack as f32) * multiplier).ceil() as i32,
+            defense: ((self.defense as f32) * multiplier).ceil() as i32,
+        }
+    }
 }
 
-pub fn spawn_cyclops(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    floor_query: Query<(&Transform, &RoomTile), Without<Enemy>>,
-    existing_cyclops: Query<Entity, With<Cyclops>>,
-    entrance_location: Option<Res<EntranceLocation>>,
-) {
-    if !existing_cyclops.is_empty() {
-        return;
+#[derive(Debug, Clone, Copy)]
+pub struct EnemyPlacement {
+    pub position: Vec3,
+    pub patrol_origin: Vec3,
+    pub patrol_range: f32,
+    pub direction: f32,
+}
+
+impl EnemyPlacement {
+    pub fn at(origin: Vec3, patrol_range: f32, direction: f32) -> Self {
+        Self {
+            position: origin,
+            patrol_origin: origin,
+            patrol_range,
+            direction,
+        }
     }
+}
 
-    spawn_cyclops_internal(
-        &mut commands,
-        asset_server.as_ref(),
-        &floor_query,
-        entrance_location.as_deref(),
-    );
+pub fn load_enemy_archetypes(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handle = asset_server.load(ENEMY_ARCHETYPES_PATH);
+    commands.insert_resource(EnemyArchetypeSetHandle(handle));
 }
 
-pub fn spawn_spider(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    floor_query: Query<(&Transform, &RoomTile), Without<Enemy>>,
-    existing_spiders: Query<Entity, With<Spider>>,
-    entrance_location: Option<Res<EntranceLocation>>,
+/// 敵人資料載入（或熱重載）完成時，更新登錄表
+pub fn apply_loaded_enemy_archetypes(
+    mut asset_events: EventReader<AssetEvent<EnemyArchetypeSet>>,
+    mut failed_events: EventReader<AssetLoadFailedEvent<EnemyArchetypeSet>>,
+    set_handle: Option<Res<EnemyArchetypeSetHandle>>,
+    sets: Res<Assets<EnemyArchetypeSet>>,
+    mut registry: ResMut<EnemyArchetypeRegistry>,
 ) {
-    if !existing_spiders.is_empty() {
-        return;
+    for event in failed_events.read() {
+        error!(
+            "Failed to load enemy data from {}: {}",
+            event.path, event.error
+        );
+        registry.set_load_error(Some(format!("{}: {}", event.path, event.error)));
     }
 
-    spawn_spider_internal(
-        &mut commands,
-        asset_server.as_ref(),
-        &floor_query,
-        entrance_location.as_deref(),
-    );
-}
+    let Some(set_handle) = set_handle else {
+        asset_events.clear();
+        return;
+    };
 
-fn spawn_slime_internal(
-    commands: &mut Commands,
-    asset_server: &AssetServer,
-    floor_query: &Query<(&Transform, &RoomTile), Without<Enemy>>,
-    entrance_location: Option<&EntranceLocation>,
-) {
-    let tile_span = ROOM_TILE_SIZE * PLAYER_SCALE;
-    let fallback_position = entrance_location
-        .map(|entrance| {
-            Vec3::new(
-                entrance.position.x,
-                entrance.position.y + tile_span * 2.0,
-                9.0,
-            )
-        })
-        .unwrap_or_else(|| Vec3::new(0.0, 0.0, 9.0));
-
-    let (spawn_position, patrol_origin, patrol_range, initial_direction, used_fallback) =
-        if let Some(details) = find_floor_spawn(floor_query, FloorSpawnPreference::LeftMost) {
-            let corridor_width = (details.max_x - details.min_x).abs();
-            if corridor_width < tile_span * 2.0 {
-                (
-                    fallback_position,
-                    fallback_position,
-                    SLIME_PATROL_RANGE,
-                    1.0,
-                    true,
-                )
-            } else {
-                let values = details.into_values(FloorSpawnPreference::LeftMost);
-                (values.0, values.1, values.2, values.3, false)
+    let mut updated = false;
+    for event in asset_events.read() {
+        match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }
+                if *id == set_handle.0.id() =>
+            {
+                updated = true;
             }
-        } else {
-            (
-                fallback_position,
-                fallback_position,
-                SLIME_PATROL_RANGE,
-                1.0,
-                true,
-            )
-        };
+            _ => {}
+        }
+    }
 
-    if used_fallback {
-        warn!(
-            "Using fallback spawn position for slime, fallback_position={:?}",
-            patrol_origin
-        );
-    } else {
-        dev_info!(
-            "Slime spawn floor position: {:?}, range={}",
-            patrol_origin,
-            patrol_range
-        );
+    if !updated {
+        return;
     }
 
-    commands.spawn((
+    let Some(set) = sets.get(&set_handle.0) else {
+        return;
+    };
+
+    registry.replace(set.archetypes.clone());
+    registry.set_load_error(None);
+    dev_info!(
+        "👾 Loaded {} enemy archetypes from {}",
+        registry.len(),
+        ENEMY_ARCHETYPES_PATH
+    );
+}
+
+/// 在既有實體上掛上敵人所需的所有元件（數值、巡邏、攻擊與 AI 行為）
+pub fn insert_enemy_archetype(
+    entity: &mut EntityCommands,
+    archetype: &EnemyArchetype,
+    placement: EnemyPlacement,
+    stats: EnemySpawnStats,
+) {
+    entity.insert((
         Enemy,
-        Slime,
-        Sprite::from_image(asset_server.load("characters/enemies/slime.png")),
-        Transform::from_translation(spawn_position).with_scale(Vec3::splat(SLIME_SCALE)),
-        Health::new(SLIME_HEALTH),
-        Attack::new(SLIME_BASE_ATTACK),
-        Defense::new(SLIME_BASE_DEFENSE),
-        EnemyAIState {
-            state: EnemyBehaviorState::Patrolling,
-        },
+        EnemyArchetypeId(archetype.id.clone()),
+        Health::new(stats.health),
+        Attack::new(stats.attack),
+        Defense::new(stats.defense),
+        archetype.resistances,
+        KnockbackWeight(archetype.weight),
+        StatusEffects::default(),
+        EnemyAIState::new(EnemyBehaviorState::Patrolling),
         EnemyPatrol {
-            origin: patrol_origin,
-            range: patrol_range,
-            direction: initial_direction,
+            origin: placement.patrol_origin,
+            range: placement.patrol_range,
+            direction: placement.direction,
         },
         EnemyAlert {
-            trigger_radius: SLIME_ALERT_RADIUS,
-            leash_radius: SLIME_LEASH_RADIUS,
+            trigger_radius: archetype.movement.alert_radius,
+            leash_radius: archetype.movement.leash_radius,
         },
         EnemySpeeds {
-            patrol: SLIME_PATROL_SPEED,
-            chase: SLIME_CHASE_SPEED,
+            patrol: archetype.movement.patrol_speed,
+            chase: archetype.movement.chase_speed,
         },
         EnemyAttack {
-            radius: SLIME_ATTACK_RADIUS,
+            radius: archetype.attack.radius,
             cooldown: {
-                let mut timer = Timer::from_seconds(SLIME_ATTACK_COOLDOWN, TimerMode::Repeating);
+                let mut timer =
+                    Timer::from_seconds(archetype.attack.cooldown_seconds, TimerMode::Repeating);
                 timer.set_elapsed(timer.duration());
                 timer
             },
         },
     ));
+
+    match archetype.behavior {
+        EnemyBehaviorKind::Chaser => {
+            entity.insert((ChaserAi, EnemyPath::default()));
+        }
+        EnemyBehaviorKind::Charger {
+            windup_seconds,
+            charge_seconds,
+            cooldown_seconds,
+        } => {
+            entity.insert((
+                ChargerAi,
+                CyclopsCharge {
+                    windup: Timer::from_seconds(windup_seconds, TimerMode::Once),
+                    charge: Timer::from_seconds(charge_seconds, TimerMode::Once),
+                    cooldown: {
+                        let mut timer = Timer::from_seconds(cooldown_seconds, TimerMode::Once);
+                        timer.set_elapsed(timer.duration());
+                        timer
+                    },
+                    facing: Vec2::X,
+                    ready: true,
+                },
+            ));
+        }
+        EnemyBehaviorKind::WebShooter => {
+            entity.insert(WebShooterAi);
+        }
+        EnemyBehaviorKind::SpellCaster => {
+            entity.insert(SpellCasterAi);
+        }
+    }
+
+    if archetype.behavior.deals_contact_damage() {
+        entity.insert(ContactAttacker);
+    }
+
+    if !archetype.inflicts.is_empty() {
+        entity.insert(OnHitStatusEffects(archetype.inflicts.clone()));
+    }
+
+    if !archetype.immunities.is_empty() {
+        entity.insert(StatusImmunities(archetype.immunities.clone()));
+    }
+
+    if !archetype.phases.is_empty() {
+        entity.insert(BossScript::new(archetype.phases.clone()));
+    }
 }
 
-fn spawn_cyclops_internal(
+/// 依資料生成一個新的敵人實體（含圖像與附屬物）
+pub fn spawn_enemy_from_archetype(
     commands: &mut Commands,
     asset_server: &AssetServer,
-    floor_query: &Query<(&Transform, &RoomTile), Without<Enemy>>,
-    entrance_location: Option<&EntranceLocation>,
-) {
-    let tile_span = ROOM_TILE_SIZE * PLAYER_SCALE;
-    let fallback_position = entrance_location
-        .map(|entrance| {
-            Vec3::new(
-                entrance.position.x + tile_span * 4.0,
-                entrance.position.y + tile_span * 2.0,
-                9.0,
-            )
-        })
-        .unwrap_or_else(|| Vec3::new(tile_span * 4.0, 0.0, 9.0));
-
-    let (spawn_position, patrol_origin, patrol_range, initial_direction, used_fallback) =
-        if let Some(details) = find_floor_spawn(floor_query, FloorSpawnPreference::RightMost) {
-            let corridor_width = (details.max_x - details.min_x).abs();
-            if corridor_width < tile_span * 2.0 {
-                (
-                    fallback_position,
-                    fallback_position,
-                    CYCLOPS_PATROL_RANGE,
-                    -1.0,
-                    true,
-                )
-            } else {
-                let values = details.into_values(FloorSpawnPreference::RightMost);
-                (values.0, values.1, values.2, values.3, false)
-            }
-        } else {
-            (
-                fallback_position,
-                fallback_position,
-                CYCLOPS_PATROL_RANGE,
-                -1.0,
-                true,
-            )
-        };
+    archetype: &EnemyArchetype,
+    placement: EnemyPlacement,
+    stats: EnemySpawnStats,
+) -> Entity {
+    let mut entity = commands.spawn((
+        Sprite::from_image(asset_server.load(archetype.sprite.clone())),
+        Transform::from_translation(placement.position).with_scale(Vec3::splat(archetype.scale)),
+    ));
 
-    if used_fallback {
-        warn!(
-            "Cyclops fallback spawn used; fallback_position={:?}",
-            patrol_origin
-        );
-    } else {
-        dev_info!(
-            "Cyclops spawn floor position: {:?}, range={}",
-            patrol_origin,
-            patrol_range
-        );
+    insert_enemy_archetype(&mut entity, archetype, placement, stats);
+
+    if let Some(accessory) = &archetype.accessory {
+        let accessory_name = format!("{}Accessory", archetype.name_tag());
+        entity.with_children(|parent| {
+            parent.spawn((
+                EnemyAccessoryVisual,
+                Sprite::from_image(asset_server.load(accessory.sprite.clone())),
+                Transform::from_translation(Vec3::from_array(accessory.offset))
+                    .with_scale(Vec3::splat(accessory.scale)),
+                Name::new(accessory_name),
+            ));
+        });
     }
 
-    commands.spawn((
-        Enemy,
-        Cyclops,
-        Sprite::from_image(asset_server.load("characters/enemies/cyclops.png")),
-        Transform::from_translation(spawn_position).with_scale(Vec3::splat(CYCLOPS_SCALE)),
-        Health::new(CYCLOPS_HEALTH),
-        Attack::new(CYCLOPS_BASE_ATTACK),
-        Defense::new(CYCLOPS_BASE_DEFENSE),
-        EnemyAIState {
-            state: EnemyBehaviorState::Patrolling,
-        },
-        EnemyPatrol {
-            origin: patrol_origin,
-            range: patrol_range,
-            direction: initial_direction,
-        },
-        EnemyAlert {
-            trigger_radius: CYCLOPS_ALERT_RADIUS,
-            leash_radius: CYCLOPS_LEASH_RADIUS,
-        },
-        EnemySpeeds {
-            patrol: CYCLOPS_PATROL_SPEED,
-            chase: CYCLOPS_CHASE_SPEED,
-        },
-        CyclopsCharge {
-            windup: Timer::from_seconds(CYCLOPS_WINDUP_SECONDS, TimerMode::Once),
-            charge: Timer::from_seconds(CYCLOPS_CHARGE_SECONDS, TimerMode::Once),
-            cooldown: {
-                let mut timer = Timer::from_seconds(CYCLOPS_COOLDOWN_SECONDS, TimerMode::Once);
-                timer.set_elapsed(timer.duration());
-                timer
-            },
-            facing: Vec2::X,
-            ready: true,
-        },
-    ));
+    entity.id()
 }
 
-fn spawn_spider_internal(
+fn spawn_roster_enemy(
     commands: &mut Commands,
     asset_server: &AssetServer,
-    floor_query: &Query<(&Transform, &RoomTile), Without<Enemy>>,
+    archetype: &EnemyArchetype,
+    slot: EnemyRespawnSlot,
+    tile_map: &TileMap,
     entrance_location: Option<&EntranceLocation>,
 ) {
     let tile_span = ROOM_TILE_SIZE * PLAYER_SCALE;
-    let fallback_position = entrance_location
-        .map(|entrance| {
-            Vec3::new(
-                entrance.position.x - tile_span * 2.0,
-                entrance.position.y + tile_span * 2.0,
-                9.0,
-            )
-        })
-        .unwrap_or_else(|| Vec3::new(-tile_span * 2.0, tile_span * 2.0, 9.0));
-
-    let (spawn_position, patrol_origin, patrol_range, initial_direction, used_fallback) =
-        if let Some(details) = find_floor_spawn(floor_query, FloorSpawnPreference::Center) {
-            let corridor_width = (details.max_x - details.min_x).abs();
-            if corridor_width < tile_span * 2.5 {
-                (
-                    fallback_position,
-                    fallback_position,
-                    SPIDER_PATROL_RANGE,
-                    1.0,
-                    true,
-                )
-            } else {
-                let values = details.into_values(FloorSpawnPreference::Center);
-                (values.0, values.1, values.2, values.3, false)
-            }
-        } else {
-            (
-                fallback_position,
+    let fallback_base = entrance_location
+        .map(|entrance| entrance.position)
+        .unwrap_or(Vec3::ZERO);
+    let fallback_position = Vec3::new(
+        fallback_base.x + slot.fallback_offset_tiles[0] * tile_span,
+        fallback_base.y + slot.fallback_offset_tiles[1] * tile_span,
+        9.0,
+    );
+    let fallback_direction = match slot.lane {
+        FloorSpawnPreference::RightMost => -1.0,
+        _ => 1.0,
+    };
+
+    let lane = find_floor_spawn(tile_map, slot.lane)
+        .filter(|details| (details.max_x - details.min_x).abs() >= tile_span * slot.min_lane_tiles);
+
+    let (mut placement, used_fallback) = match lane {
+        Some(details) => (
+            details.into_placement(slot.lane, archetype.movement.patrol_range),
+            false,
+        ),
+        None => (
+            EnemyPlacement::at(
                 fallback_position,
-                SPIDER_PATROL_RANGE,
-                1.0,
-                true,
-            )
-        };
+                archetype.movement.patrol_range,
+                fallback_direction,
+            ),
+            true,
+        ),
+    };
 
     if used_fallback {
         warn!(
-            "Using fallback spawn position for spider, fallback_position={:?}",
-            patrol_origin
+            "Using fallback spawn position for {}, fallback_position={:?}",
+            archetype.display_name, placement.patrol_origin
         );
     } else {
         dev_info!(
-            "Spider spawn floor position: {:?}, range={}",
-            patrol_origin,
-            patrol_range
+            "{} spawn floor position: {:?}, range={}",
+            archetype.display_name,
+            placement.patrol_origin,
+            placement.patrol_range
         );
     }
 
-    let floor_tiles: HashSet<(i32, i32)> = floor_query
-        .iter()
-        .filter(|(_, tile)| {
-            matches!(
-                tile.tile_type,
-                RoomTileType::Floor | RoomTileType::FloorOutdoor
-            )
-        })
-        .map(|(transform, _)| {
-            (
-                (transform.translation.x / tile_span).round() as i32,
-                (transform.translation.y / tile_span).round() as i32,
-            )
-        })
-        .collect();
-
-    let (adjusted_origin, adjusted_range) =
-        resolve_spider_patrol_bounds(spawn_position, tile_span, &floor_tiles);
+    if archetype.behavior == EnemyBehaviorKind::WebShooter {
+        let floor_tiles: HashSet<(i32, i32)> = tile_map
+            .tiles()
+            .filter(|(_, tile)| {
+                matches!(
+                    tile.tile_type,
+                    RoomTileType::Floor | RoomTileType::FloorOutdoor
+                )
+            })
+            .map(|(cell, _)| cell)
+            .collect();
+
+        let (adjusted_origin, adjusted_range) = resolve_spider_patrol_bounds(
+            placement.position,
+            tile_span,
+            archetype.movement.patrol_range,
+            &floor_tiles,
+        );
+        placement = EnemyPlacement::at(adjusted_origin, adjusted_range, placement.direction);
+    }
 
-    commands.spawn((
-        Enemy,
-        Spider,
-        Sprite::from_image(asset_server.load("characters/enemies/spider.png")),
-        Transform::from_translation(adjusted_origin).with_scale(Vec3::splat(SPIDER_SCALE)),
-        Health::new(SPIDER_HEALTH),
-        Attack::new(SPIDER_BASE_ATTACK),
-        Defense::new(SPIDER_BASE_DEFENSE),
-        EnemyAIState {
-            state: EnemyBehaviorState::Patrolling,
-        },
-        EnemyPatrol {
-            origin: adjusted_origin,
-            range: adjusted_range,
-            direction: initial_direction,
-        },
-        EnemyAlert {
-            trigger_radius: 0.0,
-            leash_radius: 0.0,
-        },
-        EnemySpeeds {
-            patrol: SPIDER_PATROL_SPEED,
-            chase: SPIDER_PATROL_SPEED,
-        },
-        EnemyAttack {
-            radius: SPIDER_ATTACK_RADIUS,
-            cooldown: {
-                let mut timer = Timer::from_seconds(SPIDER_ATTACK_COOLDOWN, TimerMode::Repeating);
-                timer.set_elapsed(timer.duration());
-                timer
-            },
-        },
-    ));
+    spawn_enemy_from_archetype(
+        commands,
+        asset_server,
+        archetype,
+        placement,
+        EnemySpawnStats::from_archetype(archetype),
+    );
 }
 
-pub fn slime_ai_system(
+pub fn chaser_ai_system(
     time: Res<Time>,
-    session: Res<GameSession>,
-    player_query: Query<&Transform, (With<Player>, Without<Slime>, Without<PlayerDead>)>,
-    mut slime_query: Query<
+    tile_map: Res<TileMap>,
+    navigation: Option<Res<NavigationGrid>>,
+    player_query: Query<&Transform, (With<Player>, Without<ChaserAi>, Without<PlayerDead>)>,
+    mut chaser_query: Query<
         (
             &mut Transform,
             &mut EnemyAIState,
             &mut EnemyPatrol,
             &EnemyAlert,
             &EnemySpeeds,
+            Option<&StatusEffects>,
+            Option<&mut EnemyPath>,
+            Option<&mut EnemyPatrolRoute>,
+        ),
+        (
+            With<ChaserAi>,
+            Without<Player>,
+            Without<EnemyDeathEffect>,
+            Without<HitStun>,
         ),
-        (With<Slime>, Without<Player>, Without<EnemyDeathEffect>),
     >,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
     let player_position = player_query
         .iter()
         .next()
         .map(|transform| transform.translation);
 
-    process_enemy_ai::<Slime>(time.as_ref(), player_position, &mut slime_query);
+    process_enemy_ai::<ChaserAi>(
+        time.as_ref(),
+        tile_map.as_ref(),
+        navigation.as_deref(),
+        player_position,
+        &mut chaser_query,
+    );
 }
 
-pub fn spider_ai_system(
+pub fn web_shooter_ai_system(
     time: Res<Time>,
-    session: Res<GameSession>,
     player_query: Query<&Transform, (With<Player>, Without<PlayerDead>)>,
     mut spider_query: Query<
         (
@@ -415,14 +394,16 @@ pub fn spider_ai_system(
             &mut EnemyPatrol,
             &EnemyAlert,
             &EnemySpeeds,
+            Option<&StatusEffects>,
+        ),
+        (
+            With<WebShooterAi>,
+            Without<Player>,
+            Without<EnemyDeathEffect>,
+            Without<HitStun>,
         ),
-        (With<Spider>, Without<Player>, Without<EnemyDeathEffect>),
     >,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
     let player_position = player_query
         .iter()
         .next()
@@ -431,7 +412,8 @@ pub fn spider_ai_system(
     let delta_secs = time.delta_secs();
 
     // Spiders keep pacing their patrol lane and nudge away from the player when pressed.
-    for (mut transform, mut ai_state, mut patrol, _alert, speeds) in &mut spider_query {
+    for (mut transform, mut ai_state, mut patrol, _alert, speeds, status) in &mut spider_query {
+        let speeds = speeds.scaled(status.map_or(1.0, StatusEffects::movement_multiplier));
         ai_state.state = EnemyBehaviorState::Patrolling;
 
         let (min_x, max_x) = patrol.bounds();
@@ -486,24 +468,28 @@ pub fn spider_ai_system(
     }
 }
 
-pub fn spider_ranged_attack_system(
+pub fn web_shooter_attack_system(
     mut commands: Commands,
     time: Res<Time>,
-    session: Res<GameSession>,
-    mut spider_query: Query<(&Transform, &mut EnemyAttack, &Attack), With<Spider>>,
+    mut spider_query: Query<
+        (
+            &Transform,
+            &mut EnemyAttack,
+            &Attack,
+            Option<&OnHitStatusEffects>,
+        ),
+        (With<WebShooterAi>, Without<HitStun>),
+    >,
     player_query: Query<&Transform, (With<Player>, Without<PlayerDead>)>,
+    tile_map: Res<TileMap>,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
     let Some(player_transform) = player_query.iter().next() else {
         return;
     };
 
     let player_position = player_transform.translation.truncate();
 
-    for (transform, mut attack, attack_stat) in &mut spider_query {
+    for (transform, mut attack, attack_stat, on_hit) in &mut spider_query {
         attack.cooldown.tick(time.delta());
 
         if !attack.cooldown.finished() {
@@ -517,6 +503,11 @@ pub fn spider_ranged_attack_system(
             continue;
         }
 
+        // 看不到玩家時不浪費蛛網去打牆
+        if !tile_map.has_line_of_sight(transform.translation.truncate(), player_position) {
+            continue;
+        }
+
         let direction = to_player.normalize_or_zero();
         if direction == Vec2::ZERO {
             continue;
@@ -527,16 +518,17 @@ pub fn spider_ranged_attack_system(
             transform.translation,
             direction,
             attack_stat.value(),
+            on_hit,
         );
 
         attack.cooldown.reset();
     }
 }
 
-pub fn cyclops_ai_system(
+pub fn charger_ai_system(
     time: Res<Time>,
-    session: Res<GameSession>,
-    player_query: Query<&Transform, (With<Player>, Without<Cyclops>, Without<PlayerDead>)>,
+    tile_map: Res<TileMap>,
+    player_query: Query<&Transform, (With<Player>, Without<ChargerAi>, Without<PlayerDead>)>,
     mut cyclops_query: Query<
         (
             &mut Transform,
@@ -544,15 +536,17 @@ pub fn cyclops_ai_system(
             &mut EnemyPatrol,
             &EnemyAlert,
             &EnemySpeeds,
+            Option<&StatusEffects>,
             &mut CyclopsCharge,
         ),
-        (With<Cyclops>, Without<Player>, Without<EnemyDeathEffect>),
+        (
+            With<ChargerAi>,
+            Without<Player>,
+            Without<EnemyDeathEffect>,
+            Without<HitStun>,
+        ),
     >,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
     let player_position = player_query
         .iter()
         .next()
@@ -561,7 +555,10 @@ pub fn cyclops_ai_system(
     let delta = time.delta();
     let delta_secs = time.delta_secs();
 
-    for (mut transform, mut ai_state, mut patrol, alert, speeds, mut charge) in &mut cyclops_query {
+    for (mut transform, mut ai_state, mut patrol, alert, speeds, status, mut charge) in
+        &mut cyclops_query
+    {
+        let speeds = speeds.scaled(status.map_or(1.0, StatusEffects::movement_multiplier));
         if !charge.ready {
             charge.cooldown.tick(delta);
             if charge.cooldown.finished() {
@@ -625,8 +622,20 @@ pub fn cyclops_ai_system(
                 _ => {}
             }
 
-            if distance_to_player <= alert.trigger_radius {
-                if charge.ready {
+            let visible =
+                tile_map.has_line_of_sight(transform.translation.truncate(), player_pos.truncate());
+            track_player(
+                &mut ai_state,
+                &mut patrol,
+                alert,
+                transform.translation.x,
+                visible,
+                distance_to_player,
+                player_pos.truncate(),
+            );
+
+            if ai_state.state == EnemyBehaviorState::Chasing {
+                if charge.ready && distance_to_player <= alert.trigger_radius {
                     ai_state.state = EnemyBehaviorState::WindUp;
                     charge.windup.reset();
                     charge.facing = to_player.truncate().normalize_or_zero();
@@ -652,11 +661,21 @@ pub fn cyclops_ai_system(
 
                     continue;
                 }
-            } else if distance_to_player > alert.leash_radius {
-                ai_state.state = EnemyBehaviorState::Patrolling;
             }
         } else {
-            ai_state.state = EnemyBehaviorState::Patrolling;
+            ai_state.give_up();
+        }
+
+        if ai_state.state == EnemyBehaviorState::Investigating
+            && investigate_along_lane(
+                &mut ai_state,
+                &mut patrol,
+                &mut transform,
+                speeds.patrol,
+                delta,
+            )
+        {
+            continue;
         }
 
         let delta_move = patrol.direction * speeds.patrol * delta_secs;
@@ -675,12 +694,12 @@ pub fn cyclops_ai_system(
     }
 }
 
-pub fn boss_wizard_ai_system(
+pub fn spell_caster_ai_system(
     mut commands: Commands,
     time: Res<Time>,
-    session: Res<GameSession>,
     mut spell_events: EventWriter<BossWizardSpellCastEvent>,
-    player_query: Query<&Transform, (With<Player>, Without<BossWizard>, Without<PlayerDead>)>,
+    tile_map: Res<TileMap>,
+    player_query: Query<&Transform, (With<Player>, Without<SpellCasterAi>, Without<PlayerDead>)>,
     mut wizard_query: Query<
         (
             &mut Transform,
@@ -688,16 +707,20 @@ pub fn boss_wizard_ai_system(
             &mut EnemyPatrol,
             &EnemyAlert,
             &EnemySpeeds,
+            Option<&StatusEffects>,
             &mut EnemyAttack,
             &Attack,
+            Option<&OnHitStatusEffects>,
+            Option<&BossScript>,
+        ),
+        (
+            With<SpellCasterAi>,
+            Without<Player>,
+            Without<EnemyDeathEffect>,
+            Without<HitStun>,
         ),
-        (With<BossWizard>, Without<Player>, Without<EnemyDeathEffect>),
     >,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
     let player_position = player_query
         .iter()
         .next()
@@ -706,9 +729,23 @@ pub fn boss_wizard_ai_system(
     let delta = time.delta();
     let delta_secs = time.delta_secs();
 
-    for (mut transform, mut ai_state, mut patrol, alert, speeds, mut attack, attack_stat) in
-        &mut wizard_query
+    for (
+        mut transform,
+        mut ai_state,
+        mut patrol,
+        alert,
+        speeds,
+        status,
+        mut attack,
+        attack_stat,
+        on_hit,
+        script,
+    ) in &mut wizard_query
     {
+        let speeds = speeds.scaled(
+            status.map_or(1.0, StatusEffects::movement_multiplier)
+                * script.map_or(1.0, BossScript::speed_multiplier),
+        );
         attack.cooldown.tick(delta);
         let (min_x, max_x) = patrol.bounds();
 
@@ -716,9 +753,19 @@ pub fn boss_wizard_ai_system(
             let to_player = player_pos - transform.translation;
             let distance = to_player.truncate().length();
 
-            if distance <= alert.trigger_radius {
-                ai_state.state = EnemyBehaviorState::Chasing;
+            let visible =
+                tile_map.has_line_of_sight(transform.translation.truncate(), player_pos.truncate());
+            track_player(
+                &mut ai_state,
+                &mut patrol,
+                alert,
+                transform.translation.x,
+                visible,
+                distance,
+                player_pos.truncate(),
+            );
 
+            if ai_state.state == EnemyBehaviorState::Chasing {
                 let direction = to_player.truncate().normalize_or_zero();
                 let dx = player_pos.x - transform.translation.x;
 
@@ -731,7 +778,7 @@ pub fn boss_wizard_ai_system(
                     let retreat = retreat_dir * speeds.chase * delta_secs;
                     transform.translation.x =
                         (transform.translation.x + retreat).clamp(min_x, max_x);
-                } else if distance > WIZARD_BOSS_ATTACK_RADIUS {
+                } else if distance > attack.radius {
                     let advance_dir = if dx.abs() > f32::EPSILON {
                         dx.signum()
                     } else {
@@ -747,12 +794,14 @@ pub fn boss_wizard_ai_system(
                 transform.translation.y = patrol.origin.y;
                 transform.translation.z = patrol.origin.z;
 
-                if distance <= WIZARD_BOSS_ATTACK_RADIUS && attack.cooldown.finished() {
+                // 有招式腳本的頭目改由 boss_pattern_system 施法
+                if distance <= attack.radius && attack.cooldown.finished() && script.is_none() {
                     spawn_wizard_projectile(
                         &mut commands,
                         transform.translation,
                         direction,
                         attack_stat.value(),
+                        on_hit,
                     );
                     spell_events.write(BossWizardSpellCastEvent);
                     attack.cooldown.reset();
@@ -764,9 +813,21 @@ pub fn boss_wizard_ai_system(
 
                 continue;
             }
+        } else {
+            ai_state.give_up();
         }
 
-        ai_state.state = EnemyBehaviorState::Patrolling;
+        if ai_state.state == EnemyBehaviorState::Investigating
+            && investigate_along_lane(
+                &mut ai_state,
+                &mut patrol,
+                &mut transform,
+                speeds.patrol,
+                delta,
+            )
+        {
+            continue;
+        }
 
         let delta_move = patrol.direction * speeds.patrol * delta_secs;
         transform.translation.x += delta_move;
@@ -784,267 +845,172 @@ pub fn boss_wizard_ai_system(
     }
 }
 
+/// 法師的魔法彈命中玩家；剛舉盾時會被格擋反擊，反彈回去變成玩家的飛行物
 pub fn boss_wizard_projectile_system(
     mut commands: Commands,
-    time: Res<Time>,
-    session: Res<GameSession>,
     mut projectile_query: Query<(
         Entity,
-        &mut Transform,
+        &Transform,
+        &mut Projectile,
         &BossWizardProjectile,
-        &mut BossWizardProjectileLifetime,
+        Option<&OnHitStatusEffects>,
     )>,
-    player_target_query: Query<
-        (&Transform, Option<&Defense>),
+    player_query: Query<
         (
-            With<Player>,
-            Without<PlayerDead>,
-            Without<BossWizardProjectile>,
+            Entity,
+            &Transform,
+            &PlayerFacing,
+            Option<(&Blocking, &EquippedShield)>,
         ),
-    >,
-    mut player_health_query: Query<
-        &mut Health,
         (
             With<Player>,
             Without<PlayerDead>,
             Without<BossWizardProjectile>,
         ),
     >,
-    mut damage_events: EventWriter<PlayerDamagedEvent>,
-    mut enemy_attack_events: EventWriter<EnemyAttackHitEvent>,
+    mut damage_requests: EventWriter<DamageRequest>,
+    mut status_requests: EventWriter<StatusEffectRequest>,
+    mut parry_events: EventWriter<PlayerParryEvent>,
 ) {
-    if !session.is_playing() {
+    let Ok((player_entity, player_transform, facing, guard)) = player_query.single() else {
         return;
-    }
+    };
+    let player_pos = player_transform.translation.truncate();
 
-    let delta = time.delta();
-    let delta_secs = time.delta_secs();
+    let mut hits: Vec<(
+        Entity,
+        &Projectile,
+        &BossWizardProjectile,
+        Option<&OnHitStatusEffects>,
+    )> = projectile_query
+        .iter()
+        .filter(|(_, transform, _, _, _)| {
+            transform.translation.truncate().distance(player_pos)
+                <= WIZARD_BOSS_PROJECTILE_HIT_RADIUS
+        })
+        .map(|(entity, _, motion, projectile, on_hit)| (entity, motion, projectile, on_hit))
+        .collect();
 
-    let player_target = player_target_query.single().ok();
-    let player_position = player_target.map(|(transform, _)| transform.translation.truncate());
-    let defense_value = player_target.and_then(|(_, defense)| defense.map(|value| value.value()));
+    hits.sort_unstable_by_key(|(entity, _, _, _)| entity.index());
 
-    let mut hits: Vec<(Entity, i32)> = Vec::new();
+    let mut reflected = Vec::new();
 
-    for (entity, mut transform, projectile, mut lifetime) in &mut projectile_query {
-        transform.translation.x += projectile.velocity.x * delta_secs;
-        transform.translation.y += projectile.velocity.y * delta_secs;
+    for (entity, motion, projectile, on_hit) in hits {
+        let parried = guard.is_some_and(|(blocking, shield)| {
+            blocking.outcome(shield.kind, facing.direction, motion.direction())
+                == BlockOutcome::Parried
+        });
 
-        lifetime.timer.tick(delta);
-        if lifetime.timer.finished() {
-            commands.entity(entity).despawn();
+        if parried {
+            reflected.push((entity, projectile.damage));
             continue;
         }
 
-        if let Some(player_pos) = player_position {
-            let distance = transform.translation.truncate().distance(player_pos);
-            if distance <= WIZARD_BOSS_PROJECTILE_HIT_RADIUS {
-                let damage = compute_damage(projectile.damage, defense_value);
-                hits.push((entity, damage));
-            }
-        }
-    }
-
-    if hits.is_empty() {
-        return;
+        commands.entity(entity).despawn();
+        damage_requests.write(
+            DamageRequest::new(player_entity, projectile.damage, DamageType::Magic)
+                .with_source(entity)
+                .with_knockback(motion.direction() * PROJECTILE_KNOCKBACK),
+        );
+        inflict_status_effects(
+            player_entity,
+            motion.direction(),
+            on_hit,
+            &mut status_requests,
+        );
     }
 
-    hits.sort_unstable_by_key(|(entity, _)| entity.index());
-
-    if let Ok(mut health) = player_health_query.single_mut() {
-        for (entity, damage) in hits {
-            commands.entity(entity).despawn();
-
-            if damage <= 0 {
-                continue;
-            }
+    for (entity, damage) in reflected {
+        let Ok((_, _, mut motion, _, _)) = projectile_query.get_mut(entity) else {
+            continue;
+        };
 
-            let new_health = (health.current - damage).max(0);
-            if new_health != health.current {
-                health.current = new_health;
-                damage_events.write(PlayerDamagedEvent {
+        motion.velocity = -motion.velocity;
+        motion.lifetime.reset();
+        commands
+            .entity(entity)
+            .remove::<(BossWizardProjectile, HomingProjectile)>()
+            .insert((
+                PlayerProjectile {
                     damage,
-                    remaining_health: health.current,
-                });
-                enemy_attack_events.write(EnemyAttackHitEvent);
-            }
-        }
-    } else {
-        for (entity, _) in hits {
-            commands.entity(entity).despawn();
-        }
+                    damage_type: DamageType::Magic,
+                },
+                Name::new("ReflectedWizardProjectile"),
+            ));
+        parry_events.write(PlayerParryEvent { attacker: None });
+        dev_info!("Wizard projectile reflected");
     }
 }
 
 pub fn spider_web_projectile_system(
     mut commands: Commands,
-    time: Res<Time>,
-    session: Res<GameSession>,
-    mut projectile_query: Query<
-        (
-            Entity,
-            &mut Transform,
-            &SpiderWebProjectile,
-            &mut SpiderWebProjectileLifetime,
-        ),
-        With<SpiderWebProjectile>,
-    >,
-    wall_query: Query<(Entity, &RoomTile, &Transform), Without<SpiderWebProjectile>>,
-    door_query: Query<&Door>,
-    mut player_query: Query<
-        (
-            Entity,
-            &Transform,
-            &mut Health,
-            Option<&Defense>,
-            Option<&mut Poisoned>,
-        ),
+    projectile_query: Query<(
+        Entity,
+        &Transform,
+        &SpiderWebProjectile,
+        Option<&OnHitStatusEffects>,
+    )>,
+    player_query: Query<
+        (Entity, &Transform),
         (
             With<Player>,
             Without<PlayerDead>,
             Without<SpiderWebProjectile>,
         ),
     >,
-    mut damage_events: EventWriter<PlayerDamagedEvent>,
-    mut enemy_attack_events: EventWriter<EnemyAttackHitEvent>,
+    mut damage_requests: EventWriter<DamageRequest>,
+    mut status_requests: EventWriter<StatusEffectRequest>,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
-    let mut player_iter = player_query.iter_mut();
-    let Some((player_entity, player_transform, mut health, defense, poison_option)) =
-        player_iter.next()
-    else {
+    let Some((player_entity, player_transform)) = player_query.iter().next() else {
         return;
     };
 
     let player_position = player_transform.translation.truncate();
-    let defense_value = defense.map(|value| value.value());
-    let mut poison_component = poison_option;
-    let mut poison_active = poison_component.is_some();
-
-    let tile_size = ROOM_TILE_SIZE * PLAYER_SCALE;
-    let mut blocking_tiles: HashMap<(i32, i32), (RoomTileType, Entity)> = HashMap::new();
+    let half_length = SPIDER_WEB_PROJECTILE_LENGTH * 0.5;
 
-    for (entity, room_tile, transform) in &wall_query {
-        let tile_key = (
-            (transform.translation.x / tile_size).round() as i32,
-            (transform.translation.y / tile_size).round() as i32,
-        );
-
-        let new_value = (room_tile.tile_type, entity);
-        match blocking_tiles.get(&tile_key) {
-            Some((existing_type, existing_entity)) => {
-                let existing_priority = projectile_tile_priority(*existing_type);
-                let new_priority = projectile_tile_priority(room_tile.tile_type);
-                if new_priority > existing_priority
-                    || (new_priority == existing_priority
-                        && entity.index() < existing_entity.index())
-                {
-                    blocking_tiles.insert(tile_key, new_value);
-                }
-            }
-            None => {
-                blocking_tiles.insert(tile_key, new_value);
-            }
-        }
-    }
-
-    for (entity, mut transform, projectile, mut lifetime) in &mut projectile_query {
-        let previous_translation = transform.translation;
-
-        let delta_secs = time.delta_secs();
-        transform.translation.x += projectile.velocity.x * delta_secs;
-        transform.translation.y += projectile.velocity.y * delta_secs;
-
-        let angle = projectile.direction.y.atan2(projectile.direction.x);
-        transform.rotation = Quat::from_rotation_z(angle);
-
-        lifetime.timer.tick(time.delta());
-
-        let previous_center = previous_translation.truncate();
+    for (entity, transform, projectile, on_hit) in &projectile_query {
         let center = transform.translation.truncate();
         let to_player = player_position - center;
         let along = to_player.dot(projectile.direction);
-        let half_length = SPIDER_WEB_PROJECTILE_LENGTH * 0.5;
-
-        let mut blocked = false;
-
-        let travel = center - previous_center;
-        let steps = 4;
-        for step in 0..=steps {
-            let t = step as f32 / steps as f32;
-            let sample = previous_center + travel * t;
-            if projectile_blocked_at(sample, tile_size, &blocking_tiles, &door_query) {
-                blocked = true;
-                break;
-            }
-        }
 
-        if !blocked {
-            let forward_tip = center + projectile.direction * half_length;
-            let backward_tip = center - projectile.direction * half_length;
-
-            if projectile_blocked_at(forward_tip, tile_size, &blocking_tiles, &door_query)
-                || projectile_blocked_at(backward_tip, tile_size, &blocking_tiles, &door_query)
-            {
-                blocked = true;
-            }
+        if along.abs() > half_length {
+            continue;
         }
 
-        if blocked {
-            commands.entity(entity).despawn();
+        let perpendicular = (to_player - projectile.direction * along).length();
+        if perpendicular > SPIDER_WEB_PROJECTILE_HIT_RADIUS {
             continue;
         }
 
-        if along.abs() <= half_length {
-            let perpendicular = (to_player - projectile.direction * along).length();
-            if perpendicular <= SPIDER_WEB_PROJECTILE_HIT_RADIUS {
-                let damage = compute_damage(projectile.damage, defense_value);
-                if damage > 0 {
-                    let new_health = (health.current - damage).max(0);
-                    if new_health != health.current {
-                        health.current = new_health;
-                        damage_events.write(PlayerDamagedEvent {
-                            damage,
-                            remaining_health: health.current,
-                        });
-                    }
-                }
-
-                if let Some(poison) = poison_component.as_mut() {
-                    poison.reset_timer();
-                } else if !poison_active {
-                    commands.entity(player_entity).insert(Poisoned::new(
-                        PLAYER_POISON_TICK_SECONDS,
-                        PLAYER_POISON_TICK_DAMAGE,
-                    ));
-                    poison_active = true;
-                }
+        damage_requests.write(
+            DamageRequest::new(player_entity, projectile.damage, DamageType::Physical)
+                .with_source(entity)
+                .with_knockback(projectile.direction * PROJECTILE_KNOCKBACK),
+        );
 
-                dev_info!(
-                    "Spider web struck player for {} damage (poison active={})",
-                    damage,
-                    poison_active
-                );
+        inflict_status_effects(
+            player_entity,
+            projectile.direction,
+            on_hit,
+            &mut status_requests,
+        );
 
-                enemy_attack_events.write(EnemyAttackHitEvent);
-                commands.entity(entity).despawn();
-                continue;
-            }
-        }
+        dev_info!("Spider web struck player");
 
-        if lifetime.timer.finished() {
-            commands.entity(entity).despawn();
-        }
+        commands.entity(entity).despawn();
     }
 }
 
-fn spawn_wizard_projectile(commands: &mut Commands, origin: Vec3, direction: Vec2, damage: i32) {
+pub fn spawn_wizard_projectile(
+    commands: &mut Commands,
+    origin: Vec3,
+    direction: Vec2,
+    damage: i32,
+    on_hit: Option<&OnHitStatusEffects>,
+) -> Option<Entity> {
     let direction = direction.normalize_or_zero();
     if direction == Vec2::ZERO {
-        return;
+        return None;
     }
 
     let offset_distance = WIZARD_BOSS_STAFF_OFFSET_X.max(6.0) + WIZARD_BOSS_PROJECTILE_SIZE * 0.5;
@@ -1054,22 +1020,21 @@ fn spawn_wizard_projectile(commands: &mut Commands, origin: Vec3, direction: Vec
         origin.z + WIZARD_BOSS_CAST_HEIGHT_OFFSET,
     );
 
-    commands.spawn((
-        LevelEntity,
-        BossWizardProjectile {
-            velocity: direction * WIZARD_BOSS_PROJECTILE_SPEED,
-            damage,
-        },
-        BossWizardProjectileLifetime {
-            timer: Timer::from_seconds(WIZARD_BOSS_PROJECTILE_LIFETIME, TimerMode::Once),
-        },
-        Sprite::from_color(
-            WIZARD_BOSS_PROJECTILE_COLOR,
+    let mut projectile = commands.spawn((
+        projectile_bundle(
+            spawn_position,
+            direction * WIZARD_BOSS_PROJECTILE_SPEED,
             Vec2::splat(WIZARD_BOSS_PROJECTILE_SIZE),
+            WIZARD_BOSS_PROJECTILE_COLOR,
+            WIZARD_BOSS_PROJECTILE_LIFETIME,
         ),
-        Transform::from_translation(spawn_position),
+        BossWizardProjectile { damage },
         Name::new("BossWizardProjectile"),
     ));
+    if let Some(on_hit) = on_hit {
+        projectile.insert(on_hit.clone());
+    }
+    Some(projectile.id())
 }
 
 fn spawn_spider_web_projectile(
@@ -1077,6 +1042,7 @@ fn spawn_spider_web_projectile(
     origin: Vec3,
     direction: Vec2,
     damage: i32,
+    on_hit: Option<&OnHitStatusEffects>,
 ) {
     let direction = direction.normalize_or_zero();
     if direction == Vec2::ZERO {
@@ -1091,99 +1057,29 @@ fn spawn_spider_web_projectile(
         origin.z + 12.0,
     );
 
-    let angle = direction.y.atan2(direction.x);
-
-    let mut transform = Transform::from_translation(spawn_position);
-    transform.rotation = Quat::from_rotation_z(angle);
-
-    commands.spawn((
-        LevelEntity,
-        SpiderWebProjectile {
-            velocity: direction * SPIDER_WEB_PROJECTILE_SPEED,
-            damage,
-            direction,
-        },
-        SpiderWebProjectileLifetime {
-            timer: Timer::from_seconds(SPIDER_WEB_PROJECTILE_LIFETIME, TimerMode::Once),
-        },
-        Sprite {
-            color: Color::srgba(0.95, 0.98, 1.0, 0.88),
-            custom_size: Some(Vec2::new(
+    let mut projectile = commands.spawn((
+        projectile_bundle(
+            spawn_position,
+            direction * SPIDER_WEB_PROJECTILE_SPEED,
+            Vec2::new(
                 SPIDER_WEB_PROJECTILE_LENGTH,
                 SPIDER_WEB_PROJECTILE_THICKNESS,
-            )),
-            ..Default::default()
-        },
-        transform,
+            ),
+            Color::srgba(0.95, 0.98, 1.0, 0.88),
+            SPIDER_WEB_PROJECTILE_LIFETIME,
+        ),
+        SpiderWebProjectile { damage, direction },
         Name::new("SpiderWebProjectile"),
     ));
-}
-
-fn projectile_blocked_at(
-    point: Vec2,
-    tile_size: f32,
-    blocking_tiles: &HashMap<(i32, i32), (RoomTileType, Entity)>,
-    door_query: &Query<&Door>,
-) -> bool {
-    let tile_key = (
-        (point.x / tile_size).round() as i32,
-        (point.y / tile_size).round() as i32,
-    );
-
-    if let Some((tile_type, entity)) = blocking_tiles.get(&tile_key) {
-        tile_blocks_projectile(*tile_type, *entity, door_query)
-    } else {
-        false
-    }
-}
-
-fn tile_blocks_projectile(
-    tile_type: RoomTileType,
-    entity: Entity,
-    door_query: &Query<&Door>,
-) -> bool {
-    match tile_type {
-        RoomTileType::Floor | RoomTileType::FloorOutdoor | RoomTileType::DoorOpen => false,
-        RoomTileType::DoorClosed => door_query
-            .get(entity)
-            .map(|door| !door.is_open)
-            .unwrap_or(true),
-        RoomTileType::WallNInnerCornerW
-        | RoomTileType::WallNInnerMid
-        | RoomTileType::WallNInnerCornerE
-        | RoomTileType::WallSInnerCapL
-        | RoomTileType::WallSInnerMid
-        | RoomTileType::WallSInnerCapR
-        | RoomTileType::WallSOuterCapL
-        | RoomTileType::WallSOuterMid
-        | RoomTileType::WallSOuterCapR
-        | RoomTileType::WallESide
-        | RoomTileType::WallWSide => true,
-    }
-}
-
-fn projectile_tile_priority(tile_type: RoomTileType) -> u8 {
-    match tile_type {
-        RoomTileType::Floor | RoomTileType::FloorOutdoor => 0,
-        RoomTileType::DoorOpen => 1,
-        RoomTileType::DoorClosed => 2,
-        RoomTileType::WallNInnerCornerW
-        | RoomTileType::WallNInnerMid
-        | RoomTileType::WallNInnerCornerE
-        | RoomTileType::WallSInnerCapL
-        | RoomTileType::WallSInnerMid
-        | RoomTileType::WallSInnerCapR
-        | RoomTileType::WallSOuterCapL
-        | RoomTileType::WallSOuterMid
-        | RoomTileType::WallSOuterCapR
-        | RoomTileType::WallESide
-        | RoomTileType::WallWSide => 3,
+    if let Some(on_hit) = on_hit {
+        projectile.insert(on_hit.clone());
     }
 }
 
 fn resolve_spider_patrol_bounds(
     position: Vec3,
     tile_size: f32,
+    max_range: f32,
     floor_tiles: &HashSet<(i32, i32)>,
 ) -> (Vec3, f32) {
     let tile_x = (position.x / tile_size).round() as i32;
@@ -1226,79 +1122,42 @@ fn resolve_spider_patrol_bounds(
 
     let origin_x = (min_edge + max_edge) * 0.5;
     let range = (width * 0.5)
-        .min(SPIDER_PATROL_RANGE)
-        .clamp(tile_size * 0.75, SPIDER_PATROL_RANGE);
+        .min(max_range)
+        .clamp(tile_size * 0.75, max_range);
 
     (Vec3::new(origin_x, position.y, position.z), range)
 }
 
-pub fn mimic_ai_system(
-    time: Res<Time>,
-    session: Res<GameSession>,
-    player_query: Query<&Transform, (With<Player>, Without<Mimic>, Without<PlayerDead>)>,
-    mut mimic_query: Query<
-        (
-            &mut Transform,
-            &mut EnemyAIState,
-            &mut EnemyPatrol,
-            &EnemyAlert,
-            &EnemySpeeds,
-        ),
-        (With<Mimic>, Without<Player>, Without<EnemyDeathEffect>),
-    >,
-) {
-    if !session.is_playing() {
-        return;
-    }
-
-    let player_position = player_query
-        .iter()
-        .next()
-        .map(|transform| transform.translation);
-
-    process_enemy_ai::<Mimic>(time.as_ref(), player_position, &mut mimic_query);
-}
-
 pub fn enemy_contact_attack_system(
     time: Res<Time>,
-    session: Res<GameSession>,
-    mut player_query: Query<
-        (&Transform, &mut Health, Option<&Defense>),
-        (With<Player>, Without<PlayerDead>),
-    >,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    player_query: Query<(Entity, &Transform), (With<Player>, Without<PlayerDead>)>,
     mut attacker_query: Query<
         (
+            Entity,
             &Transform,
             &mut EnemyAttack,
             &Attack,
-            Option<&Slime>,
-            Option<&Mimic>,
-            Option<&Cyclops>,
+            Option<&EnemyArchetypeId>,
+            Option<&OnHitStatusEffects>,
         ),
         (
             With<Enemy>,
+            With<ContactAttacker>,
             Without<EnemyDeathEffect>,
-            Without<BossWizard>,
-            Without<Spider>,
+            Without<HitStun>,
         ),
     >,
-    mut damage_events: EventWriter<PlayerDamagedEvent>,
-    mut enemy_attack_events: EventWriter<EnemyAttackHitEvent>,
+    mut damage_requests: EventWriter<DamageRequest>,
+    mut status_requests: EventWriter<StatusEffectRequest>,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
-    let mut player_iter = player_query.iter_mut();
-    let Some((player_transform, mut health, defense)) = player_iter.next() else {
+    let Some((player_entity, player_transform)) = player_query.iter().next() else {
         return;
     };
 
     let player_position = player_transform.translation.truncate();
 
-    let defense_value = defense.map(|value| value.value());
-
-    for (attacker_transform, mut attack, attack_stat, is_slime, is_mimic, is_cyclops) in
+    for (attacker, attacker_transform, mut attack, attack_stat, archetype_id, on_hit) in
         &mut attacker_query
     {
         attack.cooldown.tick(time.delta());
@@ -1309,33 +1168,21 @@ pub fn enemy_contact_attack_system(
             .distance(player_position);
 
         if distance <= attack.radius && attack.cooldown.finished() {
-            let damage = compute_damage(attack_stat.value(), defense_value);
-            let new_health = (health.current - damage).max(0);
-
-            if new_health != health.current {
-                health.current = new_health;
-                let attacker_name = if is_mimic.is_some() {
-                    "Mimic"
-                } else if is_slime.is_some() {
-                    "Slime"
-                } else if is_cyclops.is_some() {
-                    "Cyclops"
-                } else {
-                    "Enemy"
-                };
-                dev_info!(
-                    "{} attack dealt {} damage; player HP now {}",
-                    attacker_name,
-                    damage,
-                    health.current
-                );
-
-                damage_events.write(PlayerDamagedEvent {
-                    damage,
-                    remaining_health: health.current,
-                });
-                enemy_attack_events.write(EnemyAttackHitEvent);
-            }
+            let attacker_name = archetype_id
+                .and_then(|id| archetypes.get(&id.0))
+                .map(|archetype| archetype.display_name.as_str())
+                .unwrap_or("Enemy");
+            dev_info!("{} attacks the player", attacker_name);
+
+            let push = (player_position - attacker_transform.translation.truncate())
+                .normalize_or_zero()
+                * ENEMY_CONTACT_KNOCKBACK;
+            damage_requests.write(
+                DamageRequest::new(player_entity, attack_stat.value(), DamageType::Physical)
+                    .with_source(attacker)
+                    .with_knockback(push),
+            );
+            inflict_status_effects(player_entity, push, on_hit, &mut status_requests);
 
             attack.cooldown.reset();
         }
@@ -1345,6 +1192,7 @@ pub fn enemy_contact_attack_system(
 pub fn despawn_dead_enemies_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    archetypes: Res<EnemyArchetypeRegistry>,
     mut defeated_events: EventWriter<EnemyDefeatedEvent>,
     mut query: Query<
         (
@@ -1353,30 +1201,15 @@ pub fn despawn_dead_enemies_system(
             &Health,
             Option<&EnemyDeathEffect>,
             &mut Sprite,
-            Option<&Slime>,
-            Option<&Cyclops>,
-            Option<&Spider>,
-            Option<&Mimic>,
-            Option<&BossWizard>,
+            Option<&EnemyArchetypeId>,
+            Has<BossMinion>,
         ),
         With<Enemy>,
     >,
 ) {
     let mut rng = thread_rng();
 
-    for (
-        entity,
-        transform,
-        health,
-        death_effect,
-        mut sprite,
-        slime,
-        cyclops,
-        spider,
-        mimic,
-        wizard,
-    ) in &mut query
-    {
+    for (entity, transform, health, death_effect, mut sprite, archetype_id, minion) in &mut query {
         if health.current > 0 {
             continue;
         }
@@ -1387,44 +1220,54 @@ pub fn despawn_dead_enemies_system(
 
         sprite.color.set_alpha(1.0);
 
-        let (experience_reward, enemy_label) = if wizard.is_some() {
-            (WIZARD_BOSS_EXPERIENCE_REWARD, "Wizard Boss")
-        } else if slime.is_some() {
-            (SLIME_EXPERIENCE_REWARD, "Slime")
-        } else if cyclops.is_some() {
-            (CYCLOPS_EXPERIENCE_REWARD, "Cyclops")
-        } else if spider.is_some() {
-            (SPIDER_EXPERIENCE_REWARD, "Spider")
-        } else if mimic.is_some() {
-            (MIMIC_EXPERIENCE_REWARD, "Mimic")
-        } else {
-            (0, "Enemy")
-        };
+        // 頭目召喚的手下不給經驗也不掉落
+        if minion {
+            commands.entity(entity).insert(EnemyDeathEffect {
+                timer: Timer::from_seconds(ENEMY_DEATH_FADE_SECONDS, TimerMode::Once),
+            });
+            continue;
+        }
+
+        let archetype = archetype_id.and_then(|id| archetypes.get(&id.0));
 
-        if experience_reward > 0 {
+        if let Some(archetype) =
+            archetype.filter(|archetype| archetype.experience > 0 || archetype.boss)
+        {
             defeated_events.write(EnemyDefeatedEvent {
-                experience: experience_reward,
-                enemy_name: enemy_label,
+                position: transform.translation,
+                experience: archetype.experience,
+                enemy_name: archetype.display_name.clone(),
+                is_boss: archetype.boss,
             });
 
             dev_info!(
                 "Defeated {} and earned {} EXP",
-                enemy_label,
-                experience_reward
+                archetype.display_name,
+                archetype.experience
             );
         }
 
-        let drop_effect = if spider.is_some() {
-            PickupEffect::CurePoison
-        } else {
-            random_pickup_effect(&mut rng)
+        let drop_effect = match archetype {
+            Some(archetype) => archetype.drops.roll(&mut rng, random_pickup_effect),
+            None => Some(random_pickup_effect(&mut rng)),
         };
+
+        commands.entity(entity).insert(EnemyDeathEffect {
+            timer: Timer::from_seconds(ENEMY_DEATH_FADE_SECONDS, TimerMode::Once),
+        });
+
+        let Some(drop_effect) = drop_effect else {
+            dev_info!("Enemy defeated, dropped nothing and started fading");
+            continue;
+        };
+
         let drop_label = match &drop_effect {
-            PickupEffect::Heal(_) => "Red potion",
-            PickupEffect::RestoreStamina(_) => "Green potion",
-            PickupEffect::CurePoison => "Antidote",
-            PickupEffect::EquipShield(kind) => kind.display_name(),
-            PickupEffect::EquipWeapon(kind) => kind.display_name(),
+            PickupEffect::Heal(_) => "Red potion".to_string(),
+            PickupEffect::RestoreStamina(_) => "Green potion".to_string(),
+            PickupEffect::CurePoison => "Antidote".to_string(),
+            PickupEffect::EquipShield(kind) => kind.display_name().to_string(),
+            PickupEffect::EquipWeapon { kind, family } => kind.display_name(*family),
+            PickupEffect::EquipRangedWeapon(kind) => kind.display_name().to_string(),
         };
 
         spawn_pickup_entity(
@@ -1434,10 +1277,6 @@ pub fn despawn_dead_enemies_system(
             transform.translation,
         );
 
-        commands.entity(entity).insert(EnemyDeathEffect {
-            timer: Timer::from_seconds(ENEMY_DEATH_FADE_SECONDS, TimerMode::Once),
-        });
-
         dev_info!("Enemy defeated, dropped {} and started fading", drop_label);
     }
 }
@@ -1475,14 +1314,15 @@ pub fn reset_enemies_on_player_respawn(
             Option<&mut CyclopsCharge>,
             Option<&EnemyDeathEffect>,
             Option<&mut Sprite>,
+            Option<&mut BossScript>,
+            Has<BossMinion>,
         ),
-        With<Enemy>,
+        (With<Enemy>, Without<EncounterEnemy>),
     >,
     asset_server: Res<AssetServer>,
-    floor_query: Query<(&Transform, &RoomTile), Without<Enemy>>,
-    slime_entities: Query<Entity, With<Slime>>,
-    cyclops_entities: Query<Entity, With<Cyclops>>,
-    spider_entities: Query<Entity, With<Spider>>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    tile_map: Res<TileMap>,
+    archetype_ids: Query<&EnemyArchetypeId, Without<EncounterEnemy>>,
     projectiles: Query<Entity, With<SpiderWebProjectile>>,
     entrance_location: Option<Res<EntranceLocation>>,
 ) {
@@ -1509,10 +1349,21 @@ pub fn reset_enemies_on_player_respawn(
         charge,
         death_effect,
         sprite,
+        script,
+        minion,
     ) in &mut enemy_query
     {
+        if minion {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
         health.current = health.max;
 
+        if let Some(telegraph) = script.and_then(|mut script| script.enter_phase(0)) {
+            commands.entity(telegraph).despawn();
+        }
+
         if let Some(mut patrol) = patrol {
             transform.translation = patrol.origin;
             patrol.direction = if patrol.direction >= 0.0 { 1.0 } else { -1.0 };
@@ -1547,29 +1398,17 @@ pub fn reset_enemies_on_player_respawn(
         }
     }
 
-    if slime_entities.iter().next().is_none() {
-        spawn_slime_internal(
-            &mut commands,
-            asset_server.as_ref(),
-            &floor_query,
-            entrance_location.as_deref(),
-        );
-    }
-
-    if cyclops_entities.iter().next().is_none() {
-        spawn_cyclops_internal(
-            &mut commands,
-            asset_server.as_ref(),
-            &floor_query,
-            entrance_location.as_deref(),
-        );
-    }
+    for (archetype, slot) in archetypes.respawn_roster() {
+        if archetype_ids.iter().any(|id| id.0 == archetype.id) {
+            continue;
+        }
 
-    if spider_entities.iter().next().is_none() {
-        spawn_spider_internal(
+        spawn_roster_enemy(
             &mut commands,
             asset_server.as_ref(),
-            &floor_query,
+            archetype,
+            slot,
+            &tile_map,
             entrance_location.as_deref(),
         );
     }
@@ -1579,6 +1418,8 @@ pub fn reset_enemies_on_player_respawn(
 
 fn process_enemy_ai<M: Component>(
     time: &Time,
+    tile_map: &TileMap,
+    navigation: Option<&NavigationGrid>,
     player_position: Option<Vec3>,
     query: &mut Query<
         (
@@ -1587,38 +1428,57 @@ fn process_enemy_ai<M: Component>(
             &mut EnemyPatrol,
             &EnemyAlert,
             &EnemySpeeds,
+            Option<&StatusEffects>,
+            Option<&mut EnemyPath>,
+            Option<&mut EnemyPatrolRoute>,
+        ),
+        (
+            With<M>,
+            Without<Player>,
+            Without<EnemyDeathEffect>,
+            Without<HitStun>,
         ),
-        (With<M>, Without<Player>, Without<EnemyDeathEffect>),
     >,
 ) {
-    for (mut transform, mut ai_state, mut patrol, alert, speeds) in query.iter_mut() {
+    for (mut transform, mut ai_state, mut patrol, alert, speeds, status, mut path, route) in
+        query.iter_mut()
+    {
+        let speeds = speeds.scaled(status.map_or(1.0, StatusEffects::movement_multiplier));
+        let position = transform.translation.truncate();
         if let Some(player_pos) = player_position {
             let to_player = player_pos - transform.translation;
             let distance_to_player = to_player.truncate().length();
 
-            match ai_state.state {
-                EnemyBehaviorState::Patrolling => {
-                    if distance_to_player <= alert.trigger_radius {
-                        ai_state.state = EnemyBehaviorState::Chasing;
-                    }
-                }
-                EnemyBehaviorState::Chasing => {
-                    if distance_to_player > alert.leash_radius {
-                        ai_state.state = EnemyBehaviorState::Patrolling;
-                        patrol.direction = if transform.translation.x >= patrol.origin.x {
-                            -1.0
-                        } else {
-                            1.0
-                        };
-                    }
-                }
-                EnemyBehaviorState::WindUp | EnemyBehaviorState::Charging => {
-                    ai_state.state = EnemyBehaviorState::Patrolling;
-                }
+            // 隔著牆或關閉的門看不到玩家
+            let visible = tile_map.has_line_of_sight(position, player_pos.truncate());
+
+            if matches!(
+                ai_state.state,
+                EnemyBehaviorState::WindUp | EnemyBehaviorState::Charging
+            ) {
+                ai_state.state = EnemyBehaviorState::Patrolling;
             }
+            track_player(
+                &mut ai_state,
+                &mut patrol,
+                alert,
+                transform.translation.x,
+                visible,
+                distance_to_player,
+                player_pos.truncate(),
+            );
 
             if ai_state.state == EnemyBehaviorState::Chasing {
-                let direction = to_player.truncate().normalize_or_zero();
+                let direction = match path.as_deref_mut() {
+                    Some(path) => steer_along_path(
+                        navigation,
+                        path,
+                        position,
+                        player_pos.truncate(),
+                        time.delta(),
+                    ),
+                    None => to_player.truncate().normalize_or_zero(),
+                };
                 let velocity = direction * speeds.chase * time.delta_secs();
 
                 transform.translation.x += velocity.x;
@@ -1629,7 +1489,53 @@ fn process_enemy_ai<M: Component>(
                 continue;
             }
         } else {
-            ai_state.state = EnemyBehaviorState::Patrolling;
+            ai_state.give_up();
+        }
+
+        // 前往最後看到玩家的位置，在那裡張望到搜尋時間結束再回去巡邏
+        if ai_state.state == EnemyBehaviorState::Investigating {
+            let target = ai_state.last_known_position.unwrap_or(position);
+            if ai_state.investigate.tick(time.delta()).finished() {
+                ai_state.give_up();
+                patrol.face_origin(transform.translation.x);
+            } else {
+                if target.distance(position) > ENEMY_WAYPOINT_REACHED_DISTANCE {
+                    let direction = match path.as_deref_mut() {
+                        Some(path) => {
+                            steer_along_path(navigation, path, position, target, time.delta())
+                        }
+                        None => (target - position).normalize_or_zero(),
+                    };
+                    let velocity = direction * speeds.patrol * time.delta_secs();
+                    transform.translation.x += velocity.x;
+                    transform.translation.y += velocity.y;
+                }
+                transform.translation.z = patrol.origin.z;
+                continue;
+            }
+        }
+
+        // 有巡邏路徑時沿著導航網格依序走向每個路徑點
+        if let Some(mut route) = route {
+            if route.target().distance(position) <= ENEMY_WAYPOINT_REACHED_DISTANCE {
+                route.advance();
+            }
+            let direction = match path.as_deref_mut() {
+                Some(path) => {
+                    steer_along_path(navigation, path, position, route.target(), time.delta())
+                }
+                None => (route.target() - position).normalize_or_zero(),
+            };
+            let velocity = direction * speeds.patrol * time.delta_secs();
+
+            transform.translation.x += velocity.x;
+            transform.translation.y += velocity.y;
+            transform.translation.z = patrol.origin.z;
+            if velocity.x.abs() > f32::EPSILON {
+                patrol.direction = velocity.x.signum();
+            }
+
+            continue;
         }
 
         let (min_x, max_x) = patrol.bounds();
@@ -1649,10 +1555,66 @@ fn process_enemy_ai<M: Component>(
     }
 }
 
-enum FloorSpawnPreference {
-    LeftMost,
-    RightMost,
-    Center,
+/// 巡邏或搜尋中看到玩家就追擊；追擊中跟丟就改為搜尋最後看到的位置，離太遠就放棄並回頭
+fn track_player(
+    ai_state: &mut EnemyAIState,
+    patrol: &mut EnemyPatrol,
+    alert: &EnemyAlert,
+    x: f32,
+    visible: bool,
+    distance_to_player: f32,
+    player_position: Vec2,
+) {
+    match ai_state.state {
+        EnemyBehaviorState::Patrolling | EnemyBehaviorState::Investigating => {
+            if visible && distance_to_player <= alert.trigger_radius {
+                ai_state.spot(player_position);
+            }
+        }
+        EnemyBehaviorState::Chasing => {
+            if distance_to_player > alert.leash_radius {
+                ai_state.give_up();
+                patrol.face_origin(x);
+            } else if visible {
+                ai_state.last_known_position = Some(player_position);
+            } else {
+                ai_state.lose_sight();
+            }
+        }
+        EnemyBehaviorState::WindUp | EnemyBehaviorState::Charging => {}
+    }
+}
+
+/// 只在巡邏範圍內水平移動的敵人沿著範圍走到最後看到玩家的位置張望；
+/// 搜尋時間結束時放棄並回頭，回傳是否仍在搜尋
+fn investigate_along_lane(
+    ai_state: &mut EnemyAIState,
+    patrol: &mut EnemyPatrol,
+    transform: &mut Transform,
+    speed: f32,
+    delta: Duration,
+) -> bool {
+    if ai_state.investigate.tick(delta).finished() {
+        ai_state.give_up();
+        patrol.face_origin(transform.translation.x);
+        return false;
+    }
+
+    let (min_x, max_x) = patrol.bounds();
+    let target_x = ai_state
+        .last_known_position
+        .map_or(transform.translation.x, |position| position.x)
+        .clamp(min_x, max_x);
+    let dx = target_x - transform.translation.x;
+    if dx.abs() > ENEMY_WAYPOINT_REACHED_DISTANCE {
+        let step = (speed * delta.as_secs_f32()).min(dx.abs());
+        transform.translation.x += dx.signum() * step;
+        patrol.direction = dx.signum();
+    }
+
+    transform.translation.y = patrol.origin.y;
+    transform.translation.z = patrol.origin.z;
+    true
 }
 
 struct FloorSpawnInfo {
@@ -1662,7 +1624,7 @@ struct FloorSpawnInfo {
 }
 
 impl FloorSpawnInfo {
-    fn into_values(self, preference: FloorSpawnPreference) -> (Vec3, Vec3, f32, f32) {
+    fn into_placement(self, preference: FloorSpawnPreference, max_range: f32) -> EnemyPlacement {
         let tile_span = ROOM_TILE_SIZE * PLAYER_SCALE;
         let patrol_center_x = (self.min_x + self.max_x) * 0.5;
         let spawn_position = match preference {
@@ -1673,11 +1635,6 @@ impl FloorSpawnInfo {
             }
         };
 
-        let max_range = match preference {
-            FloorSpawnPreference::LeftMost => SLIME_PATROL_RANGE,
-            FloorSpawnPreference::RightMost => CYCLOPS_PATROL_RANGE,
-            FloorSpawnPreference::Center => SPIDER_PATROL_RANGE,
-        };
         let half_width = (self.max_x - self.min_x) * 0.5;
         let margin = tile_span * 0.1;
         let patrol_range = (half_width - margin).max(tile_span * 0.25).min(max_range);
@@ -1687,37 +1644,39 @@ impl FloorSpawnInfo {
             FloorSpawnPreference::Center => 1.0,
         };
 
-        (
-            spawn_position,
-            Vec3::new(patrol_center_x, spawn_position.y, spawn_position.z),
+        EnemyPlacement {
+            position: spawn_position,
+            patrol_origin: Vec3::new(patrol_center_x, spawn_position.y, spawn_position.z),
             patrol_range,
             direction,
-        )
+        }
     }
 }
 
 fn find_floor_spawn(
-    floor_query: &Query<(&Transform, &RoomTile), Without<Enemy>>,
+    tile_map: &TileMap,
     preference: FloorSpawnPreference,
 ) -> Option<FloorSpawnInfo> {
-    let tile_span = ROOM_TILE_SIZE * PLAYER_SCALE;
+    let tile_span = tile_map.tile_size();
     let mut rows: HashMap<i32, (f32, f32)> = HashMap::new();
     let mut best: Option<FloorSpawnInfo> = None;
 
-    for (transform, tile) in floor_query.iter() {
-        if tile.tile_type != RoomTileType::Floor {
-            continue;
-        }
+    // 依生成順序（由下而上、由左而右）掃描，結果才不會因為雜湊順序而改變
+    let mut floor_cells: Vec<(i32, i32)> = tile_map
+        .tiles()
+        .filter(|(_, tile)| tile.tile_type == RoomTileType::Floor)
+        .map(|(cell, _)| cell)
+        .collect();
+    floor_cells.sort_by_key(|(x, y)| (*y, *x));
 
-        let row_key = (transform.translation.y / tile_span).round() as i32;
-        let entry = rows
-            .entry(row_key)
-            .or_insert((transform.translation.x, transform.translation.x));
-        entry.0 = entry.0.min(transform.translation.x);
-        entry.1 = entry.1.max(transform.translation.x);
+    for cell in floor_cells {
+        let position = tile_map.cell_center(cell);
+        let entry = rows.entry(cell.1).or_insert((position.x, position.x));
+        entry.0 = entry.0.min(position.x);
+        entry.1 = entry.1.max(position.x);
 
         let candidate = FloorSpawnInfo {
-            position: Vec3::new(transform.translation.x, transform.translation.y, 9.0),
+            position: Vec3::new(position.x, position.y, 9.0),
             min_x: entry.0,
             max_x: entry.1,
         };
@@ -10,6 +10,12 @@ pub struct ShieldEquipEvent {
 #[derive(Event, Debug, Clone, Copy)]
 pub struct WeaponEquipEvent {
     pub kind: WeaponKind,
+    pub family: WeaponFamily,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RangedWeaponEquipEvent {
+    pub kind: RangedWeaponKind,
 }
 
 pub fn handle_shield_equip_events(
@@ -109,7 +115,7 @@ pub fn handle_weapon_equip_events(
             attack.adjust_bonus(delta);
         }
 
-        *equipped_weapon = EquippedWeapon::new(event.kind);
+        *equipped_weapon = EquippedWeapon::new(event.kind, event.family);
 
         let mut updated_weapon = false;
         if let Some(children) = children {
@@ -125,9 +131,10 @@ pub fn handle_weapon_equip_events(
                     weapon_sprites.left_sprite = left_handle.clone();
                     sprite.image = right_handle.clone();
 
-                    commands
-                        .entity(entity)
-                        .insert(Name::new(format!("Equipped{}", event.kind.display_name())));
+                    commands.entity(entity).insert(Name::new(format!(
+                        "Equipped{}",
+                        equipped_weapon.display_name()
+                    )));
 
                     updated_weapon = true;
                     break;
@@ -147,7 +154,33 @@ pub fn handle_weapon_equip_events(
         dev_info!(
             "Weapon equipped; current attack: {} ({})",
             attack.value(),
-            equipped_weapon.kind.display_name()
+            equipped_weapon.display_name()
         );
     }
 }
+
+/// 裝備遠程武器；重複撿到同一把時會補滿箭矢
+pub fn handle_ranged_weapon_equip_events(
+    mut commands: Commands,
+    mut events: EventReader<RangedWeaponEquipEvent>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    let Some(player_entity) = player_query.iter().next() else {
+        events.clear();
+        return;
+    };
+
+    let mut last_event = None;
+    for event in events.read() {
+        last_event = Some(*event);
+    }
+
+    let Some(event) = last_event else {
+        return;
+    };
+
+    commands
+        .entity(player_entity)
+        .insert(EquippedRangedWeapon::new(event.kind));
+    dev_info!("Ranged weapon equipped: {}", event.kind.display_name());
+}
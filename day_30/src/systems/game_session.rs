@@ -1,34 +1,45 @@
 use crate::components::{
-    Attack, Defense, EquippedShield, EquippedWeapon, Health, MainMenuAction, MainMenuButton,
-    MainMenuRoot, PauseMenuAction, PauseMenuButton, PauseMenuRoot, Player, PlayerDead,
-    PlayerProgression, Poisoned,
+    Attack, Defense, EquippedRangedWeapon, EquippedShield, EquippedWeapon, Health, MainMenuAction,
+    MainMenuButton, MainMenuRoot, PauseMenuAction, PauseMenuButton, PauseMenuRoot, Player,
+    PlayerDead, PlayerProgression, StatusEffects,
 };
 use crate::constants::{
     MENU_BUTTON_FONT_SIZE, MENU_BUTTON_HEIGHT, MENU_BUTTON_WIDTH, MENU_FONT_PATH,
-    MENU_OVERLAY_COLOR, MENU_TITLE_FONT_SIZE,
+    MENU_OVERLAY_COLOR, MENU_TITLE_FONT_SIZE, SAVE_SLOT_COUNT,
 };
 use crate::resources::{
-    GamePhase, GameSaveData, GameSession, LevelBuildContext, LevelState, PlayerDeathState,
+    ActionState, Encounter, EncounterStage, GamePhase, GameSaveData, GameSession, InputAction,
+    LevelBuildContext, LevelState, PendingWorldRestore, PlayerDeathState, SaveSlotPickerMode,
+    SaveSlotState, SaveStorage,
 };
+use crate::systems::controls::{OpenControlsMenuEvent, close_controls_menu};
 use crate::systems::equipment::{ShieldEquipEvent, WeaponEquipEvent};
+use crate::systems::save_slots::{
+    OpenSaveSlotPickerEvent, SaveSlotErrorEvent, SaveSlotsChangedEvent, close_save_slot_picker,
+};
+use crate::systems::world_snapshot::WorldSnapshotSource;
 use bevy::prelude::*;
 use bevy::text::{TextColor, TextFont};
 use bevy::ui::{
     AlignItems, BackgroundColor, BorderColor, FlexDirection, GlobalZIndex, Interaction,
     JustifyContent, Node, PositionType, UiRect, Val,
 };
-use serde_json;
-use std::fs;
-use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Event, Debug, Clone, Copy)]
 pub struct StartNewGameEvent;
 
 #[derive(Event, Debug, Clone, Copy)]
-pub struct RequestSaveGameEvent;
+pub struct StartEndlessRunEvent;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RequestSaveGameEvent {
+    pub slot: usize,
+}
 
 #[derive(Event, Debug, Clone, Copy)]
 pub struct RequestLoadGameEvent {
+    pub slot: usize,
     pub from_main_menu: bool,
 }
 
@@ -47,8 +58,6 @@ pub fn spawn_main_menu(
         let entity = build_main_menu(&mut commands, &asset_server);
         session.main_menu_root = Some(entity);
     }
-
-    session.set_phase(GamePhase::MainMenu);
 }
 
 pub fn handle_main_menu_interactions(
@@ -57,7 +66,9 @@ pub fn handle_main_menu_interactions(
         (Changed<Interaction>, With<Button>),
     >,
     mut start_events: EventWriter<StartNewGameEvent>,
-    mut load_events: EventWriter<RequestLoadGameEvent>,
+    mut endless_events: EventWriter<StartEndlessRunEvent>,
+    mut picker_events: EventWriter<OpenSaveSlotPickerEvent>,
+    mut controls_events: EventWriter<OpenControlsMenuEvent>,
     mut click_events: EventWriter<MenuClickEvent>,
 ) {
     for (interaction, button, mut background) in &mut interactions {
@@ -68,11 +79,19 @@ pub fn handle_main_menu_interactions(
                     MainMenuAction::NewGame => {
                         start_events.write(StartNewGameEvent);
                     }
+                    MainMenuAction::EndlessMode => {
+                        endless_events.write(StartEndlessRunEvent);
+                    }
                     MainMenuAction::LoadGame => {
-                        load_events.write(RequestLoadGameEvent {
-                            from_main_menu: true,
+                        picker_events.write(OpenSaveSlotPickerEvent {
+                            mode: SaveSlotPickerMode::Load {
+                                from_main_menu: true,
+                            },
                         });
                     }
+                    MainMenuAction::Controls => {
+                        controls_events.write(OpenControlsMenuEvent);
+                    }
                 }
             }
             Interaction::Hovered => {
@@ -86,23 +105,43 @@ pub fn handle_main_menu_interactions(
 }
 
 pub fn toggle_pause_menu_on_escape(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut session: ResMut<GameSession>,
+    phase: Res<State<GamePhase>>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
     mut resume_events: EventWriter<ResumeGameplayEvent>,
 ) {
-    if !keyboard_input.just_pressed(KeyCode::Escape) {
+    if !actions.just_pressed(InputAction::Pause) {
+        return;
+    }
+
+    // 操作設定選單正在等待新按鍵，交給綁定流程處理
+    if session
+        .controls_menu
+        .is_some_and(|menu| menu.awaiting.is_some())
+    {
         return;
     }
 
-    match session.phase() {
+    // 存檔欄位選單或操作設定選單開著時，Esc 只關掉選單並回到原本的畫面
+    if session.save_slot_picker.is_some() {
+        close_save_slot_picker(&mut commands, session.as_mut());
+        return;
+    }
+    if session.controls_menu.is_some() {
+        close_controls_menu(&mut commands, session.as_mut());
+        return;
+    }
+
+    match phase.get() {
         GamePhase::Playing => {
             let entity = session
                 .pause_menu_root
                 .unwrap_or_else(|| spawn_pause_menu(&mut commands, &asset_server));
             session.pause_menu_root = Some(entity);
-            session.set_phase(GamePhase::Paused);
+            next_phase.set(GamePhase::Paused);
         }
         GamePhase::Paused => {
             close_pause_menu(&mut commands, session.as_mut());
@@ -120,8 +159,8 @@ pub fn handle_pause_menu_interactions(
         (Changed<Interaction>, With<Button>),
     >,
     mut resume_events: EventWriter<ResumeGameplayEvent>,
-    mut save_events: EventWriter<RequestSaveGameEvent>,
-    mut load_events: EventWriter<RequestLoadGameEvent>,
+    mut picker_events: EventWriter<OpenSaveSlotPickerEvent>,
+    mut controls_events: EventWriter<OpenControlsMenuEvent>,
     mut click_events: EventWriter<MenuClickEvent>,
 ) {
     for (interaction, button, mut background) in &mut interactions {
@@ -134,13 +173,20 @@ pub fn handle_pause_menu_interactions(
                         resume_events.write(ResumeGameplayEvent);
                     }
                     PauseMenuAction::Save => {
-                        save_events.write(RequestSaveGameEvent);
+                        picker_events.write(OpenSaveSlotPickerEvent {
+                            mode: SaveSlotPickerMode::Save,
+                        });
                     }
                     PauseMenuAction::Load => {
-                        load_events.write(RequestLoadGameEvent {
-                            from_main_menu: false,
+                        picker_events.write(OpenSaveSlotPickerEvent {
+                            mode: SaveSlotPickerMode::Load {
+                                from_main_menu: false,
+                            },
                         });
                     }
+                    PauseMenuAction::Controls => {
+                        controls_events.write(OpenControlsMenuEvent);
+                    }
                 }
             }
             Interaction::Hovered => {
@@ -155,38 +201,56 @@ pub fn handle_pause_menu_interactions(
 
 pub fn process_save_game_requests(
     mut events: EventReader<RequestSaveGameEvent>,
+    mut changed_events: EventWriter<SaveSlotsChangedEvent>,
     session: Res<GameSession>,
+    phase: Res<State<GamePhase>>,
+    storage: Res<SaveStorage>,
     level_state: Option<Res<LevelState>>,
+    encounter: Option<Res<Encounter>>,
+    world: WorldSnapshotSource,
     player_query: Query<
         (
             &Health,
             &PlayerProgression,
             Option<&EquippedWeapon>,
             Option<&EquippedShield>,
+            Option<&EquippedRangedWeapon>,
         ),
         With<Player>,
     >,
 ) {
-    let mut requested = false;
-    for _ in events.read() {
-        requested = true;
+    let mut requested_slot = None;
+    for event in events.read() {
+        requested_slot = Some(event.slot);
     }
 
-    if !requested {
+    let Some(slot) = requested_slot else {
+        return;
+    };
+
+    if slot >= SAVE_SLOT_COUNT {
+        warn!("Ignoring save request for unknown slot {}", slot + 1);
         return;
     }
 
-    if matches!(session.phase(), GamePhase::MainMenu) {
+    if matches!(phase.get(), GamePhase::MainMenu) {
         warn!("Cannot save while in the main menu");
         return;
     }
 
+    // 存檔不記錄進行到一半的遭遇戰，等門解鎖後再存
+    if encounter.is_some_and(|encounter| encounter.stage() == EncounterStage::Active) {
+        warn!("Cannot save while the doors are sealed by an encounter");
+        return;
+    }
+
     let Some(level_state) = level_state else {
         warn!("Missing level state resource; cannot save progress");
         return;
     };
 
-    let Some((health, progression, weapon, shield)) = player_query.iter().next() else {
+    let Some((health, progression, weapon, shield, ranged_weapon)) = player_query.iter().next()
+    else {
         warn!("Player data not found; cannot save progress");
         return;
     };
@@ -198,23 +262,29 @@ pub fn process_save_game_requests(
     data.player_level = progression.level;
     data.player_experience = progression.experience;
     data.equipped_weapon = weapon.map(|w| w.kind);
+    data.equipped_weapon_family = weapon.map(|w| w.family);
     data.equipped_shield = shield.map(|s| s.kind);
-
-    if let Err(error) = fs::create_dir_all(GameSession::SAVE_DIRECTORY) {
-        error!("Failed to create save directory: {error}");
-        return;
-    }
-
-    match serde_json::to_string_pretty(&data) {
-        Ok(serialized) => {
-            if let Err(error) = fs::write(GameSession::SAVE_SLOT_FILE, serialized) {
-                error!("Failed to write save file: {error}");
-            } else {
-                dev_info!("Game progress saved to {}", GameSession::SAVE_SLOT_FILE);
-            }
+    data.equipped_ranged_weapon = ranged_weapon.map(|r| r.kind);
+    data.ranged_ammo = ranged_weapon.map_or(0, |r| r.ammo);
+    data.endless_run_seed = level_state.endless_run_seed();
+    data.saved_at_unix_seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    data.level_name = level_state
+        .get_definition(data.level_index)
+        .map(|definition| definition.name.clone())
+        .unwrap_or_default();
+    data.playtime_seconds = session.playtime_seconds();
+    data.world = world.capture(data.level_index);
+
+    match storage.write_slot(slot, &data) {
+        Ok(save_path) => {
+            dev_info!("Game progress saved to {}", save_path.display());
+            changed_events.write(SaveSlotsChangedEvent);
         }
         Err(error) => {
-            error!("Failed to serialize save data: {error}");
+            error!("Failed to save slot {}: {error}", slot + 1);
         }
     }
 }
@@ -223,9 +293,12 @@ pub fn process_load_game_requests(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut session: ResMut<GameSession>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    storage: Res<SaveStorage>,
     mut events: EventReader<RequestLoadGameEvent>,
     mut level_state: ResMut<LevelState>,
     mut build_context: ResMut<LevelBuildContext>,
+    mut pending_restore: ResMut<PendingWorldRestore>,
     death_state: Option<ResMut<PlayerDeathState>>,
     mut player_query: Query<
         (
@@ -241,71 +314,50 @@ pub fn process_load_game_requests(
     >,
     mut weapon_events: EventWriter<WeaponEquipEvent>,
     mut shield_events: EventWriter<ShieldEquipEvent>,
+    mut error_events: EventWriter<SaveSlotErrorEvent>,
 ) {
-    let mut requested = false;
-    let mut from_main_menu = false;
+    let mut request = None;
     for event in events.read() {
-        requested = true;
-        if event.from_main_menu {
-            from_main_menu = true;
-        }
-    }
-
-    if !requested {
-        return;
+        request = Some(*event);
     }
 
-    let save_path = Path::new(GameSession::SAVE_SLOT_FILE);
-    if !save_path.exists() {
-        warn!("No existing save found; cannot load progress");
-        if from_main_menu {
-            if session.main_menu_root.is_none() {
-                let entity = build_main_menu(&mut commands, &asset_server);
-                session.main_menu_root = Some(entity);
-            }
-            session.set_phase(GamePhase::MainMenu);
-        }
+    let Some(RequestLoadGameEvent {
+        slot,
+        from_main_menu,
+    }) = request
+    else {
         return;
-    }
+    };
 
-    let raw = match fs::read_to_string(save_path) {
-        Ok(contents) => contents,
-        Err(error) => {
-            error!("Failed to read save file: {error}");
+    let data = match storage.load_slot(slot) {
+        SaveSlotState::Occupied(data) => *data,
+        SaveSlotState::Empty => {
+            warn!("Save slot {} is empty; cannot load progress", slot + 1);
             if from_main_menu {
                 if session.main_menu_root.is_none() {
                     let entity = build_main_menu(&mut commands, &asset_server);
                     session.main_menu_root = Some(entity);
                 }
-                session.set_phase(GamePhase::MainMenu);
+                next_phase.set(GamePhase::MainMenu);
             }
             return;
         }
-    };
-
-    let data: GameSaveData = match serde_json::from_str(&raw) {
-        Ok(data) => data,
-        Err(error) => {
-            error!("Failed to parse save data: {error}");
+        SaveSlotState::Unreadable(error) => {
+            error!("Failed to load save slot {}: {error}", slot + 1);
+            error_events.write(SaveSlotErrorEvent {
+                message: format!("Slot {}: {error}", slot + 1),
+            });
             if from_main_menu {
                 if session.main_menu_root.is_none() {
                     let entity = build_main_menu(&mut commands, &asset_server);
                     session.main_menu_root = Some(entity);
                 }
-                session.set_phase(GamePhase::MainMenu);
+                next_phase.set(GamePhase::MainMenu);
             }
             return;
         }
     };
 
-    if data.version != GameSaveData::CURRENT_VERSION {
-        warn!(
-            "Save version ({}) differs from current ({}) — attempting to load anyway",
-            data.version,
-            GameSaveData::CURRENT_VERSION
-        );
-    }
-
     let Ok((entity, mut health, mut attack, mut defense, mut progression, mut sprite, shield)) =
         player_query.single_mut()
     else {
@@ -313,11 +365,15 @@ pub fn process_load_game_requests(
         return;
     };
 
-    let level_count = level_state.definition_count().max(1);
-    let target_index = data.level_index.min(level_count.saturating_sub(1));
-    level_state.set_current_index(target_index);
+    match data.endless_run_seed {
+        Some(run_seed) => level_state.start_endless(run_seed),
+        None => level_state.start_campaign(),
+    }
+    level_state.set_current_index(data.level_index);
+    let target_index = level_state.current_index();
     build_context.pending_layout = Some(target_index);
     build_context.pending_finalize = None;
+    pending_restore.0 = data.world.clone();
 
     let clamped_level = data.player_level.min(PlayerProgression::max_level());
     progression.level = clamped_level;
@@ -337,8 +393,8 @@ pub fn process_load_game_requests(
     health.max = data.player_max_health.max(1);
     health.current = data.player_health.clamp(0, health.max);
 
-    if let Some(kind) = data.equipped_weapon {
-        weapon_events.write(WeaponEquipEvent { kind });
+    if let (Some(kind), Some(family)) = (data.equipped_weapon, data.equipped_weapon_family) {
+        weapon_events.write(WeaponEquipEvent { kind, family });
     }
 
     match data.equipped_shield {
@@ -353,8 +409,19 @@ pub fn process_load_game_requests(
         }
     }
 
+    match data.equipped_ranged_weapon {
+        Some(kind) => {
+            commands
+                .entity(entity)
+                .insert(EquippedRangedWeapon::with_ammo(kind, data.ranged_ammo));
+        }
+        None => {
+            commands.entity(entity).remove::<EquippedRangedWeapon>();
+        }
+    }
+
     commands.entity(entity).remove::<PlayerDead>();
-    commands.entity(entity).remove::<Poisoned>();
+    commands.entity(entity).insert(StatusEffects::default());
 
     if let Some(mut death_state) = death_state {
         if let Some(screen_entity) = death_state.screen_entity.take() {
@@ -364,9 +431,11 @@ pub fn process_load_game_requests(
         death_state.clear_timer();
     }
 
+    close_save_slot_picker(&mut commands, session.as_mut());
     close_main_menu(&mut commands, session.as_mut());
     close_pause_menu(&mut commands, session.as_mut());
-    session.set_phase(GamePhase::Playing);
+    session.set_playtime_seconds(data.playtime_seconds);
+    next_phase.set(GamePhase::Playing);
 
     dev_info!(
         "Loaded save: level {}, HP {}/{}, Lv {}, weapon {:?}, shield {:?}",
@@ -380,7 +449,7 @@ pub fn process_load_game_requests(
 }
 
 pub fn resume_gameplay(
-    mut session: ResMut<GameSession>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
     mut resume_events: EventReader<ResumeGameplayEvent>,
 ) {
     let mut requested = false;
@@ -389,13 +458,14 @@ pub fn resume_gameplay(
     }
 
     if requested {
-        session.set_phase(GamePhase::Playing);
+        next_phase.set(GamePhase::Playing);
     }
 }
 
 pub fn activate_gameplay_after_start(
     mut commands: Commands,
     mut session: ResMut<GameSession>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
     mut events: EventReader<StartNewGameEvent>,
 ) {
     let mut triggered = false;
@@ -404,11 +474,56 @@ pub fn activate_gameplay_after_start(
     }
 
     if triggered {
-        session.set_phase(GamePhase::Playing);
+        next_phase.set(GamePhase::Playing);
+        session.set_playtime_seconds(0.0);
+        close_save_slot_picker(&mut commands, session.as_mut());
         close_main_menu(&mut commands, session.as_mut());
     }
 }
 
+/// 離開遊戲畫面時暫停虛擬時間，讓所有以 `Time` 計時的計時器停住
+pub fn pause_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+pub fn resume_virtual_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+pub fn track_playtime(time: Res<Time>, mut session: ResMut<GameSession>) {
+    session.add_playtime(time.delta_secs_f64());
+}
+
+pub fn start_endless_run(
+    mut commands: Commands,
+    mut session: ResMut<GameSession>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    mut events: EventReader<StartEndlessRunEvent>,
+    mut level_state: ResMut<LevelState>,
+    mut build_context: ResMut<LevelBuildContext>,
+) {
+    let mut triggered = false;
+    for _ in events.read() {
+        triggered = true;
+    }
+
+    if !triggered {
+        return;
+    }
+
+    let run_seed = rand::random::<u64>();
+    level_state.start_endless(run_seed);
+    build_context.pending_layout = Some(0);
+    build_context.pending_finalize = None;
+
+    next_phase.set(GamePhase::Playing);
+    session.set_playtime_seconds(0.0);
+    close_save_slot_picker(&mut commands, session.as_mut());
+    close_main_menu(&mut commands, session.as_mut());
+
+    dev_info!("♾️ Starting endless run (seed {})", run_seed);
+}
+
 fn build_main_menu(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
     let font = asset_server.load(MENU_FONT_PATH);
 
@@ -489,6 +604,37 @@ fn build_main_menu(commands: &mut Commands, asset_server: &AssetServer) -> Entit
                             ));
                         });
 
+                    panel
+                        .spawn((
+                            Button,
+                            MainMenuButton {
+                                action: MainMenuAction::EndlessMode,
+                            },
+                            Node {
+                                width: Val::Px(MENU_BUTTON_WIDTH),
+                                height: Val::Px(MENU_BUTTON_HEIGHT),
+                                padding: UiRect::all(Val::Px(12.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..Default::default()
+                            },
+                            BorderColor(Color::srgba(0.65, 0.6, 0.5, 0.6)),
+                            BackgroundColor(Color::srgba(0.22, 0.18, 0.15, 0.85)),
+                            Name::new("MainMenuButtonEndless"),
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                Text::new("Endless Mode"),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: MENU_BUTTON_FONT_SIZE,
+                                    ..Default::default()
+                                },
+                                TextColor(Color::srgb(0.95, 0.93, 0.9)),
+                            ));
+                        });
+
                     panel
                         .spawn((
                             Button,
@@ -519,6 +665,37 @@ fn build_main_menu(commands: &mut Commands, asset_server: &AssetServer) -> Entit
                                 TextColor(Color::srgb(0.95, 0.93, 0.9)),
                             ));
                         });
+
+                    panel
+                        .spawn((
+                            Button,
+                            MainMenuButton {
+                                action: MainMenuAction::Controls,
+                            },
+                            Node {
+                                width: Val::Px(MENU_BUTTON_WIDTH),
+                                height: Val::Px(MENU_BUTTON_HEIGHT),
+                                padding: UiRect::all(Val::Px(12.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                border: UiRect::all(Val::Px(2.0)),
+                                ..Default::default()
+                            },
+                            BorderColor(Color::srgba(0.65, 0.6, 0.5, 0.6)),
+                            BackgroundColor(Color::srgba(0.22, 0.18, 0.15, 0.85)),
+                            Name::new("MainMenuButtonControls"),
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                Text::new("Controls"),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: MENU_BUTTON_FONT_SIZE,
+                                    ..Default::default()
+                                },
+                                TextColor(Color::srgb(0.95, 0.93, 0.9)),
+                            ));
+                        });
                 });
         })
         .id()
@@ -577,6 +754,7 @@ fn spawn_pause_menu(commands: &mut Commands, asset_server: &AssetServer) -> Enti
                         (PauseMenuAction::Resume, "Resume"),
                         (PauseMenuAction::Save, "Save Game"),
                         (PauseMenuAction::Load, "Load Game"),
+                        (PauseMenuAction::Controls, "Controls"),
                     ];
 
                     for (action, label) in actions {
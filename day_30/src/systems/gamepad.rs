@@ -0,0 +1,125 @@
+use crate::constants::GAMEPAD_MENU_CONFIRM_BUTTON;
+use crate::resources::{GameSession, GamepadMenuFocus};
+use bevy::prelude::*;
+
+/// 任一支手把在這個影格按下指定按鈕
+pub fn any_gamepad_just_pressed(gamepads: &Query<&Gamepad>, button: GamepadButton) -> bool {
+    gamepads.iter().any(|gamepad| gamepad.just_pressed(button))
+}
+
+/// 左搖桿的移動輸入，多支手把時取推得最大的那一支（十字鍵走按鍵綁定）
+pub fn gamepad_movement(gamepads: &Query<&Gamepad>) -> Vec2 {
+    strongest(gamepads.iter().map(|gamepad| gamepad.left_stick()))
+}
+
+/// 右搖桿的瞄準方向
+pub fn gamepad_aim(gamepads: &Query<&Gamepad>) -> Vec2 {
+    strongest(gamepads.iter().map(|gamepad| gamepad.right_stick()))
+}
+
+fn strongest(inputs: impl Iterator<Item = Vec2>) -> Vec2 {
+    inputs
+        .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+        .unwrap_or(Vec2::ZERO)
+        .clamp_length_max(1.0)
+}
+
+/// 用十字鍵在主選單、暫停選單、存檔欄位選單與操作設定選單之間移動焦點，確認鍵等同點擊
+pub fn navigate_menus_with_gamepad(
+    session: Res<GameSession>,
+    gamepads: Query<&Gamepad>,
+    mut focus: ResMut<GamepadMenuFocus>,
+    children: Query<&Children>,
+    mut buttons: Query<&mut Interaction, With<Button>>,
+) {
+    // 等待新按鍵時，十字鍵與確認鍵都交給綁定流程
+    if session
+        .controls_menu
+        .is_some_and(|menu| menu.awaiting.is_some())
+    {
+        return;
+    }
+
+    // 手把的點擊只維持一個影格，讓按鈕回到一般狀態
+    if let Some(mut interaction) = focus
+        .pressed
+        .take()
+        .and_then(|entity| buttons.get_mut(entity).ok())
+    {
+        *interaction = Interaction::Hovered;
+    }
+
+    let root = session
+        .save_slot_picker
+        .map(|picker| picker.root)
+        .or(session.controls_menu.map(|menu| menu.root))
+        .or(session.pause_menu_root)
+        .or(session.main_menu_root);
+
+    let Some(root) = root else {
+        focus.root = None;
+        focus.focused = None;
+        return;
+    };
+
+    let mut ordered = Vec::new();
+    collect_menu_buttons(root, &children, &buttons, &mut ordered);
+    if ordered.is_empty() {
+        return;
+    }
+
+    let mut index = focus.index.min(ordered.len() - 1);
+    if focus.root != Some(root) {
+        focus.root = Some(root);
+        index = 0;
+    }
+
+    if any_gamepad_just_pressed(&gamepads, GamepadButton::DPadDown) {
+        index = (index + 1) % ordered.len();
+    }
+    if any_gamepad_just_pressed(&gamepads, GamepadButton::DPadUp) {
+        index = (index + ordered.len() - 1) % ordered.len();
+    }
+
+    focus.index = index;
+    let target = ordered[index];
+    if focus.focused != Some(target) {
+        if let Some(mut interaction) = focus
+            .focused
+            .replace(target)
+            .and_then(|previous| buttons.get_mut(previous).ok())
+        {
+            *interaction = Interaction::None;
+        }
+        if let Ok(mut interaction) = buttons.get_mut(target) {
+            *interaction = Interaction::Hovered;
+        }
+    }
+
+    if !any_gamepad_just_pressed(&gamepads, GAMEPAD_MENU_CONFIRM_BUTTON) {
+        return;
+    }
+
+    if let Ok(mut interaction) = buttons.get_mut(target) {
+        *interaction = Interaction::Pressed;
+        focus.pressed = Some(target);
+    }
+}
+
+/// 依子節點順序（即畫面由上到下）收集選單內的按鈕
+fn collect_menu_buttons(
+    entity: Entity,
+    children: &Query<&Children>,
+    buttons: &Query<&mut Interaction, With<Button>>,
+    ordered: &mut Vec<Entity>,
+) {
+    if buttons.contains(entity) {
+        ordered.push(entity);
+    }
+
+    if let Ok(list) = children.get(entity) {
+        for child in list.iter() {
+            collect_menu_buttons(child, children, buttons, ordered);
+        }
+    }
+}
@@ -9,32 +9,17 @@ pub struct PlayerDamagedEvent {
     pub remaining_health: i32,
 }
 
+#[derive(Event, Clone, Copy)]
+pub struct PlayerHealedEvent {
+    pub amount: i32,
+}
+
 #[derive(Event, Clone, Copy)]
 pub struct PlayerDiedEvent;
 
 #[derive(Event, Clone, Copy)]
 pub struct PlayerRespawnedEvent;
 
-pub fn health_system(
-    mut commands: Commands,
-    mut query: Query<(Entity, &Health, Option<&PlayerDead>), With<Player>>,
-    mut death_events: EventWriter<PlayerDiedEvent>,
-) {
-    for (entity, health, dead_marker) in &mut query {
-        if health.current > 0 {
-            continue;
-        }
-
-        if dead_marker.is_some() {
-            continue;
-        }
-
-        commands.entity(entity).insert(PlayerDead);
-        death_events.write(PlayerDiedEvent);
-        dev_info!("Player died!");
-    }
-}
-
 pub fn start_player_death_sequence_system(
     mut commands: Commands,
     mut death_events: EventReader<PlayerDiedEvent>,
@@ -120,6 +105,7 @@ pub fn player_respawn_system(
         .unwrap_or_else(|| Vec3::new(0.0, -ROOM_TILE_SIZE * PLAYER_SCALE * 3.0, 10.0));
 
     transform.translation = spawn_position;
+    transform.rotation = Quat::IDENTITY;
 
     if let Some(mut vel) = velocity {
         vel.x = 0.0;
@@ -139,7 +125,10 @@ pub fn player_respawn_system(
     sprite.color = Color::WHITE;
 
     commands.entity(entity).remove::<PlayerDead>();
-    commands.entity(entity).remove::<Poisoned>();
+    commands.entity(entity).insert(StatusEffects::default());
+    commands
+        .entity(entity)
+        .remove::<(Knockback, HitStun, DodgeRoll, Invulnerable)>();
 
     death_state.clear_timer();
 
@@ -2,16 +2,17 @@ use crate::components::level::LevelExitDoor;
 use crate::components::world::{Door, RoomTile};
 use crate::components::{Chest, Player, PlayerDead, PlayerFacing};
 use crate::constants::*;
-use crate::resources::GameSession;
+use crate::resources::{ActionState, InputAction};
 use crate::systems::attack::AttackInputEvent;
 use crate::systems::chest::ChestInteractionEvent;
+use crate::systems::dodge::DodgeInputEvent;
 use crate::systems::door_interaction::DoorInteractionEvent;
 use crate::systems::level::LevelAdvanceRequestEvent;
+use crate::systems::ranged_attack::RangedAttackInputEvent;
 use bevy::prelude::*;
 
 pub fn input_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    session: Res<GameSession>,
+    actions: Res<ActionState>,
     player_query: Query<(&Transform, &PlayerFacing), (With<Player>, Without<PlayerDead>)>,
     chest_query: Query<(Entity, &Transform, &Chest), Without<Player>>,
     door_query: Query<(&Door, &Transform), (With<RoomTile>, Without<Player>)>,
@@ -19,13 +20,24 @@ pub fn input_system(
     mut door_events: EventWriter<DoorInteractionEvent>,
     mut chest_events: EventWriter<ChestInteractionEvent>,
     mut attack_events: EventWriter<AttackInputEvent>,
+    mut dodge_events: EventWriter<DodgeInputEvent>,
+    mut fire_events: EventWriter<RangedAttackInputEvent>,
     mut level_exit_events: EventWriter<LevelAdvanceRequestEvent>,
 ) {
-    if !session.is_playing() {
-        return;
+    // 攻擊與互動分開，站在門邊也能揮武器
+    if actions.just_pressed(InputAction::Attack) {
+        attack_events.write(AttackInputEvent);
     }
 
-    if keyboard_input.just_pressed(KeyCode::Space) {
+    if actions.just_pressed(InputAction::Fire) {
+        fire_events.write(RangedAttackInputEvent);
+    }
+
+    if actions.just_pressed(InputAction::Dodge) {
+        dodge_events.write(DodgeInputEvent);
+    }
+
+    if actions.just_pressed(InputAction::Interact) {
         let Ok((player_transform, facing)) = player_query.single() else {
             return;
         };
@@ -106,8 +118,6 @@ pub fn input_system(
         if near_door {
             door_events.write(DoorInteractionEvent);
             dev_info!("Door interaction event dispatched");
-        } else {
-            attack_events.write(AttackInputEvent);
         }
     }
 }
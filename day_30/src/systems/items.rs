@@ -1,6 +1,7 @@
 use crate::components::*;
 use crate::constants::*;
-use crate::systems::equipment::{ShieldEquipEvent, WeaponEquipEvent};
+use crate::systems::equipment::{RangedWeaponEquipEvent, ShieldEquipEvent, WeaponEquipEvent};
+use crate::systems::health::PlayerHealedEvent;
 use bevy::prelude::*;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -54,17 +55,18 @@ pub fn player_pickup_detection_system(
             &Transform,
             &mut Health,
             Option<Mut<Stamina>>,
-            Option<&Poisoned>,
-            Entity,
+            Option<Mut<StatusEffects>>,
         ),
         (With<Player>, Without<PlayerDead>),
     >,
     pickup_query: Query<(Entity, &Transform, &Pickup)>,
     mut shield_events: EventWriter<ShieldEquipEvent>,
     mut weapon_events: EventWriter<WeaponEquipEvent>,
+    mut ranged_weapon_events: EventWriter<RangedWeaponEquipEvent>,
     mut pickup_events: EventWriter<PlayerPickupEvent>,
+    mut healed_events: EventWriter<PlayerHealedEvent>,
 ) {
-    let Some((player_transform, mut health, mut stamina, poison_state, player_entity)) =
+    let Some((player_transform, mut health, mut stamina, mut status_effects)) =
         player_query.iter_mut().next()
     else {
         return;
@@ -82,6 +84,11 @@ pub fn player_pickup_detection_system(
             PickupEffect::Heal(amount) => {
                 let before = health.current;
                 health.current = (health.current + amount).min(health.max);
+                if health.current > before {
+                    healed_events.write(PlayerHealedEvent {
+                        amount: health.current - before,
+                    });
+                }
                 dev_info!("Picked up red potion: HP {} -> {}", before, health.current);
             }
             PickupEffect::RestoreStamina(amount) => {
@@ -98,8 +105,10 @@ pub fn player_pickup_detection_system(
                 }
             }
             PickupEffect::CurePoison => {
-                if poison_state.is_some() {
-                    commands.entity(player_entity).remove::<Poisoned>();
+                if status_effects
+                    .as_mut()
+                    .is_some_and(|effects| effects.remove(StatusEffectKind::Poison))
+                {
                     dev_info!("Picked up antidote: poison cleansed");
                 } else {
                     dev_info!("Picked up antidote: player is not poisoned");
@@ -109,9 +118,16 @@ pub fn player_pickup_detection_system(
                 dev_info!("Picked up shield: equipping {:?}", kind);
                 shield_events.write(ShieldEquipEvent { kind: *kind });
             }
-            PickupEffect::EquipWeapon(kind) => {
-                dev_info!("Picked up weapon: equipping {:?}", kind);
-                weapon_events.write(WeaponEquipEvent { kind: *kind });
+            PickupEffect::EquipWeapon { kind, family } => {
+                dev_info!("Picked up weapon: equipping {:?} {:?}", family, kind);
+                weapon_events.write(WeaponEquipEvent {
+                    kind: *kind,
+                    family: *family,
+                });
+            }
+            PickupEffect::EquipRangedWeapon(kind) => {
+                dev_info!("Picked up ranged weapon: equipping {:?}", kind);
+                ranged_weapon_events.write(RangedWeaponEquipEvent { kind: *kind });
             }
         }
 
@@ -157,12 +173,19 @@ pub fn spawn_pickup_entity(
     ));
 }
 
-pub fn pickup_visual_for_effect(effect: &PickupEffect) -> (&'static str, &'static str) {
+pub fn pickup_visual_for_effect(effect: &PickupEffect) -> (&'static str, String) {
     match effect {
-        PickupEffect::Heal(_) => ("items/potions/health.png", "PickupHealth"),
-        PickupEffect::RestoreStamina(_) => ("items/potions/stamina.png", "PickupStamina"),
-        PickupEffect::CurePoison => ("items/potions/toxic.png", "PickupAntidote"),
-        PickupEffect::EquipShield(kind) => (kind.sprite_path(), kind.display_name()),
-        PickupEffect::EquipWeapon(kind) => (kind.right_sprite_path(), kind.display_name()),
+        PickupEffect::Heal(_) => ("items/potions/health.png", "PickupHealth".to_string()),
+        PickupEffect::RestoreStamina(_) => {
+            ("items/potions/stamina.png", "PickupStamina".to_string())
+        }
+        PickupEffect::CurePoison => ("items/potions/toxic.png", "PickupAntidote".to_string()),
+        PickupEffect::EquipShield(kind) => (kind.sprite_path(), kind.display_name().to_string()),
+        PickupEffect::EquipWeapon { kind, family } => {
+            (kind.right_sprite_path(), kind.display_name(*family))
+        }
+        PickupEffect::EquipRangedWeapon(kind) => {
+            (kind.sprite_path(), kind.display_name().to_string())
+        }
     }
 }
@@ -0,0 +1,87 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::TileMap;
+use crate::systems::damage::DamageAppliedEvent;
+use bevy::prelude::*;
+
+/// 命中後依傷害與目標重量給予擊退，並讓目標進入短暫硬直
+pub fn apply_knockback_on_hit_system(
+    mut commands: Commands,
+    mut events: EventReader<DamageAppliedEvent>,
+    target_query: Query<(Option<&KnockbackWeight>, Has<Player>, Has<PlayerDead>)>,
+) {
+    for event in events.read() {
+        if event.knockback == Vec2::ZERO {
+            continue;
+        }
+
+        let Ok((weight, is_player, is_dead)) = target_query.get(event.target) else {
+            continue;
+        };
+
+        if is_dead {
+            continue;
+        }
+
+        let weight = weight.map(|weight| weight.0).unwrap_or(1.0).max(0.1);
+        let scale = (event.damage as f32 / KNOCKBACK_DAMAGE_REFERENCE)
+            .clamp(KNOCKBACK_MIN_SCALE, KNOCKBACK_MAX_SCALE);
+        let velocity = event.knockback * scale / weight;
+
+        let stun_seconds = if is_player {
+            PLAYER_HIT_STUN_SECONDS
+        } else {
+            ENEMY_HIT_STUN_SECONDS
+        }
+        .max(event.stun_seconds);
+
+        commands.entity(event.target).insert((
+            Knockback::new(velocity, KNOCKBACK_SECONDS),
+            HitStun::new(stun_seconds),
+        ));
+    }
+}
+
+/// 推動被擊退的實體，速度逐漸衰減；有碰撞箱的實體沿著牆滑動，其餘交給牆壁碰撞系統修正位置
+pub fn knockback_movement_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    tile_map: Res<TileMap>,
+    mut query: Query<
+        (Entity, &mut Transform, &mut Knockback, Option<&BoxCollider>),
+        Without<PlayerDead>,
+    >,
+) {
+    let delta_secs = time.delta_secs();
+
+    for (entity, mut transform, mut knockback, collider) in &mut query {
+        let displacement = knockback.velocity * delta_secs;
+        let position = match collider {
+            Some(collider) => tile_map.move_and_slide(
+                transform.translation.truncate(),
+                displacement,
+                collider.half_size,
+            ),
+            None => transform.translation.truncate() + displacement,
+        };
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+        knockback.velocity *= (-KNOCKBACK_DAMPING * delta_secs).exp();
+
+        if knockback.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
+pub fn tick_hit_stun_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut HitStun)>,
+) {
+    for (entity, mut stun) in &mut query {
+        if stun.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<HitStun>();
+        }
+    }
+}
@@ -1,19 +1,21 @@
 use std::collections::HashSet;
 
 use crate::components::{
-    Attack, BossWizard, BossWizardStaff, Chest, ChestContents, Cyclops, CyclopsCharge, Defense,
-    Enemy, EnemyAIState, EnemyAlert, EnemyAttack, EnemyBehaviorState, EnemyPatrol, EnemySpeeds,
-    Health, PickupEffect, ShieldKind, Slime, Spider, WeaponKind,
-    level::{LevelEntity, LevelExitDoor},
+    Attack, Chest, ChestContents, Defense, EnemyPatrolRoute, Health, PickupEffect,
+    level::{DataErrorScreen, LevelEntity, LevelExitDoor},
     player::{InputVector, Player, PlayerDead, Velocity},
     world::{CorridorTile, Door, EnvironmentProp, RoomTile, RoomTileType},
 };
 use crate::constants::*;
 use crate::resources::{
-    EntranceLocation, EnvironmentAssets, LevelBuildContext, LevelDefinition, LevelExitAssets,
-    LevelState, PendingLevelRewards, RoomAssets,
+    Encounter, EnemyArchetype, EnemyArchetypeRegistry, EnemyBehaviorKind, EntranceLocation,
+    EnvironmentAssets, LevelBuildContext, LevelCampaign, LevelCampaignHandle, LevelDefinition,
+    LevelExitAssets, LevelState, PendingLevelRewards, RoomAssets, TileMap,
 };
-use crate::systems::EnemyDefeatedEvent;
+use crate::systems::{
+    EnemyDefeatedEvent, EnemyPlacement, EnemySpawnStats, spawn_enemy_from_archetype,
+};
+use bevy::asset::AssetLoadFailedEvent;
 use bevy::prelude::*;
 use bevy::text::{TextColor, TextFont};
 use bevy::ui::{Node, PositionType, Val};
@@ -24,10 +26,10 @@ pub struct LevelAdvanceRequestEvent {
     pub target_level: usize,
 }
 
-#[derive(Event, Debug, Clone, Copy)]
+#[derive(Event, Debug, Clone)]
 pub struct LevelLoadedEvent {
     pub index: usize,
-    pub name: &'static str,
+    pub name: String,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -47,6 +49,143 @@ impl PlayerCombatSnapshot {
     }
 }
 
+pub fn load_level_campaign(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let handle = asset_server.load(LEVEL_CAMPAIGN_PATH);
+    commands.insert_resource(LevelCampaignHandle(handle));
+}
+
+/// 關卡資料載入（或熱重載）完成時，更新 `LevelState` 的關卡清單
+pub fn apply_loaded_level_campaign(
+    mut asset_events: EventReader<AssetEvent<LevelCampaign>>,
+    mut failed_events: EventReader<AssetLoadFailedEvent<LevelCampaign>>,
+    campaign_handle: Option<Res<LevelCampaignHandle>>,
+    campaigns: Res<Assets<LevelCampaign>>,
+    mut level_state: ResMut<LevelState>,
+) {
+    for event in failed_events.read() {
+        error!(
+            "Failed to load level data from {}: {}",
+            event.path, event.error
+        );
+        level_state.set_load_error(Some(format!("{}: {}", event.path, event.error)));
+    }
+
+    let Some(campaign_handle) = campaign_handle else {
+        asset_events.clear();
+        return;
+    };
+
+    let mut updated = false;
+    for event in asset_events.read() {
+        match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }
+                if *id == campaign_handle.0.id() =>
+            {
+                updated = true;
+            }
+            _ => {}
+        }
+    }
+
+    if !updated {
+        return;
+    }
+
+    let Some(campaign) = campaigns.get(&campaign_handle.0) else {
+        return;
+    };
+
+    level_state.replace_definitions(campaign.definitions.clone());
+    level_state.set_load_error(None);
+    dev_info!(
+        "📜 Loaded {} level definitions from {}",
+        level_state.definition_count(),
+        LEVEL_CAMPAIGN_PATH
+    );
+}
+
+/// 關卡或敵人資料讀取失敗、無法生成關卡時，顯示失敗原因而不是停在空白畫面；修正檔案觸發熱重載後自動移除
+pub fn update_data_error_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level_state: Res<LevelState>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    screens: Query<(Entity, &DataErrorScreen)>,
+) {
+    let message = data_error_message(&level_state, &archetypes);
+    if screens
+        .iter()
+        .any(|(_, screen)| Some(&screen.message) == message.as_ref())
+    {
+        return;
+    }
+
+    for (entity, _) in &screens {
+        commands.entity(entity).despawn();
+    }
+
+    let Some(message) = message else {
+        return;
+    };
+
+    let font = asset_server.load(MENU_FONT_PATH);
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                padding: UiRect::all(Val::Px(48.0)),
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(
+                MENU_OVERLAY_COLOR[0],
+                MENU_OVERLAY_COLOR[1],
+                MENU_OVERLAY_COLOR[2],
+                MENU_OVERLAY_COLOR[3],
+            )),
+            GlobalZIndex(200),
+            Name::new("DataErrorScreen"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(message.clone()),
+                TextFont {
+                    font,
+                    font_size: MENU_BUTTON_FONT_SIZE * 0.8,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.95, 0.45, 0.4)),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        })
+        .insert(DataErrorScreen { message });
+}
+
+fn data_error_message(
+    level_state: &LevelState,
+    archetypes: &EnemyArchetypeRegistry,
+) -> Option<String> {
+    let mut errors = Vec::new();
+    if !level_state.is_loaded() {
+        errors.extend(
+            level_state
+                .load_error()
+                .map(|error| format!("Failed to load level data\n{error}")),
+        );
+    }
+    if !archetypes.is_loaded() {
+        errors.extend(
+            archetypes
+                .load_error()
+                .map(|error| format!("Failed to load enemy data\n{error}")),
+        );
+    }
+    (!errors.is_empty()).then(|| errors.join("\n\n"))
+}
+
 pub fn schedule_initial_level(mut build_context: ResMut<LevelBuildContext>) {
     if build_context.pending_layout.is_none() && build_context.pending_finalize.is_none() {
         build_context.pending_layout = Some(0);
@@ -88,14 +227,22 @@ pub fn process_level_layout(
     mut commands: Commands,
     mut build_context: ResMut<LevelBuildContext>,
     level_state: Res<LevelState>,
+    archetypes: Res<EnemyArchetypeRegistry>,
     level_entities: Query<Entity, With<LevelEntity>>,
     room_assets: Res<RoomAssets>,
 ) {
+    // 關卡與敵人資料還在載入中時先保留請求，等資料就緒再生成
+    if !level_state.is_loaded() || !archetypes.is_loaded() {
+        return;
+    }
+
     let Some(index) = build_context.pending_layout.take() else {
         return;
     };
 
     clear_level_entities(&mut commands, &level_entities);
+    commands.insert_resource(TileMap::default());
+    commands.insert_resource(Encounter::default());
 
     let definition = level_state.definition(index).clone();
     spawn_layout_for_level(&mut commands, &room_assets, &definition);
@@ -107,10 +254,11 @@ pub fn finalize_level_load(
     mut commands: Commands,
     mut build_context: ResMut<LevelBuildContext>,
     level_state: Res<LevelState>,
+    archetypes: Res<EnemyArchetypeRegistry>,
     environment_assets: Res<EnvironmentAssets>,
     asset_server: Res<AssetServer>,
     door_query: Query<&Transform, (With<Door>, With<LevelEntity>)>,
-    tile_query: Query<(&Transform, &RoomTile, Option<&CorridorTile>), With<LevelEntity>>,
+    tile_query: Query<(Entity, &Transform, &RoomTile, Option<&CorridorTile>), With<LevelEntity>>,
     mut player_query: Query<
         (
             &mut Transform,
@@ -154,11 +302,19 @@ pub fn finalize_level_load(
 
     let tile_samples: Vec<(Vec3, RoomTileType, bool)> = tile_query
         .iter()
-        .map(|(transform, tile, corridor)| {
+        .map(|(_, transform, tile, corridor)| {
             (transform.translation, tile.tile_type, corridor.is_some())
         })
         .collect();
 
+    // 碰撞、飛行物與 AI 共用的瓷磚索引，之後只隨門的開關更新
+    commands.insert_resource(TileMap::from_tiles(
+        tile_size,
+        tile_query.iter().map(|(entity, transform, tile, _)| {
+            (entity, transform.translation.truncate(), tile.tile_type)
+        }),
+    ));
+
     let corridor_tiles: HashSet<(i32, i32)> = tile_samples
         .iter()
         .filter(|(_, _, is_corridor)| *is_corridor)
@@ -173,6 +329,26 @@ pub fn finalize_level_load(
     let spawn_position = compute_player_spawn_position(&tile_samples, tile_size, door_position);
     commands.insert_resource(EntranceLocation::new(spawn_position));
 
+    // 遭遇戰範圍是房間（不含走廊）的地板，扣掉入口門周圍 `trigger_depth_tiles` 格
+    if let Some(plan) = &definition.encounter {
+        let door_clearance = plan.trigger_depth_tiles * tile_size;
+        let area = tile_samples
+            .iter()
+            .filter(|(position, tile_type, is_corridor)| {
+                !*is_corridor
+                    && *tile_type == RoomTileType::Floor
+                    && position.truncate().distance(door_position.truncate()) > door_clearance
+            })
+            .map(|(position, _, _)| {
+                (
+                    (position.x / tile_size).round() as i32,
+                    (position.y / tile_size).round() as i32,
+                )
+            })
+            .collect();
+        commands.insert_resource(Encounter::new(plan.clone(), area, definition.seed));
+    }
+
     if let Ok((mut player_transform, velocity, input_vector)) = player_query.single_mut() {
         player_transform.translation.x = spawn_position.x;
         player_transform.translation.y = spawn_position.y;
@@ -248,11 +424,17 @@ pub fn finalize_level_load(
         exit_position,
     );
 
-    let is_final_level = definition.index + 1 >= level_state.definition_count().max(1);
+    // 無盡模式沒有最後一關，改為到達一定深度後使用最終頭目的強度
+    let is_final_level = if level_state.is_endless() {
+        definition.index >= ENDLESS_ELITE_BOSS_DEPTH
+    } else {
+        definition.index + 1 >= level_state.definition_count().max(1)
+    };
 
     spawn_enemies_for_level(
         &mut commands,
         &asset_server,
+        &archetypes,
         definition,
         &mut rng,
         tile_size,
@@ -278,12 +460,15 @@ pub fn finalize_level_load(
         tile_size,
         target_level: next_level,
         rewards_spawned: false,
-        rewards_available: definition.enemy_counts.boss_wizards > 0,
+        rewards_available: definition
+            .enemy_counts
+            .iter()
+            .any(|(id, count)| count > 0 && archetypes.is_boss(id)),
     });
 
     level_loaded_events.write(LevelLoadedEvent {
         index: definition.index,
-        name: definition.name,
+        name: definition.name.clone(),
     });
 }
 
@@ -325,7 +510,7 @@ fn spawn_layout_for_level(
     }
 }
 
-fn spawn_level_exit_portal(
+pub fn spawn_level_exit_portal(
     commands: &mut Commands,
     assets: &LevelExitAssets,
     anchor: Vec3,
@@ -554,6 +739,7 @@ fn spawn_environment_props_for_level(
 fn spawn_enemies_for_level(
     commands: &mut Commands,
     asset_server: &AssetServer,
+    archetypes: &EnemyArchetypeRegistry,
     definition: &LevelDefinition,
     rng: &mut StdRng,
     tile_size: f32,
@@ -564,9 +750,33 @@ fn spawn_enemies_for_level(
     player_stats: Option<PlayerCombatSnapshot>,
     final_level: bool,
 ) {
-    let mut needed = definition.enemy_counts.slimes
-        + definition.enemy_counts.cyclops
-        + definition.enemy_counts.spiders;
+    let mut regular = Vec::new();
+    let mut bosses = Vec::new();
+    for (id, count) in definition.enemy_counts.iter() {
+        if count == 0 {
+            continue;
+        }
+
+        let Some(archetype) = archetypes.get(id) else {
+            warn!(
+                "Level {} references unknown enemy archetype '{}'; skipping",
+                definition.index + 1,
+                id
+            );
+            continue;
+        };
+
+        if archetype.boss {
+            bosses.push((archetype, count));
+        } else {
+            regular.push((archetype, count));
+        }
+    }
+
+    // 依行為決定放置順序：先放近戰，再放需要走道的蜘蛛，最後是衝撞型
+    regular.sort_by_key(|(archetype, _)| spawn_order(archetype.behavior));
+
+    let mut needed: usize = regular.iter().map(|(_, count)| count).sum();
 
     retain_valid_positions(
         available_positions,
@@ -585,10 +795,6 @@ fn spawn_enemies_for_level(
         needed = available_positions.len();
     }
 
-    let slime_texture = asset_server.load("characters/enemies/slime.png");
-    let spider_texture = asset_server.load("characters/enemies/spider.png");
-    let cyclops_texture = asset_server.load("characters/enemies/cyclops.png");
-
     let floor_tiles: HashSet<(i32, i32)> = available_positions
         .iter()
         .map(|position| {
@@ -637,347 +843,220 @@ fn spawn_enemies_for_level(
         None
     }
 
-    for serial in 0..definition.enemy_counts.slimes {
-        let Some(position) =
-            take_position(&mut spawn_positions, &mut assigned, tile_size, |_| true)
-        else {
-            break;
-        };
-
-        spawn_slime_entity(
-            commands,
-            &slime_texture,
-            position,
-            tile_size,
-            definition.index,
-            serial + 1,
-        );
-    }
-
     let entrance_position = spawn_position.truncate();
     let door_position_2d = door_position.truncate();
     let spider_entrance_buffer = tile_size * 4.0;
 
-    for serial in 0..definition.enemy_counts.spiders {
-        let position = take_position(
-            &mut spawn_positions,
-            &mut assigned,
-            tile_size,
-            |candidate| {
-                let candidate_2d = candidate.truncate();
-                candidate_2d.distance(entrance_position) >= spider_entrance_buffer
-                    && candidate_2d.distance(door_position_2d) >= spider_entrance_buffer
-                    && has_horizontal_clearance(candidate, tile_size, &floor_tiles)
-            },
-        )
-        .or_else(|| {
-            take_position(
-                &mut spawn_positions,
-                &mut assigned,
-                tile_size,
-                |candidate| {
-                    let candidate_2d = candidate.truncate();
-                    candidate_2d.distance(entrance_position) >= spider_entrance_buffer
-                        && candidate_2d.distance(door_position_2d) >= spider_entrance_buffer
-                },
-            )
-        });
-
-        let Some(position) = position else {
-            break;
-        };
+    for (archetype, count) in regular {
+        for serial in 1..=count {
+            let position = if archetype.behavior == EnemyBehaviorKind::WebShooter {
+                take_position(
+                    &mut spawn_positions,
+                    &mut assigned,
+                    tile_size,
+                    |candidate| {
+                        let candidate_2d = candidate.truncate();
+                        candidate_2d.distance(entrance_position) >= spider_entrance_buffer
+                            && candidate_2d.distance(door_position_2d) >= spider_entrance_buffer
+                            && has_horizontal_clearance(candidate, tile_size, &floor_tiles)
+                    },
+                )
+                .or_else(|| {
+                    take_position(
+                        &mut spawn_positions,
+                        &mut assigned,
+                        tile_size,
+                        |candidate| {
+                            let candidate_2d = candidate.truncate();
+                            candidate_2d.distance(entrance_position) >= spider_entrance_buffer
+                                && candidate_2d.distance(door_position_2d) >= spider_entrance_buffer
+                        },
+                    )
+                })
+            } else {
+                take_position(&mut spawn_positions, &mut assigned, tile_size, |_| true)
+            };
 
-        spawn_spider_entity(
-            commands,
-            &spider_texture,
-            position,
-            tile_size,
-            definition.index,
-            serial + 1,
-            &floor_tiles,
-        );
-    }
+            let Some(position) = position else {
+                break;
+            };
 
-    for serial in 0..definition.enemy_counts.cyclops {
-        let Some(position) =
-            take_position(&mut spawn_positions, &mut assigned, tile_size, |_| true)
-        else {
-            break;
-        };
+            let placement = level_enemy_placement(
+                archetype,
+                Vec3::new(position.x, position.y, 9.0),
+                tile_size,
+                serial,
+                &floor_tiles,
+            );
+            let entity = spawn_enemy_from_archetype(
+                commands,
+                asset_server,
+                archetype,
+                placement,
+                EnemySpawnStats::from_archetype(archetype),
+            );
+            commands.entity(entity).insert((
+                LevelEntity,
+                Name::new(format!(
+                    "Level{}{}{}",
+                    definition.index + 1,
+                    archetype.name_tag(),
+                    serial
+                )),
+            ));
 
-        spawn_cyclops_entity(
-            commands,
-            &cyclops_texture,
-            position,
-            tile_size,
-            definition.index,
-            serial + 1,
-        );
+            if let Some(route) =
+                chaser_patrol_route(archetype, placement, tile_size, serial, &floor_tiles)
+            {
+                commands.entity(entity).insert(route);
+            }
+        }
     }
 
-    if definition.enemy_counts.boss_wizards > 0 {
-        let Some(exit_location) = exit_position else {
-            warn!(
-                "Level {} defines a wizard boss but is missing portal coordinates; skipping boss",
-                definition.index + 1
-            );
-            return;
-        };
+    if bosses.is_empty() {
+        return;
+    }
 
-        let body_texture = asset_server.load(WIZARD_BOSS_SPRITE_PATH);
-        let staff_texture = asset_server.load(WIZARD_BOSS_STAFF_SPRITE_PATH);
+    let Some(exit_location) = exit_position else {
+        warn!(
+            "Level {} defines a boss but is missing portal coordinates; skipping boss",
+            definition.index + 1
+        );
+        return;
+    };
 
-        for serial in 0..definition.enemy_counts.boss_wizards {
-            let offset_multiplier = serial as f32;
-            let spawn_position = Vec3::new(
+    let mut boss_slot = 0;
+    for (archetype, count) in bosses {
+        for serial in 1..=count {
+            let offset_multiplier = boss_slot as f32;
+            boss_slot += 1;
+            let boss_position = Vec3::new(
                 exit_location.x,
                 exit_location.y - tile_size * (1.2 + offset_multiplier * 0.6),
                 9.0,
             );
 
-            spawn_boss_wizard_entity(
+            let stats = scaled_boss_stats(archetype, definition.index, final_level, player_stats)
+                .scaled(definition.boss_stat_multiplier);
+            let entity = spawn_enemy_from_archetype(
                 commands,
-                &body_texture,
-                &staff_texture,
-                spawn_position,
-                definition.index,
-                serial + 1,
-                player_stats,
-                final_level,
+                asset_server,
+                archetype,
+                EnemyPlacement::at(boss_position, archetype.movement.patrol_range, 1.0),
+                stats,
             );
+            commands.entity(entity).insert((
+                LevelEntity,
+                Name::new(format!(
+                    "Level{}{}{}",
+                    definition.index + 1,
+                    archetype.name_tag(),
+                    serial
+                )),
+            ));
         }
     }
 }
 
-fn spawn_slime_entity(
-    commands: &mut Commands,
-    texture: &Handle<Image>,
-    position: Vec3,
-    tile_size: f32,
-    level_index: usize,
-    serial: usize,
-) {
-    let patrol_origin = Vec3::new(position.x, position.y, 9.0);
-    let patrol_range = (SLIME_PATROL_RANGE)
-        .min(tile_size * 6.0)
-        .max(tile_size * 1.5);
-    let direction = if serial % 2 == 0 { 1.0 } else { -1.0 };
-
-    commands.spawn((
-        LevelEntity,
-        Enemy,
-        Slime,
-        Sprite::from_image(texture.clone()),
-        Transform::from_translation(patrol_origin).with_scale(Vec3::splat(SLIME_SCALE)),
-        Health::new(SLIME_HEALTH),
-        Attack::new(SLIME_BASE_ATTACK),
-        Defense::new(SLIME_BASE_DEFENSE),
-        EnemyAIState {
-            state: EnemyBehaviorState::Patrolling,
-        },
-        EnemyPatrol {
-            origin: patrol_origin,
-            range: patrol_range,
-            direction,
-        },
-        EnemyAlert {
-            trigger_radius: SLIME_ALERT_RADIUS,
-            leash_radius: SLIME_LEASH_RADIUS,
-        },
-        EnemySpeeds {
-            patrol: SLIME_PATROL_SPEED,
-            chase: SLIME_CHASE_SPEED,
-        },
-        EnemyAttack {
-            radius: SLIME_ATTACK_RADIUS,
-            cooldown: {
-                let mut timer = Timer::from_seconds(SLIME_ATTACK_COOLDOWN, TimerMode::Repeating);
-                timer.set_elapsed(timer.duration());
-                timer
-            },
-        },
-        Name::new(format!("Level{}Slime{}", level_index + 1, serial)),
-    ));
+fn spawn_order(behavior: EnemyBehaviorKind) -> u8 {
+    match behavior {
+        EnemyBehaviorKind::Chaser => 0,
+        EnemyBehaviorKind::WebShooter => 1,
+        EnemyBehaviorKind::Charger { .. } => 2,
+        EnemyBehaviorKind::SpellCaster => 3,
+    }
 }
 
-fn spawn_cyclops_entity(
-    commands: &mut Commands,
-    texture: &Handle<Image>,
+fn level_enemy_placement(
+    archetype: &EnemyArchetype,
     position: Vec3,
     tile_size: f32,
-    level_index: usize,
     serial: usize,
-) {
-    let patrol_origin = Vec3::new(position.x, position.y, 9.0);
-    let patrol_range = (CYCLOPS_PATROL_RANGE)
-        .min(tile_size * 6.0)
-        .max(tile_size * 2.0);
-    let direction = if serial % 2 == 0 { -1.0 } else { 1.0 };
-
-    commands.spawn((
-        LevelEntity,
-        Enemy,
-        Cyclops,
-        Sprite::from_image(texture.clone()),
-        Transform::from_translation(patrol_origin).with_scale(Vec3::splat(CYCLOPS_SCALE)),
-        Health::new(CYCLOPS_HEALTH),
-        Attack::new(CYCLOPS_BASE_ATTACK),
-        Defense::new(CYCLOPS_BASE_DEFENSE),
-        EnemyAIState {
-            state: EnemyBehaviorState::Patrolling,
-        },
-        EnemyPatrol {
-            origin: patrol_origin,
-            range: patrol_range,
-            direction,
-        },
-        EnemyAlert {
-            trigger_radius: CYCLOPS_ALERT_RADIUS,
-            leash_radius: CYCLOPS_LEASH_RADIUS,
-        },
-        EnemySpeeds {
-            patrol: CYCLOPS_PATROL_SPEED,
-            chase: CYCLOPS_CHASE_SPEED,
-        },
-        EnemyAttack {
-            radius: CYCLOPS_ATTACK_RADIUS,
-            cooldown: {
-                let mut timer = Timer::from_seconds(CYCLOPS_ATTACK_COOLDOWN, TimerMode::Repeating);
-                timer.set_elapsed(timer.duration());
-                timer
-            },
-        },
-        CyclopsCharge {
-            windup: Timer::from_seconds(CYCLOPS_WINDUP_SECONDS, TimerMode::Once),
-            charge: Timer::from_seconds(CYCLOPS_CHARGE_SECONDS, TimerMode::Once),
-            cooldown: {
-                let mut timer = Timer::from_seconds(CYCLOPS_COOLDOWN_SECONDS, TimerMode::Once);
-                timer.set_elapsed(timer.duration());
-                timer
-            },
-            facing: Vec2::X,
-            ready: true,
-        },
-        Name::new(format!("Level{}Cyclops{}", level_index + 1, serial)),
-    ));
+    floor_tiles: &HashSet<(i32, i32)>,
+) -> EnemyPlacement {
+    let even = serial % 2 == 0;
+
+    match archetype.behavior {
+        EnemyBehaviorKind::WebShooter => {
+            let (patrol_origin, patrol_range) = resolve_spider_patrol_bounds(
+                position,
+                tile_size,
+                archetype.movement.patrol_range,
+                floor_tiles,
+            );
+            EnemyPlacement::at(patrol_origin, patrol_range, if even { 1.0 } else { -1.0 })
+        }
+        EnemyBehaviorKind::Charger { .. } => {
+            let patrol_range = archetype
+                .movement
+                .patrol_range
+                .min(tile_size * 6.0)
+                .max(tile_size * 2.0);
+            EnemyPlacement::at(position, patrol_range, if even { -1.0 } else { 1.0 })
+        }
+        EnemyBehaviorKind::Chaser | EnemyBehaviorKind::SpellCaster => {
+            let patrol_range = archetype
+                .movement
+                .patrol_range
+                .min(tile_size * 6.0)
+                .max(tile_size * 1.5);
+            EnemyPlacement::at(position, patrol_range, if even { 1.0 } else { -1.0 })
+        }
+    }
 }
 
-fn spawn_spider_entity(
-    commands: &mut Commands,
-    texture: &Handle<Image>,
-    position: Vec3,
+/// 追擊型敵人在出生點附近的地板上繞一圈巡邏；上下兩個方向取地板較多的一邊
+fn chaser_patrol_route(
+    archetype: &EnemyArchetype,
+    placement: EnemyPlacement,
     tile_size: f32,
-    level_index: usize,
     serial: usize,
     floor_tiles: &HashSet<(i32, i32)>,
-) {
-    let (patrol_origin, patrol_range) =
-        resolve_spider_patrol_bounds(position, tile_size, floor_tiles);
-    let direction = if serial % 2 == 0 { 1.0 } else { -1.0 };
-
-    commands.spawn((
-        LevelEntity,
-        Enemy,
-        Spider,
-        Sprite::from_image(texture.clone()),
-        Transform::from_translation(patrol_origin).with_scale(Vec3::splat(SPIDER_SCALE)),
-        Health::new(SPIDER_HEALTH),
-        Attack::new(SPIDER_BASE_ATTACK),
-        Defense::new(SPIDER_BASE_DEFENSE),
-        EnemyAIState {
-            state: EnemyBehaviorState::Patrolling,
-        },
-        EnemyPatrol {
-            origin: patrol_origin,
-            range: patrol_range,
-            direction,
-        },
-        EnemyAlert {
-            trigger_radius: 0.0,
-            leash_radius: 0.0,
-        },
-        EnemySpeeds {
-            patrol: SPIDER_PATROL_SPEED,
-            chase: SPIDER_PATROL_SPEED,
-        },
-        EnemyAttack {
-            radius: SPIDER_ATTACK_RADIUS,
-            cooldown: {
-                let mut timer = Timer::from_seconds(SPIDER_ATTACK_COOLDOWN, TimerMode::Repeating);
-                timer.set_elapsed(timer.duration());
-                timer
-            },
-        },
-        Name::new(format!("Level{}Spider{}", level_index + 1, serial)),
-    ));
-}
+) -> Option<EnemyPatrolRoute> {
+    if archetype.behavior != EnemyBehaviorKind::Chaser {
+        return None;
+    }
 
-fn spawn_boss_wizard_entity(
-    commands: &mut Commands,
-    body_texture: &Handle<Image>,
-    staff_texture: &Handle<Image>,
-    position: Vec3,
-    level_index: usize,
-    serial: usize,
-    player_stats: Option<PlayerCombatSnapshot>,
-    final_boss: bool,
-) {
-    let patrol_origin = Vec3::new(position.x, position.y, 9.0);
-    let (boss_health, boss_attack, boss_defense) =
-        wizard_boss_stats(level_index, final_boss, player_stats);
+    let origin = (
+        (placement.patrol_origin.x / tile_size).round() as i32,
+        (placement.patrol_origin.y / tile_size).round() as i32,
+    );
+    let reach = ((placement.patrol_range / tile_size).round() as i32).max(1);
+
+    let loop_cells = |vertical: i32| -> Vec<(i32, i32)> {
+        [
+            (reach, 0),
+            (reach, vertical),
+            (-reach, vertical),
+            (-reach, 0),
+        ]
+        .into_iter()
+        .map(|(dx, dy)| (origin.0 + dx, origin.1 + dy))
+        .filter(|cell| floor_tiles.contains(cell))
+        .collect()
+    };
+    let upward = loop_cells(reach);
+    let downward = loop_cells(-reach);
+    let mut cells = if downward.len() > upward.len() {
+        downward
+    } else {
+        upward
+    };
 
-    let boss_entity = commands
-        .spawn((
-            LevelEntity,
-            Enemy,
-            BossWizard,
-            Sprite::from_image(body_texture.clone()),
-            Transform::from_translation(patrol_origin).with_scale(Vec3::splat(WIZARD_BOSS_SCALE)),
-            Health::new(boss_health),
-            Attack::new(boss_attack),
-            Defense::new(boss_defense),
-            EnemyAIState {
-                state: EnemyBehaviorState::Patrolling,
-            },
-            EnemyPatrol {
-                origin: patrol_origin,
-                range: WIZARD_BOSS_PATROL_RANGE,
-                direction: 1.0,
-            },
-            EnemyAlert {
-                trigger_radius: WIZARD_BOSS_ALERT_RADIUS,
-                leash_radius: WIZARD_BOSS_LEASH_RADIUS,
-            },
-            EnemySpeeds {
-                patrol: WIZARD_BOSS_PATROL_SPEED,
-                chase: WIZARD_BOSS_CHASE_SPEED,
-            },
-            EnemyAttack {
-                radius: WIZARD_BOSS_ATTACK_RADIUS,
-                cooldown: {
-                    let mut timer =
-                        Timer::from_seconds(WIZARD_BOSS_ATTACK_COOLDOWN, TimerMode::Repeating);
-                    timer.set_elapsed(timer.duration());
-                    timer
-                },
-            },
-            Name::new(format!("Level{}WizardBoss{}", level_index + 1, serial)),
-        ))
-        .id();
+    if cells.len() < 2 {
+        return None;
+    }
+    // 相鄰的敵人往相反方向繞
+    if serial % 2 == 1 {
+        cells.reverse();
+    }
 
-    commands.entity(boss_entity).with_children(|parent| {
-        parent.spawn((
-            BossWizardStaff,
-            Sprite::from_image(staff_texture.clone()),
-            Transform::from_translation(Vec3::new(
-                WIZARD_BOSS_STAFF_OFFSET_X,
-                WIZARD_BOSS_STAFF_OFFSET_Y,
-                WIZARD_BOSS_STAFF_OFFSET_Z,
-            ))
-            .with_scale(Vec3::splat(WIZARD_BOSS_STAFF_SCALE)),
-            Name::new(format!("Level{}WizardStaff{}", level_index + 1, serial)),
-        ));
-    });
+    Some(EnemyPatrolRoute::new(
+        cells
+            .into_iter()
+            .map(|(x, y)| Vec2::new(x as f32, y as f32) * tile_size)
+            .collect(),
+    ))
 }
 
 fn sample_positions(positions: &mut Vec<Vec3>, rng: &mut StdRng, count: usize) -> Vec<Vec3> {
@@ -1009,6 +1088,7 @@ fn has_horizontal_clearance(
 fn resolve_spider_patrol_bounds(
     position: Vec3,
     tile_size: f32,
+    max_range: f32,
     floor_tiles: &HashSet<(i32, i32)>,
 ) -> (Vec3, f32) {
     let tile_x = (position.x / tile_size).round() as i32;
@@ -1051,36 +1131,50 @@ fn resolve_spider_patrol_bounds(
 
     let origin_x = (min_edge + max_edge) * 0.5;
     let range = (width * 0.5)
-        .min(SPIDER_PATROL_RANGE)
-        .clamp(tile_size * 0.75, SPIDER_PATROL_RANGE);
+        .min(max_range)
+        .clamp(tile_size * 0.75, max_range);
 
     (Vec3::new(origin_x, position.y, position.z), range)
 }
 
-fn wizard_boss_stats(
+/// 有 player_scaling 的頭目依玩家能力決定強度，其餘沿用資料中的能力值
+fn scaled_boss_stats(
+    archetype: &EnemyArchetype,
     level_index: usize,
     final_boss: bool,
     player_stats: Option<PlayerCombatSnapshot>,
-) -> (i32, i32, i32) {
-    let multiplier = if final_boss { 1.3 } else { 1.1 };
-
-    if let Some(stats) = player_stats {
-        let attack = ((stats.attack as f32) * multiplier).ceil() as i32;
-        let defense = ((stats.defense as f32) * multiplier).ceil() as i32;
-        let health = ((stats.max_health as f32) * multiplier).ceil() as i32;
-        return (health.max(1), attack.max(1), defense.max(0));
-    }
+) -> EnemySpawnStats {
+    let Some(scaling) = archetype.player_scaling else {
+        return EnemySpawnStats::from_archetype(archetype);
+    };
+
+    let multiplier = if final_boss {
+        scaling.final_level_multiplier
+    } else {
+        scaling.multiplier
+    };
 
-    let idx = level_index.min(PLAYER_MAX_LEVEL);
-    let player_attack = PLAYER_LEVEL_BASE_ATTACK[idx];
-    let player_defense = PLAYER_LEVEL_BASE_DEFENSE[idx];
-    let player_health = PLAYER_INITIAL_HEALTH;
+    let (player_health, player_attack, player_defense) = match player_stats {
+        Some(stats) => (stats.max_health, stats.attack, stats.defense),
+        None => {
+            let idx = level_index.min(PLAYER_MAX_LEVEL);
+            (
+                PLAYER_INITIAL_HEALTH,
+                PLAYER_LEVEL_BASE_ATTACK[idx],
+                PLAYER_LEVEL_BASE_DEFENSE[idx],
+            )
+        }
+    };
 
     let attack = ((player_attack as f32) * multiplier).ceil() as i32;
     let defense = ((player_defense as f32) * multiplier).ceil() as i32;
     let health = ((player_health as f32) * multiplier).ceil() as i32;
 
-    (health.max(1), attack.max(1), defense.max(0))
+    EnemySpawnStats {
+        health: health.max(1),
+        attack: attack.max(1),
+        defense: defense.max(0),
+    }
 }
 
 fn prop_position_valid(
@@ -1228,7 +1322,7 @@ pub fn spawn_rewards_on_boss_defeat(
 
     let mut triggered = false;
     for event in events.read() {
-        if event.enemy_name == "Wizard Boss" {
+        if event.is_boss {
             triggered = true;
         }
     }
@@ -1255,18 +1349,10 @@ pub fn spawn_rewards_on_boss_defeat(
         }
     }
 
-    let loot_items = match rewards.level_index {
-        0 => vec![
-            PickupEffect::EquipShield(ShieldKind::Level1),
-            PickupEffect::EquipWeapon(WeaponKind::Level2),
-        ],
-        1 => vec![PickupEffect::EquipWeapon(WeaponKind::Level3)],
-        2 => vec![PickupEffect::EquipWeapon(WeaponKind::Level4)],
-        _ => vec![
-            PickupEffect::EquipWeapon(WeaponKind::Level5),
-            PickupEffect::EquipShield(ShieldKind::Level2),
-        ],
-    };
+    let loot_items = level_state
+        .get_definition(rewards.level_index)
+        .map(|definition| definition.boss_loot.clone())
+        .unwrap_or_default();
 
     if let Some(anchor) = rewards.portal_anchor {
         let spacing = rewards.tile_size * 0.7;
@@ -1292,7 +1378,7 @@ pub fn spawn_rewards_on_boss_defeat(
         }
 
         let final_index = level_state.definition_count().saturating_sub(1);
-        if rewards.level_index >= final_index {
+        if !level_state.is_endless() && rewards.level_index >= final_index {
             commands.spawn((
                 LevelEntity,
                 Node {
@@ -1,45 +1,81 @@
 pub mod attack;
 pub mod audio;
+pub mod block;
+pub mod boss;
 pub mod camera;
 pub mod chest;
+pub mod combat_text;
+pub mod controls;
+pub mod damage;
+pub mod dodge;
 pub mod door_interaction;
 pub mod effects;
+pub mod encounter;
 pub mod enemy;
 pub mod equipment;
 pub mod game_session;
+pub mod gamepad;
 pub mod health;
 pub mod input;
 pub mod items;
+pub mod knockback;
 pub mod level;
 pub mod movement;
+pub mod navigation;
 pub mod player_stats;
 pub mod player_status;
 pub mod progression;
+pub mod projectile;
+pub mod ranged_attack;
 pub mod room_transition;
+pub mod save_slots;
 pub mod setup;
+pub mod status_effects;
+pub mod tile_map;
 pub mod ui;
 pub mod wall_collision;
 pub mod world;
+pub mod world_snapshot;
 
 pub use attack::*;
 pub use audio::*;
+pub use block::*;
+pub use boss::*;
 pub use camera::*;
 pub use chest::*;
+pub use combat_text::*;
+#[allow(unused_imports)]
+pub use controls::*;
+pub use damage::*;
+pub use dodge::*;
 pub use door_interaction::*;
 pub use effects::*;
+pub use encounter::*;
 pub use enemy::*;
 pub use equipment::*;
 #[allow(unused_imports)]
 pub use game_session::*;
+#[allow(unused_imports)]
+pub use gamepad::*;
 pub use health::*;
 pub use input::*;
 pub use items::*;
+pub use knockback::*;
 pub use movement::*;
+pub use navigation::*;
 pub use player_stats::*;
 pub use player_status::*;
 pub use progression::*;
+pub use projectile::*;
+pub use ranged_attack::*;
 pub use room_transition::*;
+#[allow(unused_imports)]
+pub use save_slots::*;
 pub use setup::*;
+pub use status_effects::*;
+pub use tile_map::*;
 pub use ui::*;
 pub use wall_collision::*;
 pub use world::*;
+#[allow(unused_imports)]
+pub use world_snapshot::*;
@@ -1,49 +1,77 @@
 use crate::components::*;
 use crate::constants::*;
-use crate::resources::GameSession;
+use crate::resources::{ActionState, InputAction, TileMap};
 use bevy::prelude::*;
 
 pub fn movement_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    session: Res<GameSession>,
+    actions: Res<ActionState>,
     mut query: Query<
         (
             &mut Transform,
             &mut Velocity,
             &mut PlayerFacing,
             &mut InputVector,
+            Has<HitStun>,
+            Has<DodgeRoll>,
+            Has<Blocking>,
+            Option<&StatusEffects>,
+            &BoxCollider,
         ),
         (With<Player>, Without<PlayerDead>),
     >,
     time: Res<Time>,
+    tile_map: Res<TileMap>,
 ) {
-    if !session.is_playing() {
-        return;
-    }
-
-    for (mut transform, mut velocity, mut facing, mut input_vector) in &mut query {
+    for (
+        mut transform,
+        mut velocity,
+        mut facing,
+        mut input_vector,
+        stunned,
+        dodging,
+        blocking,
+        status,
+        collider,
+    ) in &mut query
+    {
         velocity.x = 0.0;
         velocity.y = 0.0;
 
+        // 受擊硬直或翻滾期間無法操作，位移交給擊退／翻滾系統
+        if stunned || dodging {
+            input_vector.0 = Vec2::ZERO;
+            continue;
+        }
+
         let mut raw_input = Vec2::ZERO;
 
-        if keyboard_input.pressed(KeyCode::KeyW) || keyboard_input.pressed(KeyCode::ArrowUp) {
+        if actions.pressed(InputAction::MoveUp) {
             velocity.y = PLAYER_SPEED;
             raw_input.y += 1.0;
         }
-        if keyboard_input.pressed(KeyCode::KeyS) || keyboard_input.pressed(KeyCode::ArrowDown) {
+        if actions.pressed(InputAction::MoveDown) {
             velocity.y = -PLAYER_SPEED;
             raw_input.y -= 1.0;
         }
-        if keyboard_input.pressed(KeyCode::KeyA) || keyboard_input.pressed(KeyCode::ArrowLeft) {
+        if actions.pressed(InputAction::MoveLeft) {
             velocity.x = -PLAYER_SPEED;
             raw_input.x -= 1.0;
         }
-        if keyboard_input.pressed(KeyCode::KeyD) || keyboard_input.pressed(KeyCode::ArrowRight) {
+        if actions.pressed(InputAction::MoveRight) {
             velocity.x = PLAYER_SPEED;
             raw_input.x += 1.0;
         }
 
+        // 沒有按下移動鍵時改用搖桿，推多少就走多快
+        if raw_input == Vec2::ZERO {
+            let stick = actions.move_axis();
+            if stick.length() > INPUT_DEADZONE {
+                velocity.x = stick.x * PLAYER_SPEED;
+                velocity.y = stick.y * PLAYER_SPEED;
+                raw_input = stick;
+            }
+        }
+
         // Update input vector for room transition system
         input_vector.0 = if raw_input.length() > INPUT_DEADZONE {
             raw_input.normalize()
@@ -51,12 +79,26 @@ pub fn movement_system(
             Vec2::ZERO
         };
 
-        // Update facing direction if moving
-        if velocity.x != 0.0 || velocity.y != 0.0 {
+        // Update facing direction if moving; 舉盾時盾牌維持原本的朝向
+        if (velocity.x != 0.0 || velocity.y != 0.0) && !blocking {
             facing.direction = Vec2::new(velocity.x, velocity.y).normalize();
         }
 
-        transform.translation.x += velocity.x * time.delta_secs();
-        transform.translation.y += velocity.y * time.delta_secs();
+        // 緩速與舉盾只影響移動距離，不改變面向
+        let mut speed_multiplier = status.map_or(1.0, StatusEffects::movement_multiplier);
+        if blocking {
+            speed_multiplier *= PLAYER_BLOCK_MOVE_MULTIPLIER;
+        }
+        velocity.x *= speed_multiplier;
+        velocity.y *= speed_multiplier;
+
+        let displacement = Vec2::new(velocity.x, velocity.y) * time.delta_secs();
+        let position = tile_map.move_and_slide(
+            transform.translation.truncate(),
+            displacement,
+            collider.half_size,
+        );
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
     }
 }
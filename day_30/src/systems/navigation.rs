@@ -0,0 +1,54 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::{NavigationGrid, TileMap};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// 瓷磚索引更新（換關、開關門、擺設生成、讀檔）後重建行走網格
+pub fn rebuild_navigation_grid_system(tile_map: Res<TileMap>, mut grid: ResMut<NavigationGrid>) {
+    if !tile_map.is_changed() {
+        return;
+    }
+
+    *grid = NavigationGrid::new(tile_map.tile_size(), tile_map.walkable_cells().collect());
+}
+
+/// 沿著路徑朝目標前進，回傳這一影格的移動方向；還沒有導航網格時直接朝目標走
+pub fn steer_along_path(
+    grid: Option<&NavigationGrid>,
+    path: &mut EnemyPath,
+    from: Vec2,
+    goal: Vec2,
+    delta: Duration,
+) -> Vec2 {
+    let Some(grid) = grid.filter(|grid| !grid.is_empty()) else {
+        return (goal - from).normalize_or_zero();
+    };
+
+    path.repath.tick(delta);
+    let goal_cell = grid.cell(goal);
+    if path.goal_cell != Some(goal_cell) || path.repath.finished() {
+        // 找不到路時原地等待，下次重新規劃再試
+        path.waypoints = if grid.has_clear_corridor(from, goal, grid.body_half_width()) {
+            vec![goal]
+        } else {
+            grid.find_path(from, goal).unwrap_or_default()
+        };
+        path.goal_cell = Some(goal_cell);
+        path.repath.reset();
+    }
+
+    // 目標在同一格內移動時直接追上最新位置
+    if let Some(last) = path.waypoints.last_mut() {
+        *last = goal;
+    }
+    while path.waypoints.len() > 1
+        && path.waypoints[0].distance(from) <= ENEMY_WAYPOINT_REACHED_DISTANCE
+    {
+        path.waypoints.remove(0);
+    }
+
+    path.waypoints
+        .first()
+        .map_or(Vec2::ZERO, |next| (*next - from).normalize_or_zero())
+}
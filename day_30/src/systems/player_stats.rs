@@ -24,11 +24,14 @@ pub struct PlayerStatsStaminaText;
 #[derive(Component)]
 pub struct PlayerStatsStatusRow;
 
+/// 狀態列上對應某一種狀態異常的圖示
+#[derive(Component)]
+pub struct PlayerStatsStatusIcon(pub StatusEffectKind);
+
 pub fn spawn_player_stats_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
     let font = asset_server.load(PLAYER_STATS_FONT_PATH);
     let sword_icon = asset_server.load(PLAYER_STATS_ATTACK_ICON_PATH);
     let shield_icon = asset_server.load(PLAYER_STATS_DEFENSE_ICON_PATH);
-    let skull_icon = asset_server.load(PLAYER_STATS_POISON_ICON_PATH);
 
     commands
         .spawn((
@@ -173,15 +176,19 @@ pub fn spawn_player_stats_panel(mut commands: Commands, asset_server: Res<AssetS
                     Name::new("PlayerStatsStatusRow"),
                 ))
                 .with_children(|row| {
-                    row.spawn((
-                        ImageNode::new(skull_icon),
-                        Node {
-                            width: Val::Px(PLAYER_STATS_ICON_SIZE),
-                            height: Val::Px(PLAYER_STATS_ICON_SIZE),
-                            ..Default::default()
-                        },
-                        Name::new("PlayerStatsStatusIcon"),
-                    ));
+                    for kind in StatusEffectKind::ALL {
+                        row.spawn((
+                            PlayerStatsStatusIcon(kind),
+                            ImageNode::new(asset_server.load(kind.icon_path())),
+                            Node {
+                                width: Val::Px(PLAYER_STATS_ICON_SIZE),
+                                height: Val::Px(PLAYER_STATS_ICON_SIZE),
+                                display: Display::None,
+                                ..Default::default()
+                            },
+                            Name::new(format!("PlayerStatsStatusIcon{kind:?}")),
+                        ));
+                    }
                 });
         });
 }
@@ -192,7 +199,7 @@ pub fn update_player_stats_panel(
             &Attack,
             &Defense,
             &Stamina,
-            Option<&Poisoned>,
+            Option<&StatusEffects>,
             &PlayerProgression,
         ),
         With<Player>,
@@ -205,8 +212,12 @@ pub fn update_player_stats_panel(
         Query<&mut Text, With<PlayerStatsStaminaText>>,
     )>,
     mut status_query: Query<&mut Node, With<PlayerStatsStatusRow>>,
+    mut status_icon_query: Query<
+        (&mut Node, &PlayerStatsStatusIcon),
+        Without<PlayerStatsStatusRow>,
+    >,
 ) {
-    let Some((attack, defense, stamina, poison_state, progression)) = player_query.iter().next()
+    let Some((attack, defense, stamina, status_effects, progression)) = player_query.iter().next()
     else {
         return;
     };
@@ -254,11 +265,24 @@ pub fn update_player_stats_panel(
         *stamina_text = Text::new(value);
     }
 
+    let active = |kind: StatusEffectKind| status_effects.is_some_and(|effects| effects.has(kind));
+
     if let Some(mut status_row) = status_query.iter_mut().next() {
-        status_row.display = if poison_state.is_some() {
+        status_row.display = if status_effects.is_some_and(|effects| !effects.is_empty()) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+
+    for (mut node, icon) in &mut status_icon_query {
+        let display = if active(icon.0) {
             Display::Flex
         } else {
             Display::None
         };
+        if node.display != display {
+            node.display = display;
+        }
     }
 }
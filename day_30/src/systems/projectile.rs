@@ -0,0 +1,85 @@
+use crate::components::level::LevelEntity;
+use crate::components::*;
+use crate::resources::TileMap;
+use bevy::prelude::*;
+
+/// 推進所有飛行物，撞到牆、關閉的門或超過存在時間就移除
+pub fn move_projectiles_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<(Entity, &mut Transform, &mut Projectile)>,
+    tile_map: Res<TileMap>,
+) {
+    if projectile_query.is_empty() {
+        return;
+    }
+
+    let delta_secs = time.delta_secs();
+
+    for (entity, mut transform, mut projectile) in &mut projectile_query {
+        let previous_center = transform.translation.truncate();
+        transform.translation.x += projectile.velocity.x * delta_secs;
+        transform.translation.y += projectile.velocity.y * delta_secs;
+        let center = transform.translation.truncate();
+
+        let half_extent = projectile.direction() * projectile.half_length;
+        // 這一步的移動路徑，以及飛行物前後兩端是否碰到牆或關閉的門
+        let blocked = tile_map.raycast(previous_center, center).is_some()
+            || (half_extent != Vec2::ZERO
+                && (tile_map.blocks_projectile(center + half_extent)
+                    || tile_map.blocks_projectile(center - half_extent)));
+
+        if blocked || projectile.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// 追蹤型飛行物每影格轉向玩家，轉角受 `turn_rate` 限制
+pub fn homing_projectile_system(
+    time: Res<Time>,
+    player_query: Query<&Transform, (With<Player>, Without<PlayerDead>, Without<Projectile>)>,
+    mut projectile_query: Query<(&mut Transform, &mut Projectile, &HomingProjectile)>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    let target = player_transform.translation.truncate();
+
+    for (mut transform, mut projectile, homing) in &mut projectile_query {
+        let current = projectile.direction();
+        let desired = (target - transform.translation.truncate()).normalize_or_zero();
+        if current == Vec2::ZERO || desired == Vec2::ZERO {
+            continue;
+        }
+
+        let max_turn = homing.turn_rate * time.delta_secs();
+        let turn = current.angle_to(desired).clamp(-max_turn, max_turn);
+        projectile.velocity = Vec2::from_angle(turn).rotate(projectile.velocity);
+        transform.rotation = Quat::from_rotation_z(projectile.velocity.to_angle());
+    }
+}
+
+/// 飛行物共用的元件：移動資料、圖像與朝飛行方向旋轉的位置
+pub fn projectile_bundle(
+    position: Vec3,
+    velocity: Vec2,
+    size: Vec2,
+    color: Color,
+    lifetime_seconds: f32,
+) -> (LevelEntity, Projectile, Sprite, Transform) {
+    let angle = velocity.y.atan2(velocity.x);
+    let mut transform = Transform::from_translation(position);
+    transform.rotation = Quat::from_rotation_z(angle);
+
+    (
+        LevelEntity,
+        Projectile::new(velocity, size.x * 0.5, lifetime_seconds),
+        Sprite {
+            color,
+            custom_size: Some(size),
+            ..Default::default()
+        },
+        transform,
+    )
+}
@@ -0,0 +1,147 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::damage::DamageRequest;
+use crate::systems::projectile::projectile_bundle;
+use crate::systems::status_effects::{StatusEffectRequest, inflict_status_effects};
+use bevy::prelude::*;
+
+#[derive(Event, Clone, Copy)]
+pub struct RangedAttackInputEvent;
+
+/// 朝準心方向發射遠程武器，依武器扣除箭矢或耐力
+pub fn player_ranged_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut fire_events: EventReader<RangedAttackInputEvent>,
+    mut player_query: Query<
+        (
+            &Transform,
+            &Attack,
+            &mut EquippedRangedWeapon,
+            Option<Mut<Stamina>>,
+        ),
+        (With<Player>, Without<PlayerDead>, Without<DodgeRoll>),
+    >,
+    reticle_query: Query<&AttackReticle>,
+) {
+    let mut requested = false;
+    for _ in fire_events.read() {
+        requested = true;
+    }
+
+    let Some((transform, attack, mut weapon, stamina)) = player_query.iter_mut().next() else {
+        return;
+    };
+
+    weapon.cooldown.tick(time.delta());
+
+    if !requested || !weapon.cooldown.finished() {
+        return;
+    }
+
+    let Some(reticle) = reticle_query.iter().next() else {
+        return;
+    };
+
+    let direction = reticle.last_direction.normalize_or_zero();
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    let kind = weapon.kind;
+    if kind.ammo_capacity().is_some() && weapon.ammo == 0 {
+        dev_info!("{} is out of ammo", kind.display_name());
+        return;
+    }
+
+    if kind.stamina_cost() > 0.0 {
+        let Some(mut stamina) = stamina else {
+            return;
+        };
+        if !stamina.spend(kind.stamina_cost()) {
+            dev_info!("Not enough stamina; ranged attack canceled");
+            return;
+        }
+    }
+
+    if kind.ammo_capacity().is_some() {
+        weapon.ammo -= 1;
+    }
+    weapon.cooldown.reset();
+
+    let origin = transform.translation;
+    let spawn_position = Vec3::new(
+        origin.x + direction.x * PLAYER_PROJECTILE_SPAWN_OFFSET,
+        origin.y + direction.y * PLAYER_PROJECTILE_SPAWN_OFFSET,
+        origin.z + 1.0,
+    );
+    let damage = ((attack.value() as f32 * kind.damage_multiplier()).round() as i32).max(1);
+
+    let mut projectile = commands.spawn((
+        projectile_bundle(
+            spawn_position,
+            direction * kind.projectile_speed(),
+            kind.projectile_size(),
+            kind.projectile_color(),
+            PLAYER_PROJECTILE_LIFETIME,
+        ),
+        PlayerProjectile {
+            damage,
+            damage_type: kind.damage_type(),
+        },
+        Name::new(format!("{}Projectile", kind.display_name())),
+    ));
+    if let Some(status) = kind.on_hit_status() {
+        projectile.insert(OnHitStatusEffects(vec![status]));
+    }
+}
+
+/// 玩家飛行物碰到敵人時造成傷害並消失，一發只命中一隻
+pub fn player_projectile_hit_system(
+    mut commands: Commands,
+    projectile_query: Query<(
+        Entity,
+        &Transform,
+        &Projectile,
+        &PlayerProjectile,
+        Option<&OnHitStatusEffects>,
+    )>,
+    enemy_query: Query<
+        (Entity, &Transform, &Health),
+        (With<Enemy>, Without<EnemyDeathEffect>, Without<Projectile>),
+    >,
+    player_query: Query<Entity, With<Player>>,
+    mut damage_requests: EventWriter<DamageRequest>,
+    mut status_requests: EventWriter<StatusEffectRequest>,
+) {
+    let player_entity = player_query.iter().next();
+
+    for (entity, transform, motion, projectile, on_hit) in &projectile_query {
+        let position = transform.translation.truncate();
+
+        let target = enemy_query
+            .iter()
+            .filter(|(_, _, health)| health.current > 0)
+            .map(|(enemy, enemy_transform, _)| {
+                (
+                    enemy,
+                    enemy_transform.translation.truncate().distance(position),
+                )
+            })
+            .filter(|(_, distance)| *distance <= PLAYER_PROJECTILE_HIT_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        let Some((enemy, _)) = target else {
+            continue;
+        };
+
+        let mut request = DamageRequest::new(enemy, projectile.damage, projectile.damage_type)
+            .with_knockback(motion.direction() * PROJECTILE_KNOCKBACK);
+        if let Some(player_entity) = player_entity {
+            request = request.with_source(player_entity);
+        }
+        damage_requests.write(request);
+        inflict_status_effects(enemy, motion.direction(), on_hit, &mut status_requests);
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,396 @@
+use crate::components::{SaveSlotAction, SaveSlotButton, SaveSlotPickerRoot};
+use crate::constants::{
+    MENU_BUTTON_FONT_SIZE, MENU_BUTTON_HEIGHT, MENU_FONT_PATH, MENU_OVERLAY_COLOR,
+    MENU_TITLE_FONT_SIZE, SAVE_SLOT_BUTTON_WIDTH, SAVE_SLOT_COUNT, SAVE_SLOT_INFO_FONT_SIZE,
+    SAVE_SLOT_PANEL_WIDTH,
+};
+use crate::resources::{
+    GameSaveData, GameSession, SaveLoadError, SaveSlotPicker, SaveSlotPickerMode, SaveSlotState,
+    SaveStorage,
+};
+use crate::systems::game_session::{MenuClickEvent, RequestLoadGameEvent, RequestSaveGameEvent};
+use bevy::prelude::*;
+
+/// 開啟（或切換用途）存檔欄位選單
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OpenSaveSlotPickerEvent {
+    pub mode: SaveSlotPickerMode,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DeleteSaveSlotEvent {
+    pub slot: usize,
+}
+
+/// 任一欄位被寫入或刪除後送出，讓開著的選單重新整理
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SaveSlotsChangedEvent;
+
+/// 讀檔失敗時送出，訊息會顯示在存檔欄位選單上
+#[derive(Event, Debug, Clone)]
+pub struct SaveSlotErrorEvent {
+    pub message: String,
+}
+
+pub fn update_save_slot_picker(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    storage: Res<SaveStorage>,
+    mut session: ResMut<GameSession>,
+    mut open_events: EventReader<OpenSaveSlotPickerEvent>,
+    mut changed_events: EventReader<SaveSlotsChangedEvent>,
+    mut error_events: EventReader<SaveSlotErrorEvent>,
+) {
+    let mut requested_mode = None;
+    for event in open_events.read() {
+        requested_mode = Some(event.mode);
+    }
+
+    let mut changed = false;
+    for _ in changed_events.read() {
+        changed = true;
+    }
+
+    let mut message = None;
+    for event in error_events.read() {
+        message = Some(event.message.clone());
+    }
+
+    let mode = match (requested_mode, session.save_slot_picker) {
+        (Some(mode), _) => mode,
+        (None, Some(picker)) if changed || message.is_some() => picker.mode,
+        _ => return,
+    };
+
+    close_save_slot_picker(&mut commands, session.as_mut());
+    let root = build_save_slot_picker(
+        &mut commands,
+        &asset_server,
+        &storage,
+        mode,
+        message.as_deref(),
+    );
+    session.save_slot_picker = Some(SaveSlotPicker { root, mode });
+}
+
+pub fn handle_save_slot_interactions(
+    mut commands: Commands,
+    mut interactions: Query<
+        (&Interaction, &SaveSlotButton, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut session: ResMut<GameSession>,
+    mut save_events: EventWriter<RequestSaveGameEvent>,
+    mut load_events: EventWriter<RequestLoadGameEvent>,
+    mut delete_events: EventWriter<DeleteSaveSlotEvent>,
+    mut click_events: EventWriter<MenuClickEvent>,
+) {
+    for (interaction, button, mut background) in &mut interactions {
+        match *interaction {
+            Interaction::Pressed => {
+                click_events.write(MenuClickEvent);
+                match button.action {
+                    SaveSlotAction::Save(slot) => {
+                        save_events.write(RequestSaveGameEvent { slot });
+                    }
+                    SaveSlotAction::Load(slot) => {
+                        let from_main_menu = matches!(
+                            session.save_slot_picker.map(|picker| picker.mode),
+                            Some(SaveSlotPickerMode::Load {
+                                from_main_menu: true
+                            })
+                        );
+                        load_events.write(RequestLoadGameEvent {
+                            slot,
+                            from_main_menu,
+                        });
+                    }
+                    SaveSlotAction::Delete(slot) => {
+                        delete_events.write(DeleteSaveSlotEvent { slot });
+                    }
+                    SaveSlotAction::Back => {
+                        close_save_slot_picker(&mut commands, session.as_mut());
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                background.0 = Color::srgba(0.35, 0.28, 0.25, 0.9);
+            }
+            Interaction::None => {
+                background.0 = Color::srgba(0.22, 0.18, 0.15, 0.85);
+            }
+        }
+    }
+}
+
+pub fn process_delete_save_requests(
+    mut events: EventReader<DeleteSaveSlotEvent>,
+    mut changed_events: EventWriter<SaveSlotsChangedEvent>,
+    storage: Res<SaveStorage>,
+) {
+    for event in events.read() {
+        match storage.delete_slot(event.slot) {
+            Ok(()) => {
+                dev_info!("Deleted save slot {}", event.slot + 1);
+                changed_events.write(SaveSlotsChangedEvent);
+            }
+            Err(error) => {
+                error!("Failed to delete save slot {}: {error}", event.slot + 1);
+            }
+        }
+    }
+}
+
+pub fn close_save_slot_picker(commands: &mut Commands, session: &mut GameSession) {
+    if let Some(picker) = session.save_slot_picker.take() {
+        commands.entity(picker.root).despawn();
+    }
+}
+
+fn build_save_slot_picker(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    storage: &SaveStorage,
+    mode: SaveSlotPickerMode,
+    message: Option<&str>,
+) -> Entity {
+    let font = asset_server.load(MENU_FONT_PATH);
+    let title = match mode {
+        SaveSlotPickerMode::Save => "Save Game",
+        SaveSlotPickerMode::Load { .. } => "Load Game",
+    };
+
+    commands
+        .spawn((
+            SaveSlotPickerRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            },
+            BackgroundColor(Color::srgba(
+                MENU_OVERLAY_COLOR[0],
+                MENU_OVERLAY_COLOR[1],
+                MENU_OVERLAY_COLOR[2],
+                MENU_OVERLAY_COLOR[3],
+            )),
+            GlobalZIndex(130),
+            Name::new("SaveSlotPickerRoot"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(SAVE_SLOT_PANEL_WIDTH),
+                        padding: UiRect::all(Val::Px(24.0)),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Stretch,
+                        justify_content: JustifyContent::Center,
+                        row_gap: Val::Px(14.0),
+                        ..Default::default()
+                    },
+                    Name::new("SaveSlotPanel"),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new(title),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: MENU_TITLE_FONT_SIZE * 0.8,
+                            ..Default::default()
+                        },
+                        TextColor(Color::srgb(0.95, 0.93, 0.9)),
+                        Name::new("SaveSlotTitle"),
+                    ));
+
+                    if let Some(message) = message {
+                        panel.spawn((
+                            Text::new(message),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: SAVE_SLOT_INFO_FONT_SIZE,
+                                ..Default::default()
+                            },
+                            TextColor(Color::srgb(0.95, 0.45, 0.4)),
+                            Name::new("SaveSlotMessage"),
+                        ));
+                    }
+
+                    for slot in 0..SAVE_SLOT_COUNT {
+                        let state = storage.read_slot(slot);
+                        spawn_slot_row(panel, &font, slot, &state, mode);
+                    }
+
+                    spawn_slot_button(panel, &font, SaveSlotAction::Back, "Back");
+                });
+        })
+        .id()
+}
+
+fn spawn_slot_row(
+    panel: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    slot: usize,
+    state: &SaveSlotState,
+    mode: SaveSlotPickerMode,
+) {
+    let occupied = matches!(state, SaveSlotState::Occupied(_));
+
+    panel
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..Default::default()
+            },
+            BorderColor(Color::srgba(0.65, 0.6, 0.5, 0.4)),
+            Name::new(format!("SaveSlotRow_{}", slot + 1)),
+        ))
+        .with_children(|row| {
+            row.spawn((
+                Text::new(slot_summary(slot, state)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: SAVE_SLOT_INFO_FONT_SIZE,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.95, 0.93, 0.9)),
+                Node {
+                    flex_grow: 1.0,
+                    ..Default::default()
+                },
+            ));
+
+            match mode {
+                SaveSlotPickerMode::Save => {
+                    let label = if occupied { "Overwrite" } else { "Save" };
+                    spawn_slot_button(row, font, SaveSlotAction::Save(slot), label);
+                }
+                SaveSlotPickerMode::Load { .. } if occupied => {
+                    spawn_slot_button(row, font, SaveSlotAction::Load(slot), "Load");
+                }
+                SaveSlotPickerMode::Load { .. } => {}
+            }
+
+            if occupied {
+                spawn_slot_button(row, font, SaveSlotAction::Delete(slot), "Delete");
+            }
+        });
+}
+
+fn spawn_slot_button(
+    parent: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    action: SaveSlotAction,
+    label: &str,
+) {
+    parent
+        .spawn((
+            Button,
+            SaveSlotButton { action },
+            Node {
+                width: Val::Px(SAVE_SLOT_BUTTON_WIDTH),
+                height: Val::Px(MENU_BUTTON_HEIGHT),
+                padding: UiRect::all(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..Default::default()
+            },
+            BorderColor(Color::srgba(0.65, 0.6, 0.5, 0.6)),
+            BackgroundColor(Color::srgba(0.22, 0.18, 0.15, 0.85)),
+            Name::new(format!("SaveSlotButton_{:?}", action)),
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                TextFont {
+                    font: font.clone(),
+                    font_size: MENU_BUTTON_FONT_SIZE * 0.8,
+                    ..Default::default()
+                },
+                TextColor(Color::srgb(0.95, 0.93, 0.9)),
+            ));
+        });
+}
+
+fn slot_summary(slot: usize, state: &SaveSlotState) -> String {
+    match state {
+        SaveSlotState::Empty => format!("Slot {} - Empty", slot + 1),
+        SaveSlotState::Unreadable(SaveLoadError::NewerVersion { found, .. }) => format!(
+            "Slot {} - Saved by a newer version of the game (v{found})\nUpdate the game to load it",
+            slot + 1
+        ),
+        SaveSlotState::Unreadable(_) => format!("Slot {} - Corrupted save", slot + 1),
+        SaveSlotState::Occupied(data) => format!(
+            "Slot {} - {}  Lv {}  {}\n{}  {}",
+            slot + 1,
+            data.display_level_name(),
+            data.player_level,
+            format_playtime(data.playtime_seconds),
+            format_timestamp(data.saved_at_unix_seconds),
+            gear_summary(data),
+        ),
+    }
+}
+
+fn gear_summary(data: &GameSaveData) -> String {
+    let weapon = data
+        .equipped_weapon
+        .zip(data.equipped_weapon_family)
+        .map(|(kind, family)| kind.display_name(family))
+        .unwrap_or_else(|| "No weapon".to_string());
+    let shield = data
+        .equipped_shield
+        .map(|shield| shield.display_name())
+        .unwrap_or("No shield");
+    format!("{weapon} / {shield}")
+}
+
+fn format_playtime(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        (total / 60) % 60,
+        total % 60
+    )
+}
+
+/// 以 UTC 顯示存檔時間，避免為了日期格式額外引入套件
+fn format_timestamp(unix_seconds: u64) -> String {
+    if unix_seconds == 0 {
+        return "Unknown date".to_string();
+    }
+
+    let days = (unix_seconds / 86_400) as i64;
+    let seconds_of_day = unix_seconds % 86_400;
+
+    // Howard Hinnant 的 civil_from_days 演算法
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        seconds_of_day / 3600,
+        (seconds_of_day / 60) % 60
+    )
+}
@@ -31,10 +31,15 @@ pub fn spawn_player(
             Attack::new(base_attack),
             Defense::new(base_defense),
             Stamina::new(PLAYER_MAX_STAMINA, PLAYER_STAMINA_REGEN_PER_SECOND),
+            StatusEffects::default(),
             Velocity::zero(),
-            PlayerFacing::new(),
-            InputVector(Vec2::ZERO),
-            EquippedWeapon::new(WeaponKind::Level1),
+            (
+                PlayerFacing::new(),
+                InputVector(Vec2::ZERO),
+                MeleeCombo::new(),
+                BoxCollider::square(PLAYER_COLLIDER_HALF_SIZE),
+            ),
+            EquippedWeapon::new(WeaponKind::Level1, WeaponFamily::Sword),
             progression,
         ))
         .id();
@@ -62,8 +67,13 @@ pub fn spawn_player(
                 timer: Timer::from_seconds(0.5, TimerMode::Once),
                 from_angle: 0.0,
                 to_angle: 0.0,
+                style: WeaponFamily::Sword.swing_style(),
+                reach: PLAYER_ATTACK_RADIUS,
             },
-            Name::new(format!("Equipped{}", WeaponKind::Level1.display_name())),
+            Name::new(format!(
+                "Equipped{}",
+                WeaponKind::Level1.display_name(WeaponFamily::Sword)
+            )),
         ))
         .id();
 
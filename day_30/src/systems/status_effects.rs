@@ -0,0 +1,126 @@
+use crate::components::*;
+use crate::systems::damage::DamageRequest;
+use bevy::prelude::*;
+
+/// 對目標附加狀態異常的請求，是否生效交給 `apply_status_effect_requests` 判斷
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StatusEffectRequest {
+    pub target: Entity,
+    pub spec: StatusEffectSpec,
+    /// 攻擊打來的方向，用來判斷是否被盾牌擋下；零代表無法格擋
+    pub incoming: Vec2,
+}
+
+/// 命中目標時，把攻擊者或飛行物帶有的狀態全部送出
+pub fn inflict_status_effects(
+    target: Entity,
+    incoming: Vec2,
+    effects: Option<&OnHitStatusEffects>,
+    requests: &mut EventWriter<StatusEffectRequest>,
+) {
+    let Some(effects) = effects else {
+        return;
+    };
+
+    for spec in &effects.0 {
+        requests.write(StatusEffectRequest {
+            target,
+            spec: *spec,
+            incoming,
+        });
+    }
+}
+
+/// 套用狀態請求；免疫、無敵時間中、已死亡或格擋反擊成功的目標不受影響，
+/// 正面擋下時持續時間依盾牌減傷比例縮短
+pub fn apply_status_effect_requests(
+    mut commands: Commands,
+    mut requests: EventReader<StatusEffectRequest>,
+    mut target_query: Query<(
+        &mut StatusEffects,
+        &Health,
+        Option<&StatusImmunities>,
+        Has<Invulnerable>,
+        Has<PlayerDead>,
+        Option<(&Blocking, &EquippedShield, &PlayerFacing)>,
+    )>,
+) {
+    for request in requests.read() {
+        let Ok((mut effects, health, immunities, invulnerable, dead, guard)) =
+            target_query.get_mut(request.target)
+        else {
+            continue;
+        };
+
+        if dead || invulnerable || health.current <= 0 {
+            continue;
+        }
+
+        if immunities.is_some_and(|immunities| immunities.contains(request.spec.kind)) {
+            dev_info!("Target is immune to {:?}", request.spec.kind);
+            continue;
+        }
+
+        let mut spec = request.spec;
+        if let Some((blocking, shield, facing)) = guard {
+            match blocking.outcome(shield.kind, facing.direction, request.incoming) {
+                BlockOutcome::Parried => continue,
+                BlockOutcome::Blocked => {
+                    spec.duration_seconds *= 1.0 - shield.kind.block_reduction();
+                }
+                BlockOutcome::Unblocked => {}
+            }
+        }
+
+        effects.apply(spec);
+
+        // 冰凍立即生效，不必等到下一個影格
+        if spec.kind == StatusEffectKind::Freeze {
+            commands
+                .entity(request.target)
+                .insert(HitStun::new(spec.duration_seconds));
+        }
+    }
+}
+
+/// 推進狀態時間：結算持續傷害、冰凍時維持硬直，並同步虛弱造成的攻擊力倍率
+pub fn tick_status_effects_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            Entity,
+            &mut StatusEffects,
+            Option<&mut Attack>,
+            Option<&HitStun>,
+        ),
+        Without<PlayerDead>,
+    >,
+    mut damage_requests: EventWriter<DamageRequest>,
+) {
+    for (entity, mut effects, attack, hit_stun) in &mut query {
+        for (kind, damage) in effects.tick(time.delta()) {
+            let Some(damage_type) = kind.damage_type() else {
+                continue;
+            };
+            damage_requests.write(DamageRequest::new(entity, damage, damage_type));
+        }
+
+        // 冰凍沿用受擊硬直，AI 與玩家操作都會暫停
+        if let Some(frozen_seconds) = effects.frozen_seconds() {
+            let stun_remaining = hit_stun
+                .map(|stun| stun.timer.remaining_secs())
+                .unwrap_or(0.0);
+            if stun_remaining < frozen_seconds {
+                commands.entity(entity).insert(HitStun::new(frozen_seconds));
+            }
+        }
+
+        if let Some(mut attack) = attack {
+            let multiplier = effects.attack_multiplier();
+            if attack.multiplier != multiplier {
+                attack.multiplier = multiplier;
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::components::world::{Door, EnvironmentProp};
+use crate::resources::TileMap;
+use bevy::prelude::*;
+
+/// 門開關（互動或讀檔）與擺設生成時更新瓷磚索引；整張索引在換關時由 `finalize_level_load` 重建
+pub fn sync_tile_map_system(
+    mut tile_map: ResMut<TileMap>,
+    door_query: Query<(Entity, &Door, &Transform), Changed<Door>>,
+    prop_query: Query<(&EnvironmentProp, &Transform), Changed<EnvironmentProp>>,
+) {
+    for (entity, door, transform) in &door_query {
+        tile_map.set_door_open(entity, transform.translation.truncate(), door.is_open);
+    }
+
+    for (prop, transform) in &prop_query {
+        tile_map.set_prop_blocking(transform.translation.truncate(), prop.blocks_movement);
+    }
+}
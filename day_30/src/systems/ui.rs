@@ -70,12 +70,15 @@ pub fn setup_player_health_ui(mut commands: Commands, asset_server: Res<AssetSer
         });
 
     commands
-        .spawn((Name::new("PlayerStatusTextRoot"), Node {
-            position_type: PositionType::Absolute,
-            top: Val::Px(PLAYER_STATUS_TEXT_TOP_OFFSET),
-            left: Val::Px(PLAYER_HEALTH_BAR_MARGIN),
-            ..Default::default()
-        }))
+        .spawn((
+            Name::new("PlayerStatusTextRoot"),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(PLAYER_STATUS_TEXT_TOP_OFFSET),
+                left: Val::Px(PLAYER_HEALTH_BAR_MARGIN),
+                ..Default::default()
+            },
+        ))
         .with_children(|parent| {
             parent.spawn((
                 PlayerStatusText,
@@ -127,22 +130,29 @@ pub fn update_player_stamina_ui(
 }
 
 pub fn update_player_status_text(
-    player_query: Query<Option<&Poisoned>, (With<Player>, Without<PlayerDead>)>,
+    player_query: Query<Option<&StatusEffects>, (With<Player>, Without<PlayerDead>)>,
     mut text_query: Query<(&mut Text, &mut TextColor), With<PlayerStatusText>>,
 ) {
     let Some((mut text, mut color)) = text_query.iter_mut().next() else {
         return;
     };
 
-    let Some(poison_state) = player_query.iter().next() else {
+    let Some(status_effects) = player_query.iter().next() else {
         *text = Text::new("");
         color.0 = Color::srgb(0.78, 0.78, 0.72);
         return;
     };
 
-    match poison_state {
-        Some(_) => {
-            *text = Text::new("Space: Attack / Interact | Esc: Pause\nPoisoned: HP -3 per tick");
+    match status_effects.filter(|effects| !effects.is_empty()) {
+        Some(effects) => {
+            let names: Vec<&str> = effects
+                .iter()
+                .map(|effect| effect.kind.display_name())
+                .collect();
+            *text = Text::new(format!(
+                "Space: Attack / Interact | Esc: Pause\n{}",
+                names.join(" / ")
+            ));
             color.0 = Color::srgb(0.95, 0.38, 0.32);
         }
         None => {
@@ -0,0 +1,474 @@
+use crate::components::level::{EncounterEnemy, LevelEntity};
+use crate::components::*;
+use crate::constants::{CHEST_SCALE, ITEM_PICKUP_Z_OFFSET};
+use crate::resources::{
+    ChestSnapshot, DoorSnapshot, Encounter, EncounterStage, EnemyArchetypeRegistry, EnemySnapshot,
+    LevelBuildContext, LevelExitAssets, LevelSnapshot, LevelState, PendingLevelRewards,
+    PendingWorldRestore, PickupSnapshot, PlayerSnapshot, RewardsSnapshot, RoomAssets,
+    StatusEffectSnapshot,
+};
+use crate::systems::chest::attach_chest_item_reveal;
+use crate::systems::enemy::{
+    EnemyPlacement, EnemySpawnStats, insert_enemy_archetype, spawn_enemy_from_archetype,
+};
+use crate::systems::items::spawn_pickup_entity;
+use crate::systems::level::{LevelLoadedEvent, spawn_level_exit_portal};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// 門的位置在重建前後可能有些微浮點誤差
+const DOOR_MATCH_DISTANCE: f32 = 1.0;
+
+type EnemyStateData = (
+    &'static Transform,
+    &'static EnemyArchetypeId,
+    &'static Health,
+    &'static Attack,
+    &'static Defense,
+    &'static EnemyAIState,
+    &'static EnemyPatrol,
+    Option<&'static Name>,
+    Option<&'static EnemyPatrolRoute>,
+    Option<&'static StatusEffects>,
+);
+
+/// 存檔時擷取關卡即時狀態所需的查詢
+#[derive(SystemParam)]
+pub struct WorldSnapshotSource<'w, 's> {
+    build_context: Res<'w, LevelBuildContext>,
+    rewards: Res<'w, PendingLevelRewards>,
+    encounter: Res<'w, Encounter>,
+    player: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            Option<&'static Stamina>,
+            Option<&'static StatusEffects>,
+        ),
+        (With<Player>, Without<PlayerDead>),
+    >,
+    /// 頭目召喚的手下與遭遇戰的敵人不存檔，讀檔後由頭目或遭遇戰重新生成
+    enemies: Query<
+        'w,
+        's,
+        EnemyStateData,
+        (
+            With<Enemy>,
+            Without<Chest>,
+            Without<EnemyDeathEffect>,
+            Without<BossMinion>,
+            Without<EncounterEnemy>,
+        ),
+    >,
+    chests: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static Chest,
+            Option<&'static Name>,
+            Option<EnemyStateData>,
+            Has<EnemyDeathEffect>,
+        ),
+    >,
+    pickups: Query<'w, 's, (&'static Transform, &'static Pickup)>,
+    doors: Query<'w, 's, (&'static Transform, &'static Door), With<LevelEntity>>,
+}
+
+impl WorldSnapshotSource<'_, '_> {
+    /// 關卡尚未生成完成或玩家不在場上時回傳 None，讀檔時會改為重建整個關卡
+    pub fn capture(&self, level_index: usize) -> Option<LevelSnapshot> {
+        if self.build_context.pending_layout.is_some()
+            || self.build_context.pending_finalize.is_some()
+        {
+            return None;
+        }
+
+        let (transform, stamina, status_effects) = self.player.iter().next()?;
+        let player = PlayerSnapshot {
+            position: transform.translation.to_array(),
+            stamina: stamina.map(|stamina| stamina.current),
+            status_effects: status_effects
+                .map(|effects| effects.iter().map(status_effect_snapshot).collect())
+                .unwrap_or_default(),
+        };
+
+        let enemies = self.enemies.iter().filter_map(enemy_snapshot).collect();
+
+        let chests = self
+            .chests
+            .iter()
+            .map(|(transform, chest, name, mimic, dying)| ChestSnapshot {
+                name: name.map(|name| name.as_str().to_string()),
+                position: transform.translation.to_array(),
+                contents: chest.contents.clone(),
+                state: chest.state,
+                mimic: mimic.filter(|_| !dying).and_then(enemy_snapshot),
+            })
+            // 已被擊倒的寶箱怪不會留下寶箱
+            .filter(|chest| chest.state != ChestState::MimicAwakened || chest.mimic.is_some())
+            .collect();
+
+        let pickups = self
+            .pickups
+            .iter()
+            .map(|(transform, pickup)| PickupSnapshot {
+                position: [
+                    transform.translation.x,
+                    transform.translation.y - ITEM_PICKUP_Z_OFFSET,
+                    transform.translation.z,
+                ],
+                effect: pickup.effect.clone(),
+            })
+            .collect();
+
+        let doors = self
+            .doors
+            .iter()
+            .map(|(transform, door)| DoorSnapshot {
+                position: transform.translation.to_array(),
+                is_open: door.is_open,
+            })
+            .collect();
+
+        Some(LevelSnapshot {
+            level_index,
+            player,
+            enemies,
+            chests,
+            pickups,
+            doors,
+            rewards: RewardsSnapshot {
+                portal_anchor: self.rewards.portal_anchor.map(|anchor| anchor.to_array()),
+                target_level: self.rewards.target_level,
+                rewards_spawned: self.rewards.rewards_spawned,
+                rewards_available: self.rewards.rewards_available,
+            },
+            encounter_cleared: self.encounter.stage() == EncounterStage::Cleared,
+        })
+    }
+}
+
+fn status_effect_snapshot(effect: &ActiveStatusEffect) -> StatusEffectSnapshot {
+    StatusEffectSnapshot {
+        kind: effect.kind,
+        magnitude: effect.magnitude,
+        stacks: effect.stacks,
+        remaining_seconds: effect.remaining_seconds(),
+        tick_elapsed_seconds: effect
+            .tick
+            .as_ref()
+            .map(|tick| tick.elapsed_secs())
+            .unwrap_or(0.0),
+    }
+}
+
+fn restore_status_effect(saved: &StatusEffectSnapshot) -> ActiveStatusEffect {
+    let mut effect = ActiveStatusEffect::new(StatusEffectSpec::new(
+        saved.kind,
+        saved.remaining_seconds,
+        saved.magnitude,
+    ));
+    effect.stacks = saved.stacks.max(1);
+    if let Some(tick) = effect.tick.as_mut() {
+        tick.set_elapsed(Duration::from_secs_f32(saved.tick_elapsed_seconds.max(0.0)));
+    }
+    effect
+}
+
+fn enemy_snapshot(
+    (transform, id, health, attack, defense, ai_state, patrol, name, route, status_effects): (
+        &Transform,
+        &EnemyArchetypeId,
+        &Health,
+        &Attack,
+        &Defense,
+        &EnemyAIState,
+        &EnemyPatrol,
+        Option<&Name>,
+        Option<&EnemyPatrolRoute>,
+        Option<&StatusEffects>,
+    ),
+) -> Option<EnemySnapshot> {
+    if health.current <= 0 {
+        return None;
+    }
+
+    Some(EnemySnapshot {
+        archetype_id: id.0.clone(),
+        name: name.map(|name| name.as_str().to_string()),
+        position: transform.translation.to_array(),
+        health: health.current,
+        max_health: health.max,
+        attack: attack.base,
+        defense: defense.base,
+        ai_state: ai_state.state,
+        last_known_position: ai_state.last_known_position.map(|point| point.to_array()),
+        patrol_origin: patrol.origin.to_array(),
+        patrol_range: patrol.range,
+        patrol_direction: patrol.direction,
+        patrol_route: route
+            .map(|route| {
+                route
+                    .waypoints
+                    .iter()
+                    .map(|point| point.to_array())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        status_effects: status_effects
+            .map(|effects| effects.iter().map(status_effect_snapshot).collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// 關卡重建完成後，把讀檔時保留的快照套用到場上
+pub fn restore_world_snapshot(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    room_assets: Res<RoomAssets>,
+    level_exit_assets: Option<Res<LevelExitAssets>>,
+    level_state: Res<LevelState>,
+    mut pending: ResMut<PendingWorldRestore>,
+    mut rewards: ResMut<PendingLevelRewards>,
+    mut encounter: ResMut<Encounter>,
+    mut loaded_events: EventReader<LevelLoadedEvent>,
+    stale_entities: Query<Entity, Or<(With<Enemy>, With<Chest>, With<Pickup>)>>,
+    mut doors: Query<(&Transform, &mut Door, &mut RoomTile, &mut Sprite), With<LevelEntity>>,
+    mut player: Query<
+        (Entity, &mut Transform, Option<&mut Stamina>),
+        (With<Player>, Without<LevelEntity>),
+    >,
+) {
+    let mut loaded_index = None;
+    for event in loaded_events.read() {
+        loaded_index = Some(event.index);
+    }
+
+    let Some(index) = loaded_index else {
+        return;
+    };
+
+    // 快照只對應存檔當時的那一關，換關後就丟棄
+    let Some(snapshot) = pending.0.take() else {
+        return;
+    };
+    if snapshot.level_index != index {
+        warn!(
+            "World snapshot was taken on level {} but level {} loaded; ignoring it",
+            snapshot.level_index + 1,
+            index + 1
+        );
+        return;
+    }
+
+    for entity in &stale_entities {
+        commands.entity(entity).despawn();
+    }
+
+    for enemy in &snapshot.enemies {
+        let Some(archetype) = archetypes.get(&enemy.archetype_id) else {
+            warn!(
+                "Saved enemy uses unknown archetype '{}'; skipping",
+                enemy.archetype_id
+            );
+            continue;
+        };
+
+        let entity = spawn_enemy_from_archetype(
+            &mut commands,
+            &asset_server,
+            archetype,
+            enemy_placement(enemy),
+            enemy_stats(enemy),
+        );
+        apply_enemy_state(&mut commands.entity(entity), enemy, archetype.name_tag());
+        commands.entity(entity).insert(LevelEntity);
+    }
+
+    for chest_snapshot in &snapshot.chests {
+        restore_chest(&mut commands, &asset_server, &archetypes, chest_snapshot);
+    }
+
+    for pickup in &snapshot.pickups {
+        spawn_pickup_entity(
+            &mut commands,
+            &asset_server,
+            pickup.effect.clone(),
+            Vec3::from_array(pickup.position),
+        );
+    }
+
+    for (transform, mut door, mut tile, mut sprite) in &mut doors {
+        let Some(saved) = snapshot.doors.iter().find(|saved| {
+            Vec3::from_array(saved.position).distance(transform.translation) < DOOR_MATCH_DISTANCE
+        }) else {
+            continue;
+        };
+
+        door.is_open = saved.is_open;
+        if saved.is_open {
+            tile.tile_type = RoomTileType::DoorOpen;
+            sprite.image = room_assets.door_open.clone();
+        } else {
+            tile.tile_type = RoomTileType::DoorClosed;
+            sprite.image = room_assets.door_closed.clone();
+        }
+    }
+
+    if let Ok((entity, mut transform, stamina)) = player.single_mut() {
+        transform.translation = Vec3::from_array(snapshot.player.position);
+
+        if let (Some(mut stamina), Some(current)) = (stamina, snapshot.player.stamina) {
+            stamina.current = current.clamp(0.0, stamina.max);
+        }
+
+        let mut status_effects = StatusEffects::default();
+        for saved in &snapshot.player.status_effects {
+            status_effects.restore(restore_status_effect(saved));
+        }
+        commands.entity(entity).insert(status_effects);
+    }
+
+    if snapshot.encounter_cleared {
+        encounter.clear();
+    }
+
+    rewards.portal_anchor = snapshot.rewards.portal_anchor.map(Vec3::from_array);
+    rewards.target_level = snapshot.rewards.target_level;
+    rewards.rewards_spawned = snapshot.rewards.rewards_spawned;
+    rewards.rewards_available = snapshot.rewards.rewards_available;
+
+    // 頭目已被擊倒：補回出口傳送門，獎勵寶箱則已包含在寶箱快照中
+    if let (true, Some(anchor), Some(target), Some(assets)) = (
+        rewards.rewards_spawned,
+        rewards.portal_anchor,
+        rewards.target_level,
+        level_exit_assets.as_ref(),
+    ) {
+        spawn_level_exit_portal(
+            &mut commands,
+            assets.as_ref(),
+            anchor,
+            rewards.tile_size,
+            target,
+        );
+    }
+
+    dev_info!(
+        "Restored level {} snapshot ({} enemies, {} chests, {} pickups)",
+        level_state
+            .get_definition(index)
+            .map(|definition| definition.name.as_str())
+            .unwrap_or("?"),
+        snapshot.enemies.len(),
+        snapshot.chests.len(),
+        snapshot.pickups.len()
+    );
+}
+
+fn restore_chest(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    archetypes: &EnemyArchetypeRegistry,
+    snapshot: &ChestSnapshot,
+) {
+    let sprite_path = match snapshot.state {
+        ChestState::Closed => "items/chests/chest_closed.png",
+        ChestState::RevealingItem => "items/chests/chest_open.png",
+        ChestState::Empty => "items/chests/chest_empty.png",
+        ChestState::MimicAwakened => "items/chests/chest_closed.png",
+    };
+
+    let entity = commands
+        .spawn((
+            LevelEntity,
+            Chest {
+                contents: snapshot.contents.clone(),
+                state: snapshot.state,
+            },
+            Sprite::from_image(asset_server.load(sprite_path)),
+            Transform::from_translation(Vec3::from_array(snapshot.position))
+                .with_scale(Vec3::splat(CHEST_SCALE)),
+            Name::new(snapshot.name.clone().unwrap_or_else(|| "Chest".to_string())),
+        ))
+        .id();
+
+    match (snapshot.state, &snapshot.contents, &snapshot.mimic) {
+        (ChestState::RevealingItem, ChestContents::Item(effect), _) => {
+            attach_chest_item_reveal(commands, asset_server, entity, effect.clone());
+        }
+        (ChestState::MimicAwakened, _, Some(mimic)) => {
+            let Some(archetype) = archetypes.get(&mimic.archetype_id) else {
+                warn!(
+                    "Saved mimic uses unknown archetype '{}'; leaving an empty chest",
+                    mimic.archetype_id
+                );
+                return;
+            };
+
+            let mut chest = commands.entity(entity);
+            chest.insert(Sprite::from_image(
+                asset_server.load(archetype.sprite.clone()),
+            ));
+            insert_enemy_archetype(
+                &mut chest,
+                archetype,
+                enemy_placement(mimic),
+                enemy_stats(mimic),
+            );
+            apply_enemy_state(&mut chest, mimic, archetype.name_tag());
+        }
+        _ => {}
+    }
+}
+
+fn enemy_placement(snapshot: &EnemySnapshot) -> EnemyPlacement {
+    EnemyPlacement {
+        position: Vec3::from_array(snapshot.position),
+        patrol_origin: Vec3::from_array(snapshot.patrol_origin),
+        patrol_range: snapshot.patrol_range,
+        direction: snapshot.patrol_direction,
+    }
+}
+
+fn enemy_stats(snapshot: &EnemySnapshot) -> EnemySpawnStats {
+    EnemySpawnStats {
+        health: snapshot.max_health,
+        attack: snapshot.attack,
+        defense: snapshot.defense,
+    }
+}
+
+fn apply_enemy_state(entity: &mut EntityCommands, snapshot: &EnemySnapshot, name_tag: String) {
+    let mut status_effects = StatusEffects::default();
+    for saved in &snapshot.status_effects {
+        status_effects.restore(restore_status_effect(saved));
+    }
+
+    entity.insert((
+        Health {
+            current: snapshot.health.clamp(1, snapshot.max_health.max(1)),
+            max: snapshot.max_health,
+        },
+        EnemyAIState {
+            last_known_position: snapshot.last_known_position.map(Vec2::from_array),
+            ..EnemyAIState::new(snapshot.ai_state)
+        },
+        Name::new(snapshot.name.clone().unwrap_or(name_tag)),
+        status_effects,
+    ));
+
+    if !snapshot.patrol_route.is_empty() {
+        entity.insert(EnemyPatrolRoute::new(
+            snapshot
+                .patrol_route
+                .iter()
+                .map(|point| Vec2::from_array(*point))
+                .collect(),
+        ));
+    }
+}
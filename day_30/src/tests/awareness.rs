@@ -0,0 +1,131 @@
+use super::harness::{FRAME_SECONDS, TestApp};
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::TileMap;
+use bevy::prelude::*;
+
+const TILE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;
+
+/// 把玩家上方 `rows_above` 格那一列的地板改成橫牆，並照關卡載入的方式重建瓷磚索引
+fn build_wall(game: &mut TestApp, rows_above: f32) {
+    let y = game.player_position().y + TILE * rows_above;
+    let mut tiles = game.world_mut().query::<(&mut RoomTile, &Transform)>();
+    for (mut tile, transform) in tiles.iter_mut(game.world_mut()) {
+        if (transform.translation.y - y).abs() < 1.0 && transform.translation.x.abs() <= TILE * 5.0
+        {
+            tile.tile_type = RoomTileType::WallSInnerMid;
+        }
+    }
+
+    let tiles: Vec<_> = game
+        .world_mut()
+        .query::<(Entity, &RoomTile, &Transform)>()
+        .iter(game.world())
+        .map(|(entity, tile, transform)| (entity, transform.translation.truncate(), tile.tile_type))
+        .collect();
+    game.world_mut()
+        .insert_resource(TileMap::from_tiles(TILE, tiles));
+}
+
+fn ai_state(game: &TestApp, enemy: Entity) -> EnemyBehaviorState {
+    game.world().get::<EnemyAIState>(enemy).unwrap().state
+}
+
+#[test]
+fn chasers_do_not_notice_the_player_through_walls() {
+    let mut game = TestApp::training_hall();
+    build_wall(&mut game, 2.0);
+    let player = game.player_position();
+    let hidden = game.spawn_enemy("slime", player + Vec3::new(0.0, TILE * 3.0, 0.0));
+    let seen = game.spawn_enemy("slime", player + Vec3::new(TILE * 3.0, 0.0, 0.0));
+
+    game.step_frames(30);
+    assert_eq!(ai_state(&game, hidden), EnemyBehaviorState::Patrolling);
+    assert_eq!(ai_state(&game, seen), EnemyBehaviorState::Chasing);
+}
+
+#[test]
+fn losing_sight_sends_chasers_to_investigate_the_last_known_position() {
+    let mut game = TestApp::training_hall();
+    build_wall(&mut game, 2.0);
+    let player = game.player_position();
+    let slime = game.spawn_enemy("slime", player + Vec3::new(0.0, TILE, 0.0));
+
+    assert!(game.step_until(|world| {
+        world.get::<EnemyAIState>(slime).unwrap().state == EnemyBehaviorState::Chasing
+    }));
+
+    // 玩家繞到牆後：敵人記得最後看到的位置
+    let last_seen = game.player_position().truncate();
+    let player_entity = game.player();
+    game.world_mut()
+        .get_mut::<Transform>(player_entity)
+        .unwrap()
+        .translation = player + Vec3::new(0.0, TILE * 4.0, 0.0);
+    game.step();
+
+    let state = game.world().get::<EnemyAIState>(slime).unwrap();
+    assert_eq!(state.state, EnemyBehaviorState::Investigating);
+    assert!(state.last_known_position.unwrap().distance(last_seen) < 1.0);
+
+    // 搜尋時間結束後回去巡邏
+    game.step_frames((ENEMY_INVESTIGATE_SECONDS as f64 / FRAME_SECONDS) as usize + 10);
+    let state = game.world().get::<EnemyAIState>(slime).unwrap();
+    assert_eq!(state.state, EnemyBehaviorState::Patrolling);
+    assert!(state.last_known_position.is_none());
+}
+
+#[test]
+fn spiders_hold_fire_without_line_of_sight() {
+    let mut game = TestApp::training_hall();
+    build_wall(&mut game, 2.0);
+    let player = game.player_position();
+    game.spawn_enemy("spider", player + Vec3::new(0.0, TILE * 4.0, 0.0));
+
+    let fired = game.step_until(|world| {
+        world
+            .query::<&SpiderWebProjectile>()
+            .iter(world)
+            .next()
+            .is_some()
+    });
+    assert!(!fired, "spider fired through the wall");
+}
+
+#[test]
+fn chargers_and_casters_investigate_after_losing_sight() {
+    for archetype in ["cyclops", "wizard_boss"] {
+        let mut game = TestApp::training_hall();
+        build_wall(&mut game, 2.0);
+        let player = game.player_position();
+        let enemy = game.spawn_enemy(archetype, player + Vec3::new(TILE, TILE, 0.0));
+        // 讓獨眼巨人先追擊而不是直接衝撞
+        if let Some(mut charge) = game.world_mut().get_mut::<CyclopsCharge>(enemy) {
+            charge.ready = false;
+        }
+
+        assert!(game.step_until(|world| {
+            world.get::<EnemyAIState>(enemy).unwrap().state == EnemyBehaviorState::Chasing
+        }));
+
+        let player_entity = game.player();
+        game.world_mut()
+            .get_mut::<Transform>(player_entity)
+            .unwrap()
+            .translation = player + Vec3::new(0.0, TILE * 4.0, 0.0);
+        game.step();
+
+        let state = game.world().get::<EnemyAIState>(enemy).unwrap();
+        assert_eq!(
+            state.state,
+            EnemyBehaviorState::Investigating,
+            "{archetype}"
+        );
+        assert!(state.last_known_position.is_some());
+
+        game.step_frames((ENEMY_INVESTIGATE_SECONDS as f64 / FRAME_SECONDS) as usize + 10);
+        let state = game.world().get::<EnemyAIState>(enemy).unwrap();
+        assert_eq!(state.state, EnemyBehaviorState::Patrolling, "{archetype}");
+        assert!(state.last_known_position.is_none());
+    }
+}
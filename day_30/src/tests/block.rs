@@ -0,0 +1,220 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::{DamageRequest, ShieldEquipEvent, mitigate_damage, projectile_bundle};
+use bevy::prelude::*;
+
+const BLOCK_KEY: KeyCode = KeyCode::KeyQ;
+
+fn equip_shield(game: &mut TestApp, kind: ShieldKind) {
+    game.send(ShieldEquipEvent { kind });
+    game.step();
+}
+
+/// 從玩家面向（右方）正面打來的攻擊
+fn frontal_hit(player: Entity, amount: i32) -> DamageRequest {
+    DamageRequest::new(player, amount, DamageType::Physical)
+        .with_knockback(Vec2::NEG_X * ENEMY_CONTACT_KNOCKBACK)
+}
+
+/// 按住格擋鍵直到超過格擋反擊的時間
+fn raise_shield_past_parry_window(game: &mut TestApp, kind: ShieldKind) {
+    game.set_key(BLOCK_KEY, true);
+    let player = game.player();
+    assert!(game.step_until(|world| {
+        world
+            .get::<Blocking>(player)
+            .is_some_and(|blocking| blocking.elapsed_seconds > kind.parry_window_seconds())
+    }));
+}
+
+/// 在玩家正前方放一隻接觸攻擊會下毒的史萊姆，並讓牠立刻出手
+fn poisoning_slime_attacks(game: &mut TestApp) {
+    let player_position = game.player_position();
+    let slime = game.spawn_enemy("slime", player_position);
+    let mut slime = game.world_mut().entity_mut(slime);
+    let mut attack = slime.get_mut::<EnemyAttack>().unwrap();
+    let reach = attack.radius * 0.5;
+    let cooldown = attack.cooldown.duration();
+    attack.cooldown.set_elapsed(cooldown);
+    slime.get_mut::<Transform>().unwrap().translation.x += reach;
+    slime.insert(OnHitStatusEffects(vec![StatusEffectSpec::new(
+        StatusEffectKind::Poison,
+        8.0,
+        3.0,
+    )]));
+    game.step_frames(2);
+}
+
+fn health_lost_to(game: &mut TestApp, request: DamageRequest) -> i32 {
+    let player = game.player();
+    let before = game.component::<Health>(player).current;
+    game.send(request);
+    game.step();
+    before - game.component::<Health>(player).current
+}
+
+#[test]
+fn blocking_reduces_frontal_damage_by_shield_tier() {
+    for kind in [ShieldKind::Level1, ShieldKind::Level2] {
+        let mut game = TestApp::training_hall();
+        equip_shield(&mut game, kind);
+        raise_shield_past_parry_window(&mut game, kind);
+
+        let player = game.player();
+        let defense = game.component::<Defense>(player).value();
+        let request = frontal_hit(player, 60);
+        let unblocked = mitigate_damage(&request, Some(defense), Resistances::default());
+        let expected = ((unblocked as f32 * (1.0 - kind.block_reduction())).round() as i32).max(1);
+
+        assert_eq!(health_lost_to(&mut game, request), expected, "{kind:?}");
+    }
+}
+
+#[test]
+fn hits_from_behind_are_not_blocked() {
+    let mut game = TestApp::training_hall();
+    equip_shield(&mut game, ShieldKind::Level2);
+    raise_shield_past_parry_window(&mut game, ShieldKind::Level2);
+
+    let player = game.player();
+    let defense = game.component::<Defense>(player).value();
+    let request = DamageRequest::new(player, 60, DamageType::Physical)
+        .with_knockback(Vec2::X * ENEMY_CONTACT_KNOCKBACK);
+    let unblocked = mitigate_damage(&request, Some(defense), Resistances::default());
+
+    assert_eq!(health_lost_to(&mut game, request), unblocked);
+}
+
+#[test]
+fn blocking_needs_a_shield_and_drains_stamina() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+
+    game.set_key(BLOCK_KEY, true);
+    game.step_frames(3);
+    assert!(game.world().get::<Blocking>(player).is_none());
+    game.set_key(BLOCK_KEY, false);
+    game.step();
+
+    equip_shield(&mut game, ShieldKind::Level1);
+    game.set_key(BLOCK_KEY, true);
+    game.step_frames(30);
+    assert!(game.world().get::<Blocking>(player).is_some());
+    assert!(game.player_component::<Stamina>().current < PLAYER_MAX_STAMINA);
+
+    game.set_key(BLOCK_KEY, false);
+    game.step();
+    assert!(game.world().get::<Blocking>(player).is_none());
+}
+
+#[test]
+fn blocking_slows_movement_and_keeps_facing() {
+    let mut game = TestApp::training_hall();
+    equip_shield(&mut game, ShieldKind::Level1);
+
+    let start = game.player_position();
+    game.set_key(KeyCode::KeyA, true);
+    game.step_frames(10);
+    let free_distance = start.x - game.player_position().x;
+    game.set_key(KeyCode::KeyA, false);
+    game.step();
+
+    // 先轉回面向右方再舉盾，往左退時盾牌仍朝右
+    game.tap_key(KeyCode::KeyD);
+    game.set_key(BLOCK_KEY, true);
+    game.step();
+    let start = game.player_position();
+    game.set_key(KeyCode::KeyA, true);
+    game.step_frames(10);
+    let blocking_distance = start.x - game.player_position().x;
+
+    assert!(blocking_distance > 0.0);
+    assert!(blocking_distance < free_distance * 0.6);
+    let player = game.player();
+    let facing = game.world().get::<PlayerFacing>(player).unwrap();
+    assert_eq!(facing.direction, Vec2::X);
+}
+
+#[test]
+fn parry_negates_the_hit_and_staggers_the_attacker() {
+    let mut game = TestApp::training_hall();
+    equip_shield(&mut game, ShieldKind::Level1);
+    let slime = game.spawn_enemy_in_reach("slime");
+
+    game.set_key(BLOCK_KEY, true);
+    game.step();
+
+    let player = game.player();
+    let lost = health_lost_to(&mut game, frontal_hit(player, 60).with_source(slime));
+
+    assert_eq!(lost, 0);
+    let stun = game.component::<HitStun>(slime);
+    assert!(stun.timer.remaining_secs() > ENEMY_HIT_STUN_SECONDS);
+}
+
+#[test]
+fn parry_reflects_wizard_projectiles() {
+    let mut game = TestApp::training_hall();
+    equip_shield(&mut game, ShieldKind::Level1);
+    let player = game.player();
+    let starting_health = game.component::<Health>(player).current;
+
+    game.set_key(BLOCK_KEY, true);
+    game.step();
+
+    let position =
+        game.player_position() + Vec3::new(WIZARD_BOSS_PROJECTILE_HIT_RADIUS * 0.5, 0.0, 0.0);
+    let projectile = game
+        .world_mut()
+        .spawn((
+            projectile_bundle(
+                position,
+                Vec2::NEG_X * WIZARD_BOSS_PROJECTILE_SPEED,
+                Vec2::splat(WIZARD_BOSS_PROJECTILE_SIZE),
+                WIZARD_BOSS_PROJECTILE_COLOR,
+                WIZARD_BOSS_PROJECTILE_LIFETIME,
+            ),
+            BossWizardProjectile { damage: 20 },
+        ))
+        .id();
+    game.step();
+
+    assert_eq!(game.component::<Health>(player).current, starting_health);
+    assert!(
+        game.world()
+            .get::<BossWizardProjectile>(projectile)
+            .is_none()
+    );
+    assert!(game.world().get::<PlayerProjectile>(projectile).is_some());
+    assert!(game.component::<Projectile>(projectile).velocity.x > 0.0);
+}
+
+#[test]
+fn parry_shrugs_off_on_hit_statuses() {
+    let mut game = TestApp::training_hall();
+    equip_shield(&mut game, ShieldKind::Level1);
+    game.set_key(BLOCK_KEY, true);
+    game.step();
+
+    poisoning_slime_attacks(&mut game);
+
+    let effects = game.player_component::<StatusEffects>();
+    assert!(effects.get(StatusEffectKind::Poison).is_none());
+}
+
+#[test]
+fn blocking_shortens_on_hit_statuses() {
+    let kind = ShieldKind::Level1;
+    let mut game = TestApp::training_hall();
+    equip_shield(&mut game, kind);
+    raise_shield_past_parry_window(&mut game, kind);
+
+    poisoning_slime_attacks(&mut game);
+
+    let effects = game.player_component::<StatusEffects>();
+    let poison = effects
+        .get(StatusEffectKind::Poison)
+        .expect("a blocked hit still poisons");
+    assert!(poison.remaining_seconds() <= 8.0 * (1.0 - kind.block_reduction()));
+}
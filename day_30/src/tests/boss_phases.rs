@@ -0,0 +1,198 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::{EnemyArchetypeRegistry, EnemyArchetypeSet};
+use crate::systems::projectile_bundle;
+use bevy::prelude::*;
+
+/// 在玩家右側生成法師，並換成只施放指定招式的單一階段腳本
+fn scripted_wizard(game: &mut TestApp, pattern: Vec<BossAttack>) -> Entity {
+    let position = game.player_position() + Vec3::new(160.0, 0.0, 0.0);
+    let boss = game.spawn_enemy("wizard_boss", Vec3::new(position.x, position.y, 9.0));
+    game.world_mut()
+        .entity_mut(boss)
+        .insert(BossScript::new(vec![BossPhase {
+            name: "Test".to_string(),
+            health_fraction: 1.0,
+            pattern,
+            cooldown_seconds: 0.1,
+            telegraph_seconds: 0.2,
+            speed_multiplier: 1.0,
+            damage_multiplier: 1.0,
+            enraged: false,
+        }]));
+    boss
+}
+
+fn count<F: bevy::ecs::query::QueryFilter>(game: &mut TestApp) -> usize {
+    game.world_mut()
+        .query_filtered::<Entity, F>()
+        .iter(game.world())
+        .count()
+}
+
+fn set_health_fraction(game: &mut TestApp, boss: Entity, fraction: f32) {
+    let mut health = game.world_mut().get_mut::<Health>(boss).unwrap();
+    health.current = (health.max as f32 * fraction).round() as i32;
+}
+
+#[test]
+fn wizard_boss_enters_later_phases_at_health_thresholds() {
+    let mut game = TestApp::training_hall();
+    let position = game.player_position() + Vec3::new(400.0, 0.0, 0.0);
+    let boss = game.spawn_enemy("wizard_boss", position);
+    let phases = game
+        .resource::<EnemyArchetypeRegistry>()
+        .get("wizard_boss")
+        .unwrap()
+        .phases
+        .clone();
+    assert_eq!(phases.len(), 3);
+
+    game.step();
+    assert_eq!(game.component::<BossScript>(boss).phase, 0);
+
+    set_health_fraction(&mut game, boss, phases[1].health_fraction - 0.01);
+    game.step();
+    let script = game.component::<BossScript>(boss);
+    assert_eq!(script.phase, 1);
+    assert!(!script.enraged());
+
+    set_health_fraction(&mut game, boss, phases[2].health_fraction - 0.01);
+    game.step();
+    let script = game.component::<BossScript>(boss);
+    assert_eq!(script.phase, 2);
+    assert!(script.enraged());
+    let color = game.component::<Sprite>(boss).color.to_srgba();
+    assert_eq!(color, BOSS_ENRAGED_TINT.to_srgba());
+}
+
+#[test]
+fn attacks_are_telegraphed_before_the_spread_volley_fires() {
+    let mut game = TestApp::training_hall();
+    scripted_wizard(
+        &mut game,
+        vec![BossAttack::Spread {
+            count: 5,
+            angle_degrees: 60.0,
+        }],
+    );
+
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), With<BossTelegraph>>()
+            .iter(world)
+            .next()
+            .is_some()
+    }));
+    assert_eq!(count::<With<BossWizardProjectile>>(&mut game), 0);
+
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), With<BossWizardProjectile>>()
+            .iter(world)
+            .count()
+            == 5
+    }));
+    assert_eq!(count::<With<BossTelegraph>>(&mut game), 0);
+}
+
+#[test]
+fn homing_orbs_turn_toward_the_player() {
+    let mut game = TestApp::training_hall();
+    let player_position = game.player_position();
+    let start = player_position + Vec3::new(-140.0, 80.0, 0.0);
+    let orb = game
+        .world_mut()
+        .spawn((
+            projectile_bundle(
+                start,
+                Vec2::X * WIZARD_BOSS_ORB_SPEED,
+                Vec2::splat(WIZARD_BOSS_ORB_SIZE),
+                WIZARD_BOSS_ORB_COLOR,
+                WIZARD_BOSS_ORB_LIFETIME,
+            ),
+            HomingProjectile {
+                turn_rate: 120f32.to_radians(),
+            },
+        ))
+        .id();
+
+    let alignment = |game: &mut TestApp| {
+        let position = game.component::<Transform>(orb).translation;
+        let to_player = (player_position - position).truncate().normalize();
+        game.component::<Projectile>(orb).direction().dot(to_player)
+    };
+
+    let before = alignment(&mut game);
+    game.step_frames(12);
+    let after = alignment(&mut game);
+    assert!(after > before, "orb did not steer: {before} -> {after}");
+    assert!(
+        (game.component::<Projectile>(orb).velocity.length() - WIZARD_BOSS_ORB_SPEED).abs() < 0.1
+    );
+}
+
+#[test]
+fn summoned_minions_give_no_experience_and_fall_with_the_boss() {
+    let mut game = TestApp::training_hall();
+    let boss = scripted_wizard(
+        &mut game,
+        vec![BossAttack::Summon {
+            archetype_id: "slime".to_string(),
+            count: 2,
+        }],
+    );
+
+    assert!(game.step_until(|world| { world.query::<&BossMinion>().iter(world).count() == 2 }));
+    let minions: Vec<Entity> = game
+        .world_mut()
+        .query_filtered::<Entity, With<BossMinion>>()
+        .iter(game.world())
+        .collect();
+
+    let experience = game.player_component::<PlayerProgression>().experience;
+    game.world_mut()
+        .get_mut::<Health>(minions[0])
+        .unwrap()
+        .current = 0;
+    game.step_frames(2);
+    assert!(game.world().get::<EnemyDeathEffect>(minions[0]).is_some());
+    assert_eq!(
+        game.player_component::<PlayerProgression>().experience,
+        experience
+    );
+
+    game.world_mut().get_mut::<Health>(boss).unwrap().current = 0;
+    assert!(
+        game.step_until(|world| world.get::<EnemyDeathEffect>(minions[1]).is_some()),
+        "minion outlived its boss"
+    );
+}
+
+#[test]
+fn summoning_unknown_archetypes_is_rejected() {
+    let json = br#"{
+        "archetypes": [{
+            "id": "lich",
+            "display_name": "Lich",
+            "sprite": "characters/enemies/wizard.png",
+            "scale": 4.0,
+            "stats": { "health": 100, "attack": 10, "defense": 5 },
+            "experience": 100,
+            "behavior": "SpellCaster",
+            "movement": { "patrol_range": 40.0, "patrol_speed": 40.0, "chase_speed": 60.0 },
+            "attack": { "radius": 200.0, "cooldown_seconds": 1.0 },
+            "phases": [{
+                "name": "Rise",
+                "health_fraction": 1.0,
+                "pattern": [{ "Summon": { "archetype_id": "ghoul", "count": 2 } }],
+                "cooldown_seconds": 1.0,
+                "telegraph_seconds": 0.3
+            }]
+        }]
+    }"#;
+
+    let error = EnemyArchetypeSet::from_json(json).unwrap_err();
+    assert!(error.to_string().contains("ghoul"), "{error}");
+}
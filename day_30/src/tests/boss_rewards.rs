@@ -0,0 +1,98 @@
+use super::harness::{TestApp, test_level};
+use crate::components::level::LevelExitDoor;
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::level::LevelAdvanceRequestEvent;
+
+fn boss_campaign() -> Vec<LevelDefinition> {
+    let mut boss_level = test_level("Boss Arena", &[("wizard_boss", 1)]);
+    boss_level.boss_loot = vec![
+        PickupEffect::EquipWeapon {
+            kind: WeaponKind::Level2,
+            family: WeaponFamily::Dagger,
+        },
+        PickupEffect::EquipShield(ShieldKind::Level1),
+    ];
+    vec![boss_level, test_level("Aftermath", &[])]
+}
+
+fn defeat_boss(game: &mut TestApp) {
+    let bosses = game.enemies_with_archetype("wizard_boss");
+    assert_eq!(bosses.len(), 1, "boss level should spawn exactly one boss");
+    game.world_mut()
+        .get_mut::<Health>(bosses[0])
+        .unwrap()
+        .current = 0;
+}
+
+#[test]
+fn defeating_the_boss_spawns_loot_chests_and_exit_portal() {
+    let mut game = TestApp::new(boss_campaign());
+    assert!(game.resource::<PendingLevelRewards>().rewards_available);
+
+    defeat_boss(&mut game);
+    let spawned = game.step_until(|world| world.resource::<PendingLevelRewards>().rewards_spawned);
+    assert!(spawned, "boss rewards were never spawned");
+    game.step();
+
+    let mut loot: Vec<PickupEffect> = game
+        .world_mut()
+        .query::<&Chest>()
+        .iter(game.world())
+        .filter_map(|chest| match &chest.contents {
+            ChestContents::Item(effect) => Some(effect.clone()),
+            ChestContents::Mimic => None,
+        })
+        .collect();
+    loot.retain(|effect| {
+        matches!(
+            effect,
+            PickupEffect::EquipWeapon {
+                kind: WeaponKind::Level2,
+                family: WeaponFamily::Dagger
+            } | PickupEffect::EquipShield(ShieldKind::Level1)
+        )
+    });
+    assert!(loot.contains(&PickupEffect::EquipWeapon {
+        kind: WeaponKind::Level2,
+        family: WeaponFamily::Dagger
+    }));
+    assert!(loot.contains(&PickupEffect::EquipShield(ShieldKind::Level1)));
+
+    let portal_targets: Vec<usize> = game
+        .world_mut()
+        .query::<&LevelExitDoor>()
+        .iter(game.world())
+        .map(|door| door.target_level)
+        .collect();
+    assert_eq!(portal_targets, vec![1]);
+}
+
+#[test]
+fn level_advance_request_loads_the_next_level_after_boss() {
+    let mut game = TestApp::new(boss_campaign());
+    defeat_boss(&mut game);
+    assert!(game.step_until(|world| world.resource::<PendingLevelRewards>().rewards_spawned));
+
+    game.send(LevelAdvanceRequestEvent { target_level: 1 });
+    game.step();
+    game.wait_for_level();
+
+    assert_eq!(game.resource::<LevelState>().current_index(), 1);
+    let rewards = game.resource::<PendingLevelRewards>();
+    assert_eq!(rewards.level_index, 1);
+    assert!(!rewards.rewards_available);
+    assert!(!rewards.rewards_spawned);
+    assert!(game.enemies_with_archetype("wizard_boss").is_empty());
+}
+
+#[test]
+fn level_advance_request_for_the_wrong_level_is_ignored() {
+    let mut game = TestApp::new(boss_campaign());
+
+    game.send(LevelAdvanceRequestEvent { target_level: 5 });
+    game.step_frames(2);
+
+    assert_eq!(game.resource::<LevelState>().current_index(), 0);
+    assert_eq!(game.enemies_with_archetype("wizard_boss").len(), 1);
+}
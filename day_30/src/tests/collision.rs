@@ -0,0 +1,113 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::TileMap;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+const TILE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;
+const HALF: Vec2 = Vec2::splat(PLAYER_COLLIDER_HALF_SIZE);
+
+/// 以字元畫出地圖：`#` 是牆、`.` 是地板，最下面一列是 y = 0
+fn map_from(rows: &[&str]) -> TileMap {
+    let mut tiles = Vec::new();
+    for (y, row) in rows.iter().rev().enumerate() {
+        for (x, cell) in row.chars().enumerate() {
+            let tile_type = if cell == '#' {
+                RoomTileType::WallWSide
+            } else {
+                RoomTileType::Floor
+            };
+            let entity = Entity::from_raw(tiles.len() as u32);
+            tiles.push((entity, Vec2::new(x as f32, y as f32) * TILE, tile_type));
+        }
+    }
+    TileMap::from_tiles(TILE, tiles)
+}
+
+#[test]
+fn diagonal_movement_slides_along_walls() {
+    let map = map_from(&[
+        "#####", //
+        ".....", //
+        ".....", //
+    ]);
+    let contact_y = TILE * 2.0 - TILE * 0.5 - HALF.y;
+    let start = Vec2::new(TILE, contact_y - 4.0);
+
+    let end = map.move_and_slide(start, Vec2::new(20.0, 20.0), HALF);
+    assert!(
+        (end.x - (start.x + 20.0)).abs() < 0.001,
+        "x should keep moving"
+    );
+    assert!(
+        (end.y - contact_y).abs() < 0.001,
+        "y should stop at the wall"
+    );
+
+    // 貼著牆走過接縫也不會被卡住
+    let along = map.move_and_slide(end, Vec2::new(TILE * 2.0, 0.0), HALF);
+    assert!((along.x - (end.x + TILE * 2.0)).abs() < 0.001);
+}
+
+#[test]
+fn fast_movement_does_not_tunnel_through_thin_walls() {
+    let map = map_from(&["..#.."]);
+    let end = map.move_and_slide(Vec2::ZERO, Vec2::new(TILE * 4.0, 0.0), HALF);
+    assert!(
+        (end.x - (TILE * 1.5 - HALF.x)).abs() < 0.001,
+        "ended at {end}"
+    );
+}
+
+#[test]
+fn overlapping_boxes_are_pushed_out_of_every_contact() {
+    let map = map_from(&[
+        "###", //
+        "#..", //
+        "#..", //
+    ]);
+    // 同時卡在西牆與北牆的角落
+    let stuck = Vec2::new(TILE * 0.6, TILE * 1.4);
+    let freed = map.depenetrate(stuck, HALF);
+    assert!(
+        freed.x >= TILE * 0.5 + HALF.x - 0.001,
+        "still in the west wall: {freed}"
+    );
+    assert!(
+        freed.y <= TILE * 1.5 - HALF.y + 0.001,
+        "still in the north wall: {freed}"
+    );
+}
+
+#[test]
+fn player_stays_inside_the_room_at_low_framerates() {
+    let mut game = TestApp::training_hall();
+    let start = game.player_position() + Vec3::new(0.0, TILE * 2.0, 0.0);
+    let player = game.player();
+    game.world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation = start;
+
+    // 每影格四分之一秒：一步就超過半格
+    game.world_mut()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            0.25,
+        )));
+    game.set_key(KeyCode::KeyA, true);
+    game.set_key(KeyCode::KeyW, true);
+    game.step_frames(30);
+    game.set_key(KeyCode::KeyA, false);
+    game.set_key(KeyCode::KeyW, false);
+
+    let position = game.player_position().truncate();
+    let map = game.resource::<TileMap>();
+    let cell = map.cell(position);
+    assert!(map.is_walkable(cell), "player left the floor at {position}");
+    assert!(
+        !map.is_walkable((cell.0 - 1, cell.1)) && !map.is_walkable((cell.0, cell.1 + 1)),
+        "player should have slid into the north-west corner, ended at {position}"
+    );
+}
@@ -0,0 +1,102 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::DamageRequest;
+use bevy::prelude::*;
+
+/// 目前正在播放的戰鬥文字內容與種類
+fn visible_texts(game: &mut TestApp) -> Vec<(String, CombatTextKind)> {
+    game.world_mut()
+        .query::<(&CombatText, &Text2d)>()
+        .iter(game.world())
+        .filter(|(text, _)| text.active)
+        .map(|(text, content)| (content.0.clone(), text.kind))
+        .collect()
+}
+
+fn combat_text_count(game: &mut TestApp) -> usize {
+    game.world_mut()
+        .query::<&CombatText>()
+        .iter(game.world())
+        .count()
+}
+
+#[test]
+fn melee_hit_shows_the_damage_dealt() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+    let starting_health = game.component::<Health>(slime).current;
+
+    game.attack();
+    game.step();
+
+    let damage = starting_health - game.component::<Health>(slime).current;
+    assert!(damage > 0);
+    assert!(visible_texts(&mut game).contains(&(damage.to_string(), CombatTextKind::DamageDealt)));
+}
+
+#[test]
+fn damage_taken_poison_and_heals_use_their_own_colors() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    game.world_mut().get_mut::<Health>(player).unwrap().current -= 30;
+
+    game.send(DamageRequest::new(player, 50, DamageType::Physical));
+    game.send(DamageRequest::new(player, 2, DamageType::Poison));
+    let position = game.player_position();
+    game.world_mut().spawn((
+        Pickup {
+            effect: PickupEffect::Heal(10),
+        },
+        Transform::from_translation(position),
+    ));
+    game.step();
+    game.step();
+
+    let kinds: Vec<CombatTextKind> = visible_texts(&mut game)
+        .into_iter()
+        .map(|(_, kind)| kind)
+        .collect();
+    assert!(kinds.contains(&CombatTextKind::DamageTaken));
+    assert!(kinds.contains(&CombatTextKind::Poison));
+    assert!(visible_texts(&mut game).contains(&("+10".to_string(), CombatTextKind::Heal)));
+}
+
+#[test]
+fn defeating_an_enemy_shows_experience() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+    game.world_mut().get_mut::<Health>(slime).unwrap().current = 1;
+
+    game.attack();
+    game.step_frames(2);
+
+    assert!(visible_texts(&mut game).contains(&("+30 XP".to_string(), CombatTextKind::Experience)));
+}
+
+#[test]
+fn texts_fade_out_and_the_pool_stays_bounded() {
+    let mut game = TestApp::training_hall();
+    let player_position = game.player_position();
+    let cyclops = game.spawn_enemy(
+        "cyclops",
+        player_position + Vec3::new(ROOM_TILE_SIZE * 4.0, 0.0, 0.0),
+    );
+    game.world_mut().get_mut::<Health>(cyclops).unwrap().current = 10_000;
+
+    for _ in 0..3 {
+        for _ in 0..COMBAT_TEXT_POOL_SIZE {
+            game.send(DamageRequest::new(cyclops, 1, DamageType::Poison));
+        }
+        game.step();
+    }
+    assert_eq!(combat_text_count(&mut game), COMBAT_TEXT_POOL_SIZE);
+
+    game.step_frames(60);
+    assert!(visible_texts(&mut game).is_empty());
+
+    game.send(DamageRequest::new(cyclops, 1, DamageType::Poison));
+    game.step();
+    assert_eq!(combat_text_count(&mut game), COMBAT_TEXT_POOL_SIZE);
+    assert_eq!(visible_texts(&mut game).len(), 1);
+}
@@ -0,0 +1,173 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::*;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+
+fn controls_button(game: &mut TestApp, action: ControlsMenuAction) -> Entity {
+    game.world_mut()
+        .query::<(Entity, &ControlsMenuButton)>()
+        .iter(game.world())
+        .find(|(_, button)| button.action == action)
+        .map(|(entity, _)| entity)
+        .unwrap_or_else(|| panic!("controls menu should have a {action:?} button"))
+}
+
+#[test]
+fn attack_and_interact_are_separate_actions() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+    let starting_health = game.component::<Health>(slime).current;
+
+    game.tap_key(KeyCode::KeyE);
+    assert_eq!(game.component::<Health>(slime).current, starting_health);
+
+    game.tap_key(KeyCode::Space);
+    assert!(game.component::<Health>(slime).current < starting_health);
+}
+
+#[test]
+fn rebinding_from_the_controls_menu_changes_and_saves_the_key() {
+    let mut game = TestApp::training_hall();
+    game.tap_key(KeyCode::Escape);
+    game.send(OpenControlsMenuEvent);
+    game.step();
+
+    let rebind = controls_button(&mut game, ControlsMenuAction::Rebind(InputAction::Attack));
+    game.click_button(rebind);
+    assert_eq!(
+        game.resource::<GameSession>()
+            .controls_menu
+            .and_then(|menu| menu.awaiting),
+        Some(InputAction::Attack)
+    );
+
+    game.tap_key(KeyCode::KeyK);
+
+    let bindings = game.resource::<InputBindings>().clone();
+    assert_eq!(
+        bindings.binding(InputAction::Attack).keys,
+        vec![KeyCode::KeyK]
+    );
+    let menu = game.resource::<GameSession>().controls_menu.unwrap();
+    assert_eq!(menu.awaiting, None);
+
+    let saved = game
+        .resource::<SettingsStorage>()
+        .load_bindings()
+        .expect("settings file should be readable");
+    assert_eq!(saved, bindings);
+
+    // Esc 先關掉操作設定選單，再關掉暫停選單
+    game.tap_key(KeyCode::Escape);
+    assert!(game.resource::<GameSession>().controls_menu.is_none());
+    game.tap_key(KeyCode::Escape);
+    assert_eq!(game.phase(), GamePhase::Playing);
+
+    game.wait_for_weapon_ready();
+    let slime = game.spawn_enemy_in_reach("slime");
+    let starting_health = game.component::<Health>(slime).current;
+    game.tap_key(KeyCode::Space);
+    assert_eq!(game.component::<Health>(slime).current, starting_health);
+    game.tap_key(KeyCode::KeyK);
+    assert!(game.component::<Health>(slime).current < starting_health);
+}
+
+#[test]
+fn clicking_the_pending_binding_again_cancels_it() {
+    let mut game = TestApp::training_hall();
+    game.tap_key(KeyCode::Escape);
+    game.send(OpenControlsMenuEvent);
+    game.step();
+
+    let rebind = controls_button(&mut game, ControlsMenuAction::Rebind(InputAction::Pause));
+    game.click_button(rebind);
+    let rebind = controls_button(&mut game, ControlsMenuAction::Rebind(InputAction::Pause));
+    game.click_button(rebind);
+
+    let session = game.resource::<GameSession>();
+    assert_eq!(session.controls_menu.map(|menu| menu.awaiting), Some(None));
+    assert_eq!(
+        game.resource::<InputBindings>()
+            .binding(InputAction::Pause)
+            .keys,
+        vec![KeyCode::Escape]
+    );
+}
+
+#[test]
+fn escape_can_be_bound_back_to_pause() {
+    let mut game = TestApp::training_hall();
+    game.tap_key(KeyCode::Escape);
+    game.send(OpenControlsMenuEvent);
+    game.step();
+
+    for key in [KeyCode::KeyP, KeyCode::Escape] {
+        let rebind = controls_button(&mut game, ControlsMenuAction::Rebind(InputAction::Pause));
+        game.click_button(rebind);
+        game.tap_key(key);
+        assert_eq!(
+            game.resource::<InputBindings>()
+                .binding(InputAction::Pause)
+                .keys,
+            vec![key]
+        );
+    }
+
+    // 綁定完成後 Esc 照常關掉選單
+    game.tap_key(KeyCode::Escape);
+    assert!(game.resource::<GameSession>().controls_menu.is_none());
+}
+
+#[test]
+fn rebinding_a_key_removes_it_from_other_actions() {
+    let mut bindings = InputBindings::default();
+
+    bindings.rebind_key(InputAction::Interact, KeyCode::Space);
+
+    assert_eq!(
+        bindings.binding(InputAction::Interact).keys,
+        vec![KeyCode::Space]
+    );
+    assert_eq!(
+        bindings.binding(InputAction::Attack).keys,
+        vec![KeyCode::KeyJ]
+    );
+}
+
+#[test]
+fn taking_an_actions_only_key_gives_it_the_old_key() {
+    let mut bindings = InputBindings::default();
+
+    bindings.rebind_key(InputAction::Attack, KeyCode::KeyE);
+
+    assert_eq!(
+        bindings.binding(InputAction::Attack).keys,
+        vec![KeyCode::KeyE]
+    );
+    assert_eq!(
+        bindings.binding(InputAction::Interact).keys,
+        vec![KeyCode::Space, KeyCode::KeyJ]
+    );
+}
+
+#[test]
+fn saved_bindings_are_loaded_at_startup() {
+    let mut game = TestApp::training_hall();
+    let mut bindings = InputBindings::default();
+    bindings.rebind_key(InputAction::MoveRight, KeyCode::KeyL);
+    game.resource::<SettingsStorage>()
+        .write_bindings(&bindings)
+        .unwrap();
+
+    game.world_mut()
+        .run_system_once(load_input_settings)
+        .unwrap();
+    assert_eq!(*game.resource::<InputBindings>(), bindings);
+
+    let start = game.player_position();
+    game.set_key(KeyCode::KeyL, true);
+    game.step_frames(10);
+    assert!(game.player_position().x > start.x);
+}
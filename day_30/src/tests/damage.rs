@@ -0,0 +1,87 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::PlayerDeathState;
+use crate::systems::DamageRequest;
+use bevy::prelude::*;
+
+#[test]
+fn magic_resistance_halves_spell_damage() {
+    let mut game = TestApp::training_hall();
+    let wizard = game.spawn_enemy_away("wizard_boss", Vec2::new(ROOM_TILE_SIZE * 6.0, 0.0));
+    let defense = game.component::<Defense>(wizard).value();
+    let starting_health = game.component::<Health>(wizard).current;
+
+    game.send(DamageRequest::new(wizard, 40, DamageType::Magic));
+    game.step();
+
+    let expected = (compute_damage(40, Some(defense)) as f32 * 0.5).round() as i32;
+    assert_eq!(
+        game.component::<Health>(wizard).current,
+        starting_health - expected.max(1)
+    );
+}
+
+#[test]
+fn spider_is_immune_to_poison() {
+    let mut game = TestApp::training_hall();
+    let spider = game.spawn_enemy_away("spider", Vec2::new(ROOM_TILE_SIZE * 6.0, 0.0));
+    let starting_health = game.component::<Health>(spider).current;
+
+    game.send(DamageRequest::new(spider, 5, DamageType::Poison));
+    game.step();
+
+    assert_eq!(game.component::<Health>(spider).current, starting_health);
+}
+
+#[test]
+fn invulnerability_blocks_hits_but_not_poison() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    game.world_mut()
+        .entity_mut(player)
+        .insert(Invulnerable::new(5.0));
+    let starting_health = game.component::<Health>(player).current;
+
+    game.send(DamageRequest::new(player, 30, DamageType::Physical));
+    game.step();
+    assert_eq!(game.component::<Health>(player).current, starting_health);
+
+    game.send(DamageRequest::new(player, 2, DamageType::Poison));
+    game.step();
+    assert_eq!(
+        game.component::<Health>(player).current,
+        starting_health - 2
+    );
+}
+
+#[test]
+fn critical_hits_multiply_before_defense() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_away("slime", Vec2::new(ROOM_TILE_SIZE * 6.0, 0.0));
+    let defense = game.component::<Defense>(slime).value();
+    let starting_health = game.component::<Health>(slime).current;
+
+    game.send(DamageRequest::new(slime, 20, DamageType::Physical).with_critical(true));
+    game.step();
+
+    let boosted = (20.0 * CRITICAL_HIT_MULTIPLIER).round() as i32;
+    assert_eq!(
+        game.component::<Health>(slime).current,
+        (starting_health - compute_damage(boosted, Some(defense))).max(0)
+    );
+}
+
+#[test]
+fn lethal_damage_starts_player_death_sequence() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    let health = game.component::<Health>(player).current;
+
+    game.send(DamageRequest::new(player, health * 10, DamageType::Poison));
+    game.step_frames(2);
+
+    assert_eq!(game.component::<Health>(player).current, 0);
+    assert!(game.world().get::<PlayerDead>(player).is_some());
+    assert!(game.resource::<PlayerDeathState>().is_active());
+}
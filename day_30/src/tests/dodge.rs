@@ -0,0 +1,80 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::DamageRequest;
+use bevy::prelude::*;
+
+#[test]
+fn dodge_rolls_forward_and_spends_stamina() {
+    let mut game = TestApp::training_hall();
+    let start = game.player_position();
+
+    game.tap_key(KeyCode::ShiftLeft);
+
+    let player = game.player();
+    assert!(game.world().get::<Invulnerable>(player).is_some());
+    assert!(game.player_component::<Stamina>().current < PLAYER_MAX_STAMINA);
+
+    assert!(game.step_until(|world| world.get::<DodgeRoll>(player).is_none()));
+    let travelled = game.player_position().x - start.x;
+    assert!(
+        travelled > ROOM_TILE_SIZE * PLAYER_SCALE,
+        "travelled only {travelled}"
+    );
+    assert_eq!(
+        game.player_component::<Transform>().rotation,
+        Quat::IDENTITY
+    );
+}
+
+#[test]
+fn dodge_ignores_hits_during_roll() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    let starting_health = game.component::<Health>(player).current;
+
+    game.tap_key(KeyCode::ShiftLeft);
+    game.send(DamageRequest::new(player, 30, DamageType::Magic));
+    game.step();
+
+    assert_eq!(game.component::<Health>(player).current, starting_health);
+}
+
+#[test]
+fn dodge_is_canceled_without_stamina() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    game.world_mut().get_mut::<Stamina>(player).unwrap().current = 0.0;
+    let start = game.player_position();
+
+    game.tap_key(KeyCode::ShiftLeft);
+
+    assert!(game.world().get::<DodgeRoll>(player).is_none());
+    assert!(game.world().get::<Invulnerable>(player).is_none());
+    assert_eq!(game.player_position(), start);
+}
+
+#[test]
+fn dodge_does_not_pass_through_walls() {
+    let mut game = TestApp::training_hall();
+    let wall_x = game
+        .world_mut()
+        .query::<(&Transform, &RoomTile)>()
+        .iter(game.world())
+        .filter(|(_, tile)| tile.tile_type == RoomTileType::WallESide)
+        .map(|(transform, _)| transform.translation.x)
+        .fold(f32::MIN, f32::max);
+
+    let player = game.player();
+    let tile_span = ROOM_TILE_SIZE * PLAYER_SCALE;
+    game.world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation
+        .x = wall_x - tile_span * 1.5;
+
+    game.tap_key(KeyCode::ShiftLeft);
+    game.step_frames(30);
+
+    assert!(game.player_position().x < wall_x - tile_span * 0.5);
+}
@@ -0,0 +1,331 @@
+use super::harness::{TestApp, test_level};
+use crate::components::level::{DoorLocked, EncounterEnemy, EncounterSpawnTelegraph};
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::{
+    Encounter, EncounterPlan, EncounterStage, EncounterWave, EnemyCounts, LevelDefinition,
+    SaveSlotState, SaveStorage, TileMap,
+};
+use crate::systems::DoorInteractionEvent;
+use crate::systems::game_session::RequestSaveGameEvent;
+use bevy::prelude::*;
+
+const TILE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;
+
+fn wave(slimes: usize) -> EncounterWave {
+    let mut enemies = EnemyCounts::default();
+    enemies.set("slime", slimes);
+    EncounterWave {
+        enemies,
+        delay_seconds: 0.1,
+    }
+}
+
+fn encounter_level(waves: Vec<EncounterWave>) -> LevelDefinition {
+    let mut level = test_level("Sealed Hall", &[]);
+    level.encounter = Some(EncounterPlan {
+        trigger_depth_tiles: 3.0,
+        telegraph_seconds: 0.3,
+        waves,
+    });
+    level
+}
+
+fn move_player_to(game: &mut TestApp, position: Vec2) {
+    let player = game.player();
+    let mut transform = game.world_mut().get_mut::<Transform>(player).unwrap();
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+    game.step();
+}
+
+/// 把玩家移到遭遇戰範圍內離入口最近的格子
+fn enter_room(game: &mut TestApp) {
+    let player = game.player_position().truncate();
+    let tile_map = game.resource::<TileMap>();
+    let cell = game
+        .resource::<Encounter>()
+        .area()
+        .min_by(|a, b| {
+            let a = tile_map.cell_center(*a).distance(player);
+            let b = tile_map.cell_center(*b).distance(player);
+            a.total_cmp(&b)
+        })
+        .expect("encounter area should not be empty");
+    let position = tile_map.cell_center(cell);
+    move_player_to(game, position);
+}
+
+fn doors(game: &mut TestApp) -> Vec<(Entity, bool, bool)> {
+    game.world_mut()
+        .query::<(Entity, &Door, Has<DoorLocked>)>()
+        .iter(game.world())
+        .map(|(entity, door, locked)| (entity, door.is_open, locked))
+        .collect()
+}
+
+fn living_wave(game: &mut TestApp) -> Vec<Entity> {
+    game.world_mut()
+        .query_filtered::<Entity, (With<EncounterEnemy>, Without<EnemyDeathEffect>)>()
+        .iter(game.world())
+        .collect()
+}
+
+fn defeat_wave(game: &mut TestApp) {
+    for enemy in living_wave(game) {
+        game.world_mut().get_mut::<Health>(enemy).unwrap().current = 0;
+    }
+    game.step();
+}
+
+#[test]
+fn entering_the_room_seals_the_doors() {
+    let mut game = TestApp::new(vec![encounter_level(vec![wave(1)])]);
+    assert_eq!(
+        game.resource::<Encounter>().stage(),
+        EncounterStage::Dormant
+    );
+    assert!(!doors(&mut game).is_empty());
+
+    enter_room(&mut game);
+    assert_eq!(game.resource::<Encounter>().stage(), EncounterStage::Active);
+    for (_, is_open, locked) in doors(&mut game) {
+        assert!(!is_open && locked);
+    }
+
+    // 上鎖的門不理會互動鍵
+    game.send(DoorInteractionEvent);
+    game.step();
+    assert!(doors(&mut game).iter().all(|(_, is_open, _)| !is_open));
+}
+
+#[test]
+fn standing_outside_the_room_floor_does_not_trigger() {
+    let mut game = TestApp::new(vec![encounter_level(vec![wave(1)])]);
+    let entrance = game.player_position().truncate();
+
+    // 入口門附近與房間外（與房間內同高度）都不算進入房間
+    move_player_to(&mut game, entrance + Vec2::Y * TILE);
+    let (min_y, max_y) = game
+        .resource::<Encounter>()
+        .area()
+        .fold((i32::MAX, i32::MIN), |(min, max), (_, y)| {
+            (min.min(y), max.max(y))
+        });
+    let inside_y = (min_y + max_y) as f32 * 0.5 * TILE;
+    move_player_to(&mut game, Vec2::new(entrance.x + TILE * 100.0, inside_y));
+    assert_eq!(
+        game.resource::<Encounter>().stage(),
+        EncounterStage::Dormant
+    );
+
+    enter_room(&mut game);
+    assert_eq!(game.resource::<Encounter>().stage(), EncounterStage::Active);
+}
+
+#[test]
+fn waves_appear_after_their_telegraphs() {
+    let mut game = TestApp::new(vec![encounter_level(vec![wave(3)])]);
+    enter_room(&mut game);
+
+    assert!(game.step_until(|world| {
+        world
+            .query::<&EncounterSpawnTelegraph>()
+            .iter(world)
+            .count()
+            == 3
+    }));
+    assert!(living_wave(&mut game).is_empty());
+
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), With<EncounterEnemy>>()
+            .iter(world)
+            .count()
+            == 3
+    }));
+    let telegraphs = game
+        .world_mut()
+        .query::<&EncounterSpawnTelegraph>()
+        .iter(game.world())
+        .count();
+    assert_eq!(telegraphs, 0);
+
+    let player = game.player_position().truncate();
+    for enemy in living_wave(&mut game) {
+        let position = game.component::<Transform>(enemy).translation.truncate();
+        let cell = game.resource::<TileMap>().cell(position);
+        assert!(
+            game.resource::<Encounter>().contains(cell),
+            "enemy spawned outside the room"
+        );
+        assert!(
+            position.distance(player) >= TILE * 2.0,
+            "enemy spawned on the player"
+        );
+    }
+}
+
+#[test]
+fn waves_wait_for_free_floor_and_may_spawn_close_to_the_player() {
+    let mut game = TestApp::new(vec![encounter_level(vec![wave(2)])]);
+    enter_room(&mut game);
+
+    // 沒有任何空地時這一波不會被跳過，只是延後
+    let real_map = game.resource::<TileMap>().clone();
+    game.world_mut().insert_resource(TileMap::default());
+    game.step_frames(20);
+    let telegraphs = |game: &mut TestApp| {
+        game.world_mut()
+            .query::<&EncounterSpawnTelegraph>()
+            .iter(game.world())
+            .count()
+    };
+    assert_eq!(telegraphs(&mut game), 0);
+    assert!(game.resource::<Encounter>().has_remaining_waves());
+
+    // 唯一的空地就在玩家腳下時仍然放出整波
+    let player = game.player_position().truncate();
+    let cell = real_map.cell(player);
+    game.world_mut().insert_resource(TileMap::from_tiles(
+        real_map.tile_size(),
+        [(
+            Entity::PLACEHOLDER,
+            real_map.cell_center(cell),
+            RoomTileType::Floor,
+        )],
+    ));
+    game.step();
+    assert_eq!(telegraphs(&mut game), 2);
+    assert!(!game.resource::<Encounter>().has_remaining_waves());
+}
+
+#[test]
+fn doors_unlock_once_the_final_wave_falls() {
+    let mut game = TestApp::new(vec![encounter_level(vec![wave(1), wave(2)])]);
+    enter_room(&mut game);
+
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), With<EncounterEnemy>>()
+            .iter(world)
+            .count()
+            == 1
+    }));
+    defeat_wave(&mut game);
+
+    // 第一波倒下後門仍然鎖著，接著出現第二波
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), (With<EncounterEnemy>, Without<EnemyDeathEffect>)>()
+            .iter(world)
+            .count()
+            == 2
+    }));
+    assert!(doors(&mut game).iter().all(|(_, _, locked)| *locked));
+    assert_eq!(game.resource::<Encounter>().stage(), EncounterStage::Active);
+
+    defeat_wave(&mut game);
+    assert!(
+        game.step_until(|world| {
+            world.resource::<Encounter>().stage() == EncounterStage::Cleared
+        })
+    );
+    game.step();
+    for (_, is_open, locked) in doors(&mut game) {
+        assert!(is_open && !locked);
+    }
+}
+
+#[test]
+fn encounter_waves_are_validated() {
+    let mut level = encounter_level(Vec::new());
+    assert!(level.validate().is_err());
+
+    level.encounter = Some(EncounterPlan {
+        trigger_depth_tiles: 3.0,
+        telegraph_seconds: 0.3,
+        waves: vec![wave(0)],
+    });
+    let error = level.validate().unwrap_err();
+    assert!(error.contains("wave 1"), "{error}");
+
+    level.encounter = Some(EncounterPlan {
+        trigger_depth_tiles: 3.0,
+        telegraph_seconds: 0.3,
+        waves: vec![wave(2)],
+    });
+    assert!(level.validate().is_ok());
+}
+
+#[test]
+fn respawning_resets_an_unfinished_encounter() {
+    let mut game = TestApp::new(vec![encounter_level(vec![wave(1), wave(2)])]);
+    enter_room(&mut game);
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), With<EncounterEnemy>>()
+            .iter(world)
+            .count()
+            == 1
+    }));
+
+    game.kill_and_respawn_player();
+    game.step();
+
+    assert_eq!(
+        game.resource::<Encounter>().stage(),
+        EncounterStage::Dormant
+    );
+    assert!(living_wave(&mut game).is_empty());
+    let telegraphs = game
+        .world_mut()
+        .query::<&EncounterSpawnTelegraph>()
+        .iter(game.world())
+        .count();
+    assert_eq!(telegraphs, 0);
+    for (_, is_open, locked) in doors(&mut game) {
+        assert!(is_open && !locked);
+    }
+
+    // 再次進房時從第一波重新開始
+    enter_room(&mut game);
+    assert_eq!(game.resource::<Encounter>().stage(), EncounterStage::Active);
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), With<EncounterEnemy>>()
+            .iter(world)
+            .count()
+            == 1
+    }));
+}
+
+#[test]
+fn saving_waits_until_the_encounter_is_over() {
+    let mut game = TestApp::new(vec![encounter_level(vec![wave(1)])]);
+    enter_room(&mut game);
+
+    game.send(RequestSaveGameEvent { slot: 0 });
+    game.step();
+    let storage = game.resource::<SaveStorage>();
+    assert!(matches!(storage.read_slot(0), SaveSlotState::Empty));
+
+    assert!(game.step_until(|world| {
+        world
+            .query_filtered::<(), With<EncounterEnemy>>()
+            .iter(world)
+            .count()
+            == 1
+    }));
+    defeat_wave(&mut game);
+    assert!(
+        game.step_until(|world| {
+            world.resource::<Encounter>().stage() == EncounterStage::Cleared
+        })
+    );
+
+    game.send(RequestSaveGameEvent { slot: 0 });
+    game.step();
+    let storage = game.resource::<SaveStorage>();
+    assert!(matches!(storage.read_slot(0), SaveSlotState::Occupied(_)));
+}
@@ -0,0 +1,27 @@
+use crate::components::PickupEffect;
+use crate::constants::*;
+use crate::resources::EndlessLevelGenerator;
+
+fn potions(loot: &[PickupEffect]) -> usize {
+    loot.iter()
+        .filter(|effect| matches!(effect, PickupEffect::Heal(_)))
+        .count()
+}
+
+#[test]
+fn endless_bosses_keep_scaling_past_the_elite_depth() {
+    let generator = EndlessLevelGenerator::new(42);
+    let elite = generator.generate(ENDLESS_ELITE_BOSS_DEPTH);
+    let deeper = generator.generate(ENDLESS_ELITE_BOSS_DEPTH + 2);
+    let deepest = generator.generate(ENDLESS_ELITE_BOSS_DEPTH + 200);
+
+    assert_eq!(elite.boss_stat_multiplier, 1.0);
+    assert!(deeper.boss_stat_multiplier > elite.boss_stat_multiplier);
+    // 成長有上限
+    assert_eq!(deepest.boss_stat_multiplier, ENDLESS_MAX_BOSS_MULTIPLIER);
+
+    assert!(potions(&deeper.boss_loot) >= 1);
+    assert!(potions(&deepest.boss_loot) > potions(&deeper.boss_loot));
+    assert_eq!(potions(&deepest.boss_loot), ENDLESS_MAX_BOSS_POTIONS);
+    assert!(deepest.validate().is_ok());
+}
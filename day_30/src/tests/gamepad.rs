@@ -0,0 +1,93 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::resources::*;
+use bevy::prelude::*;
+
+#[test]
+fn left_stick_moves_the_player() {
+    let mut game = TestApp::training_hall();
+    let gamepad = game.connect_gamepad();
+    let start = game.player_position();
+
+    game.set_gamepad_axis(gamepad, GamepadAxis::LeftStickX, 1.0);
+    game.step_frames(10);
+
+    assert!(game.player_position().x > start.x);
+    let player = game.player();
+    assert_eq!(
+        game.world().get::<PlayerFacing>(player).unwrap().direction,
+        Vec2::X
+    );
+}
+
+#[test]
+fn stick_drift_inside_the_deadzone_is_ignored() {
+    let mut game = TestApp::training_hall();
+    let gamepad = game.connect_gamepad();
+    let start = game.player_position();
+
+    game.set_gamepad_axis(gamepad, GamepadAxis::LeftStickY, 0.08);
+    game.step_frames(10);
+
+    assert_eq!(game.player_position(), start);
+}
+
+#[test]
+fn right_stick_aims_the_attack_reticle() {
+    let mut game = TestApp::training_hall();
+    let gamepad = game.connect_gamepad();
+
+    game.set_gamepad_axis(gamepad, GamepadAxis::RightStickY, -0.9);
+    game.step();
+
+    let reticle = game
+        .world_mut()
+        .query::<&AttackReticle>()
+        .single(game.world())
+        .unwrap()
+        .last_direction;
+    assert_eq!(reticle, Vec2::NEG_Y);
+}
+
+#[test]
+fn attack_button_swings_at_enemies() {
+    let mut game = TestApp::training_hall();
+    let gamepad = game.connect_gamepad();
+    let slime = game.spawn_enemy_in_reach("slime");
+    let starting_health = game.component::<Health>(slime).current;
+
+    game.tap_gamepad_button(gamepad, GamepadButton::West);
+
+    assert!(game.component::<Health>(slime).current < starting_health);
+}
+
+#[test]
+fn start_button_toggles_the_pause_menu() {
+    let mut game = TestApp::training_hall();
+    let gamepad = game.connect_gamepad();
+
+    game.tap_gamepad_button(gamepad, GamepadButton::Start);
+    assert_eq!(game.phase(), GamePhase::Paused);
+    assert!(game.resource::<GameSession>().pause_menu_root.is_some());
+
+    game.tap_gamepad_button(gamepad, GamepadButton::Start);
+    assert_eq!(game.phase(), GamePhase::Playing);
+}
+
+#[test]
+fn dpad_navigates_the_pause_menu() {
+    let mut game = TestApp::training_hall();
+    let gamepad = game.connect_gamepad();
+    game.tap_gamepad_button(gamepad, GamepadButton::Start);
+
+    // 暫停選單依序為 Resume、Save Game、Load Game
+    game.tap_gamepad_button(gamepad, GamepadButton::DPadDown);
+    game.tap_gamepad_button(gamepad, GamepadButton::South);
+    game.step();
+
+    let picker = game
+        .resource::<GameSession>()
+        .save_slot_picker
+        .expect("save slot picker should be open");
+    assert_eq!(picker.mode, SaveSlotPickerMode::Save);
+}
@@ -0,0 +1,390 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::plugins::*;
+use crate::resources::*;
+use crate::systems::*;
+use bevy::asset::AssetPlugin;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::ButtonState;
+use bevy::input::InputPlugin;
+use bevy::input::gamepad::{
+    GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+    RawGamepadButtonChangedEvent, RawGamepadEvent,
+};
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// 每次 `step` 推進的固定時間
+pub const FRAME_SECONDS: f64 = 1.0 / 60.0;
+
+/// 等待關卡生成、武器冷卻等狀態時最多推進的影格數
+const MAX_SETTLE_FRAMES: usize = 240;
+
+const BESTIARY_JSON: &[u8] = include_bytes!("../../assets/enemies/bestiary.enemies.json");
+
+static NEXT_SAVE_DIRECTORY: AtomicUsize = AtomicUsize::new(0);
+
+/// 無視窗的遊戲模擬：只載入遊戲邏輯相關的 plugin，圖片與音效 handle 都不會真的讀到檔案
+pub struct TestApp {
+    app: App,
+    save_directory: PathBuf,
+}
+
+impl TestApp {
+    /// 以指定的關卡清單開始新遊戲，並推進到第一關生成完成、武器可以揮動為止
+    pub fn new(mut levels: Vec<LevelDefinition>) -> Self {
+        let mut app = App::new();
+
+        // 資產路徑指向不存在的資料夾，避免背景載入的關卡檔覆蓋測試注入的資料
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: "headless-test-assets".to_string(),
+                ..default()
+            },
+            InputPlugin,
+            StatesPlugin,
+        ))
+        .init_asset::<Image>()
+        .init_asset::<Font>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            FRAME_SECONDS,
+        )));
+
+        app.add_plugins((SessionPlugin, ControlsPlugin))
+            .add_plugins((
+                WorldPlugin,
+                LevelPlugin,
+                PlayerPlugin,
+                EnemyPlugin,
+                ProgressionPlugin,
+                ItemPlugin,
+                ChestPlugin,
+                EquipmentPlugin,
+                AttackPlugin,
+                WallCollisionPlugin,
+                DoorInteractionPlugin,
+                RoomTransitionPlugin,
+            ))
+            .add_plugins((
+                DamagePlugin,
+                StatusEffectsPlugin,
+                ProjectilePlugin,
+                CombatTextPlugin,
+            ));
+
+        let save_directory = std::env::temp_dir().join(format!(
+            "rogue_lite_test_saves_{}_{}",
+            std::process::id(),
+            NEXT_SAVE_DIRECTORY.fetch_add(1, Ordering::Relaxed)
+        ));
+        app.insert_resource(SaveStorage::new(save_directory.clone()))
+            .insert_resource(SettingsStorage::new(save_directory.join("settings.json")));
+
+        let bestiary =
+            EnemyArchetypeSet::from_json(BESTIARY_JSON).expect("bundled bestiary should be valid");
+        app.world_mut()
+            .resource_mut::<EnemyArchetypeRegistry>()
+            .replace(bestiary.archetypes);
+        // 與關卡清單載入時相同，index 依清單順序決定
+        for (index, definition) in levels.iter_mut().enumerate() {
+            definition.index = index;
+        }
+        app.world_mut()
+            .resource_mut::<LevelState>()
+            .replace_definitions(levels);
+
+        app.finish();
+        app.cleanup();
+
+        let mut test_app = Self {
+            app,
+            save_directory,
+        };
+        test_app.send(StartNewGameEvent);
+        test_app.wait_for_level();
+        test_app.wait_for_weapon_ready();
+        test_app
+    }
+
+    /// 只有一間空房間的訓練關卡，大多數測試都從這裡開始
+    pub fn training_hall() -> Self {
+        Self::new(vec![test_level("Training Hall", &[])])
+    }
+
+    pub fn step(&mut self) {
+        self.app.update();
+    }
+
+    pub fn step_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    /// 推進影格直到條件成立，回傳是否在上限內達成
+    pub fn step_until(&mut self, mut condition: impl FnMut(&mut World) -> bool) -> bool {
+        for _ in 0..MAX_SETTLE_FRAMES {
+            if condition(self.app.world_mut()) {
+                return true;
+            }
+            self.step();
+        }
+        condition(self.app.world_mut())
+    }
+
+    pub fn send<E: Event>(&mut self, event: E) {
+        self.app.world_mut().send_event(event);
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.app.world().resource::<R>()
+    }
+
+    pub fn phase(&self) -> GamePhase {
+        *self.resource::<State<GamePhase>>().get()
+    }
+
+    pub fn save_directory(&self) -> &PathBuf {
+        &self.save_directory
+    }
+
+    pub fn player(&mut self) -> Entity {
+        self.app
+            .world_mut()
+            .query_filtered::<Entity, With<Player>>()
+            .single(self.app.world())
+            .expect("player should be spawned")
+    }
+
+    pub fn player_component<C: Component + Clone>(&mut self) -> C {
+        let player = self.player();
+        self.component::<C>(player)
+    }
+
+    pub fn component<C: Component + Clone>(&self, entity: Entity) -> C {
+        self.app
+            .world()
+            .get::<C>(entity)
+            .cloned()
+            .unwrap_or_else(|| panic!("{entity} is missing {}", std::any::type_name::<C>()))
+    }
+
+    pub fn player_position(&mut self) -> Vec3 {
+        self.player_component::<Transform>().translation
+    }
+
+    /// 讓玩家倒下，推進到在入口重生為止
+    pub fn kill_and_respawn_player(&mut self) {
+        let player = self.player();
+        self.app
+            .world_mut()
+            .get_mut::<Health>(player)
+            .unwrap()
+            .current = 0;
+        self.app.world_mut().entity_mut(player).insert(PlayerDead);
+        self.send(PlayerDiedEvent);
+        assert!(
+            self.step_until(|world| world.get::<PlayerDead>(player).is_none()),
+            "player never respawned"
+        );
+    }
+
+    /// 依 archetype id 在指定位置生成一隻敵人（不屬於關卡，不會被換關清除）
+    pub fn spawn_enemy(&mut self, archetype_id: &str, position: Vec3) -> Entity {
+        let archetype = self
+            .resource::<EnemyArchetypeRegistry>()
+            .get(archetype_id)
+            .unwrap_or_else(|| panic!("unknown archetype {archetype_id}"))
+            .clone();
+
+        let entity = self
+            .app
+            .world_mut()
+            .run_system_once(
+                move |mut commands: Commands, asset_server: Res<AssetServer>| {
+                    spawn_enemy_from_archetype(
+                        &mut commands,
+                        &asset_server,
+                        &archetype,
+                        EnemyPlacement::at(position, 0.0, 1.0),
+                        EnemySpawnStats::from_archetype(&archetype),
+                    )
+                },
+            )
+            .expect("spawning an enemy should not fail");
+        self.step();
+        entity
+    }
+
+    /// 在離玩家 `offset` 的位置生成敵人，預設放在攻擊與警戒範圍之外
+    pub fn spawn_enemy_away(&mut self, archetype_id: &str, offset: Vec2) -> Entity {
+        let position = self.player_position() + offset.extend(0.0);
+        self.spawn_enemy(archetype_id, position)
+    }
+
+    /// 在武器揮擊範圍正中央（玩家右方）生成敵人
+    pub fn spawn_enemy_in_reach(&mut self, archetype_id: &str) -> Entity {
+        let reticle = self
+            .app
+            .world_mut()
+            .query_filtered::<&Transform, With<AttackReticle>>()
+            .single(self.app.world())
+            .expect("attack reticle should be spawned")
+            .translation;
+        self.spawn_enemy(
+            archetype_id,
+            Vec3::new(reticle.x + PLAYER_ATTACK_RADIUS * 0.25, reticle.y, 9.0),
+        )
+    }
+
+    pub fn enemies_with_archetype(&mut self, archetype_id: &str) -> Vec<Entity> {
+        self.app
+            .world_mut()
+            .query::<(Entity, &EnemyArchetypeId)>()
+            .iter(self.app.world())
+            .filter(|(_, id)| id.0 == archetype_id)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// 送出一次攻擊輸入並推進一個影格
+    pub fn attack(&mut self) {
+        self.send(AttackInputEvent);
+        self.step();
+    }
+
+    pub fn set_key(&mut self, key_code: KeyCode, pressed: bool) {
+        self.send(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state: if pressed {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Released
+            },
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+
+    /// 按下並放開一次鍵盤按鍵，按下的那個影格會執行遊戲邏輯
+    pub fn tap_key(&mut self, key_code: KeyCode) {
+        self.set_key(key_code, true);
+        self.step();
+        self.set_key(key_code, false);
+        self.step();
+    }
+
+    /// 模擬點擊一個選單按鈕
+    pub fn click_button(&mut self, button: Entity) {
+        *self
+            .app
+            .world_mut()
+            .get_mut::<Interaction>(button)
+            .expect("entity should be a button") = Interaction::Pressed;
+        self.step();
+    }
+
+    /// 接上一支虛擬手把，之後以 raw 事件模擬按鍵與搖桿
+    pub fn connect_gamepad(&mut self) -> Entity {
+        let gamepad = self.app.world_mut().spawn_empty().id();
+        self.send(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected {
+                name: "Test gamepad".to_string(),
+                vendor_id: None,
+                product_id: None,
+            },
+        ));
+        self.step();
+        gamepad
+    }
+
+    pub fn set_gamepad_button(&mut self, gamepad: Entity, button: GamepadButton, value: f32) {
+        self.send(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad, button, value,
+        )));
+    }
+
+    pub fn set_gamepad_axis(&mut self, gamepad: Entity, axis: GamepadAxis, value: f32) {
+        self.send(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+            gamepad, axis, value,
+        )));
+    }
+
+    /// 按下並放開一次手把按鍵，按下的那個影格會執行遊戲邏輯
+    pub fn tap_gamepad_button(&mut self, gamepad: Entity, button: GamepadButton) {
+        self.set_gamepad_button(gamepad, button, 1.0);
+        self.step();
+        self.set_gamepad_button(gamepad, button, 0.0);
+        self.step();
+    }
+
+    pub fn wait_for_level(&mut self) {
+        let loaded = self.step_until(|world| {
+            let context = world.resource::<LevelBuildContext>();
+            context.pending_layout.is_none()
+                && context.pending_finalize.is_none()
+                && world.get_resource::<EntranceLocation>().is_some()
+        });
+        assert!(loaded, "level did not finish loading");
+    }
+
+    pub fn wait_for_weapon_ready(&mut self) {
+        let ready = self.step_until(|world| {
+            world
+                .query_filtered::<&WeaponSwing, With<Weapon>>()
+                .iter(world)
+                .all(|swing| swing.timer.finished())
+        });
+        assert!(ready, "weapon swing never finished");
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.save_directory);
+    }
+}
+
+/// 只有一個矩形房間、沒有道具的測試關卡
+pub fn test_level(name: &str, enemies: &[(&str, usize)]) -> LevelDefinition {
+    let mut enemy_counts = EnemyCounts::default();
+    for (id, count) in enemies {
+        enemy_counts.set(*id, *count);
+    }
+
+    LevelDefinition {
+        index: 0,
+        name: name.to_string(),
+        layout: RoomLayout::Rectangle {
+            width: 14,
+            height: 10,
+        },
+        enemy_counts,
+        prop_plan: PropPlan {
+            trees: 0,
+            rocks: 0,
+            crates: 0,
+        },
+        seed: 7,
+        boss_loot: Vec::new(),
+        boss_stat_multiplier: 1.0,
+        encounter: None,
+    }
+}
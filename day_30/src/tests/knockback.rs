@@ -0,0 +1,90 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::DamageRequest;
+use bevy::prelude::*;
+
+#[test]
+fn melee_hit_pushes_enemy_away_and_stuns_it() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+    let player_x = game.player_position().x;
+    let starting_gap = game.component::<Transform>(slime).translation.x - player_x;
+
+    game.attack();
+    game.step();
+
+    assert!(game.world().get::<HitStun>(slime).is_some());
+    game.step_frames(5);
+    let gap = game.component::<Transform>(slime).translation.x - player_x;
+    assert!(gap > starting_gap, "gap {gap} should exceed {starting_gap}");
+}
+
+#[test]
+fn heavier_enemies_are_pushed_less() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_away(
+        "slime",
+        Vec2::new(-ROOM_TILE_SIZE * 3.0, ROOM_TILE_SIZE * 4.0),
+    );
+    let cyclops = game.spawn_enemy_away(
+        "cyclops",
+        Vec2::new(ROOM_TILE_SIZE * 3.0, ROOM_TILE_SIZE * 4.0),
+    );
+
+    for target in [slime, cyclops] {
+        game.send(
+            DamageRequest::new(target, 20, DamageType::Physical)
+                .with_knockback(Vec2::X * PLAYER_ATTACK_KNOCKBACK),
+        );
+    }
+    game.step();
+
+    let slime_speed = game.component::<Knockback>(slime).velocity.length();
+    let cyclops_speed = game.component::<Knockback>(cyclops).velocity.length();
+    assert!(slime_speed > cyclops_speed);
+}
+
+#[test]
+fn stunned_enemy_stops_chasing() {
+    let mut game = TestApp::training_hall();
+    let player_position = game.player_position();
+    let slime = game.spawn_enemy(
+        "slime",
+        player_position + Vec3::new(ROOM_TILE_SIZE * 2.0, 0.0, 0.0),
+    );
+    game.world_mut().entity_mut(slime).insert(HitStun::new(1.0));
+    let position = game.component::<Transform>(slime).translation;
+
+    game.step_frames(5);
+
+    assert_eq!(game.component::<Transform>(slime).translation, position);
+}
+
+#[test]
+fn knockback_does_not_push_player_through_walls() {
+    let mut game = TestApp::training_hall();
+    let wall_x = game
+        .world_mut()
+        .query::<(&Transform, &RoomTile)>()
+        .iter(game.world())
+        .filter(|(_, tile)| tile.tile_type == RoomTileType::WallESide)
+        .map(|(transform, _)| transform.translation.x)
+        .fold(f32::MIN, f32::max);
+
+    let player = game.player();
+    let tile_span = ROOM_TILE_SIZE * PLAYER_SCALE;
+    game.world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation
+        .x = wall_x - tile_span * 1.5;
+
+    game.send(
+        DamageRequest::new(player, 1, DamageType::Physical)
+            .with_knockback(Vec2::X * ENEMY_CONTACT_KNOCKBACK * 5.0),
+    );
+    game.step_frames(30);
+
+    assert!(game.player_position().x < wall_x - tile_span * 0.5);
+}
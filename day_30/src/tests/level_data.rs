@@ -0,0 +1,57 @@
+use super::harness::{TestApp, test_level};
+use crate::components::level::DataErrorScreen;
+use crate::constants::{ENEMY_ARCHETYPES_PATH, LEVEL_CAMPAIGN_PATH};
+use crate::resources::*;
+
+fn data_error_message(game: &mut TestApp) -> Option<String> {
+    game.world_mut()
+        .query::<&DataErrorScreen>()
+        .iter(game.world())
+        .next()
+        .map(|screen| screen.message.clone())
+}
+
+#[test]
+fn missing_level_data_is_shown_instead_of_a_blank_screen() {
+    // 測試用的資產資料夾不存在，關卡清單檔一定讀取失敗；注入的關卡讓畫面照常運作
+    let mut game = TestApp::training_hall();
+    assert!(game.step_until(|world| world.resource::<LevelState>().load_error().is_some()));
+    game.step();
+    assert_eq!(data_error_message(&mut game), None);
+
+    game.world_mut()
+        .resource_mut::<LevelState>()
+        .replace_definitions(Vec::new());
+    game.step();
+
+    let message = data_error_message(&mut game).expect("error screen should be shown");
+    assert!(message.contains(LEVEL_CAMPAIGN_PATH), "{message}");
+
+    // 資料修好重新載入後就移除
+    game.world_mut()
+        .resource_mut::<LevelState>()
+        .replace_definitions(vec![test_level("Training Hall", &[])]);
+    game.step();
+    assert_eq!(data_error_message(&mut game), None);
+}
+
+#[test]
+fn missing_enemy_data_is_shown_instead_of_a_blank_screen() {
+    let mut game = TestApp::training_hall();
+    assert!(game.step_until(|world| {
+        world
+            .resource::<EnemyArchetypeRegistry>()
+            .load_error()
+            .is_some()
+    }));
+    game.step();
+    assert_eq!(data_error_message(&mut game), None);
+
+    game.world_mut()
+        .resource_mut::<EnemyArchetypeRegistry>()
+        .replace(Vec::new());
+    game.step();
+
+    let message = data_error_message(&mut game).expect("error screen should be shown");
+    assert!(message.contains(ENEMY_ARCHETYPES_PATH), "{message}");
+}
@@ -0,0 +1,195 @@
+use super::harness::{FRAME_SECONDS, TestApp};
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::ShieldEquipEvent;
+use bevy::prelude::*;
+
+#[test]
+fn melee_attack_damages_enemy_in_reach() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+
+    let player_attack = game.player_component::<Attack>().value();
+    let slime_defense = game.component::<Defense>(slime).value();
+    let starting_health = game.component::<Health>(slime).current;
+
+    game.attack();
+
+    let expected = compute_damage(player_attack, Some(slime_defense));
+    assert!(expected > 0);
+    assert_eq!(
+        game.component::<Health>(slime).current,
+        starting_health - expected
+    );
+}
+
+#[test]
+fn melee_attack_ignores_enemy_behind_player() {
+    let mut game = TestApp::training_hall();
+    let player_position = game.player_position();
+    let slime = game.spawn_enemy(
+        "slime",
+        Vec3::new(
+            player_position.x - ATTACK_RETICLE_DISTANCE,
+            player_position.y,
+            9.0,
+        ),
+    );
+    let starting_health = game.component::<Health>(slime).current;
+
+    game.attack();
+
+    assert_eq!(game.component::<Health>(slime).current, starting_health);
+}
+
+#[test]
+fn attack_is_canceled_without_stamina() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+    let starting_health = game.component::<Health>(slime).current;
+
+    let player = game.player();
+    game.world_mut().get_mut::<Stamina>(player).unwrap().current = 0.0;
+
+    game.attack();
+
+    assert_eq!(game.component::<Health>(slime).current, starting_health);
+}
+
+/// 生成一隻不會死也不會移動的史萊姆，方便觀察連續命中
+fn spawn_training_dummy(game: &mut TestApp, position: Option<Vec3>) -> Entity {
+    let slime = match position {
+        Some(position) => game.spawn_enemy("slime", position),
+        None => game.spawn_enemy_in_reach("slime"),
+    };
+    let mut entity = game.world_mut().entity_mut(slime);
+    entity.get_mut::<Health>().unwrap().current = 10_000;
+    entity.insert(HitStun::new(60.0));
+    slime
+}
+
+#[test]
+fn consecutive_attacks_advance_the_combo() {
+    let mut game = TestApp::training_hall();
+    let slime = spawn_training_dummy(&mut game, None);
+    let player_attack = game.player_component::<Attack>().value();
+    let slime_defense = game.component::<Defense>(slime).value();
+
+    let slime_position = game.component::<Transform>(slime).translation;
+
+    let mut damage_per_step = Vec::new();
+    for step in 0..PLAYER_COMBO_LENGTH {
+        assert_eq!(game.player_component::<MeleeCombo>().step, step);
+        // 擊退會把史萊姆推開，每段之前放回原位
+        game.world_mut()
+            .get_mut::<Transform>(slime)
+            .unwrap()
+            .translation = slime_position;
+        let before = game.component::<Health>(slime).current;
+        game.attack();
+        damage_per_step.push(before - game.component::<Health>(slime).current);
+        game.wait_for_weapon_ready();
+    }
+
+    for (step, damage) in damage_per_step.iter().enumerate() {
+        let swing = MeleeSwingSpec::combo_step(step, WeaponFamily::Sword);
+        assert_eq!(
+            *damage,
+            compute_damage(swing.damage(player_attack), Some(slime_defense))
+        );
+    }
+    assert!(damage_per_step[2] > damage_per_step[0]);
+    assert_eq!(game.player_component::<MeleeCombo>().step, 0);
+}
+
+#[test]
+fn combo_resets_when_the_window_expires() {
+    let mut game = TestApp::training_hall();
+    game.wait_for_weapon_ready();
+
+    game.attack();
+    assert_eq!(game.player_component::<MeleeCombo>().step, 1);
+
+    let window = PLAYER_COMBO_SWING_SECONDS[0] + PLAYER_COMBO_WINDOW_SECONDS;
+    game.step_frames((window as f64 / FRAME_SECONDS) as usize + 2);
+    assert_eq!(game.player_component::<MeleeCombo>().step, 0);
+}
+
+#[test]
+fn holding_attack_releases_a_wider_heavy_attack() {
+    let mut game = TestApp::training_hall();
+    game.wait_for_weapon_ready();
+    let reticle = game
+        .world_mut()
+        .query_filtered::<&Transform, With<AttackReticle>>()
+        .single(game.world())
+        .unwrap()
+        .translation;
+    let distance = (PLAYER_ATTACK_RADIUS + PLAYER_HEAVY_ATTACK_RADIUS) * 0.5;
+    let slime = spawn_training_dummy(
+        &mut game,
+        Some(Vec3::new(reticle.x + distance, reticle.y, 9.0)),
+    );
+    let starting_health = game.component::<Health>(slime).current;
+
+    // 按下時的輕攻擊搆不到，蓄力期間也不會造成傷害
+    game.set_key(KeyCode::KeyJ, true);
+    game.step_frames((PLAYER_HEAVY_ATTACK_CHARGE_SECONDS as f64 / FRAME_SECONDS) as usize + 2);
+    assert!(game.player_component::<MeleeCombo>().is_charged());
+    assert_eq!(game.component::<Health>(slime).current, starting_health);
+
+    game.set_key(KeyCode::KeyJ, false);
+    game.step();
+
+    assert!(
+        game.player_component::<MeleeCombo>()
+            .charge_seconds
+            .is_none()
+    );
+    assert!(game.component::<Health>(slime).current < starting_health);
+}
+
+#[test]
+fn releasing_early_does_not_trigger_a_heavy_attack() {
+    let mut game = TestApp::training_hall();
+    game.wait_for_weapon_ready();
+
+    game.set_key(KeyCode::KeyJ, true);
+    game.step_frames(5);
+    game.set_key(KeyCode::KeyJ, false);
+    game.step();
+    game.wait_for_weapon_ready();
+
+    // 重擊會把連擊重設回第一段
+    let combo = game.player_component::<MeleeCombo>();
+    assert!(combo.charge_seconds.is_none());
+    assert_eq!(combo.step, 1);
+}
+
+#[test]
+fn raising_the_shield_cancels_a_charged_attack() {
+    let mut game = TestApp::training_hall();
+    game.send(ShieldEquipEvent {
+        kind: ShieldKind::Level1,
+    });
+    game.wait_for_weapon_ready();
+    let slime = game.spawn_enemy_in_reach("slime");
+
+    // 按下時的輕攻擊可能已經打中，之後舉盾放開不該再放出重擊
+    game.set_key(KeyCode::KeyJ, true);
+    game.step_frames((PLAYER_HEAVY_ATTACK_CHARGE_SECONDS as f64 / FRAME_SECONDS) as usize + 2);
+    assert!(game.player_component::<MeleeCombo>().is_charged());
+    let charged_health = game.component::<Health>(slime).current;
+
+    game.set_key(KeyCode::KeyQ, true);
+    game.step();
+    game.set_key(KeyCode::KeyJ, false);
+    game.step_frames(10);
+
+    assert!(
+        game.player_component::<MeleeCombo>()
+            .charge_seconds
+            .is_none()
+    );
+    assert_eq!(game.component::<Health>(slime).current, charged_health);
+}
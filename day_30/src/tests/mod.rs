@@ -0,0 +1,29 @@
+// 不開視窗的整合測試：用 MinimalPlugins 跑遊戲邏輯，資產改由測試直接注入
+mod harness;
+
+mod awareness;
+mod block;
+mod boss_phases;
+mod boss_rewards;
+mod collision;
+mod combat_text;
+mod controls;
+mod damage;
+mod dodge;
+mod encounters;
+mod endless;
+mod gamepad;
+mod knockback;
+mod level_data;
+mod melee;
+mod navigation;
+mod pause;
+mod progression;
+mod ranged;
+mod respawn;
+mod save;
+mod save_migration;
+mod status_effects;
+mod tile_map;
+mod weapons;
+mod world_snapshot;
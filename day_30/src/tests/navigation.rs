@@ -0,0 +1,138 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::NavigationGrid;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+const TILE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;
+
+fn grid_from(rows: &[&str]) -> NavigationGrid {
+    let mut walkable = HashSet::new();
+    for (y, row) in rows.iter().rev().enumerate() {
+        for (x, cell) in row.chars().enumerate() {
+            if cell == '.' {
+                walkable.insert((x as i32, y as i32));
+            }
+        }
+    }
+    NavigationGrid::new(TILE, walkable)
+}
+
+fn at(x: i32, y: i32) -> Vec2 {
+    Vec2::new(x as f32, y as f32) * TILE
+}
+
+#[test]
+fn paths_route_around_walls_without_cutting_corners() {
+    let grid = grid_from(&[
+        ".....", //
+        ".###.", //
+        ".#...", //
+        ".#.#.", //
+        "...#.", //
+    ]);
+    let from = at(2, 1);
+    let to = at(4, 0);
+
+    let path = grid.find_path(from, to).expect("goal should be reachable");
+    assert_eq!(*path.last().unwrap(), to);
+
+    let mut previous = from;
+    for point in &path {
+        assert!(
+            grid.has_clear_corridor(previous, *point, grid.body_half_width()),
+            "segment {previous} -> {point} clips a wall"
+        );
+        previous = *point;
+    }
+}
+
+#[test]
+fn unreachable_goals_have_no_path() {
+    let grid = grid_from(&[
+        "..#..", //
+        "..#..", //
+    ]);
+    assert!(grid.find_path(at(0, 0), at(4, 1)).is_none());
+    assert!(grid.find_path(at(0, 0), at(2, 0)).is_none());
+}
+
+#[test]
+fn level_load_builds_the_navigation_grid() {
+    let mut game = TestApp::training_hall();
+    let player = game.player_position().truncate();
+    let grid = game.resource::<NavigationGrid>();
+    assert!(!grid.is_empty());
+    assert!(grid.is_walkable(grid.cell(player)));
+
+    // 擋路的擺設生成後，所在的格子就不能走
+    let blocked = game.player_position() + Vec3::new(TILE * 2.0, TILE, 0.0);
+    game.world_mut().spawn((
+        EnvironmentProp::blocking(),
+        Transform::from_translation(blocked),
+    ));
+    game.step();
+    let grid = game.resource::<NavigationGrid>();
+    assert!(!grid.is_walkable(grid.cell(blocked.truncate())));
+}
+
+#[test]
+fn chasers_path_around_blocking_props() {
+    let mut game = TestApp::training_hall();
+    let player = game.player_position();
+    for dx in -1..=1 {
+        game.world_mut().spawn((
+            EnvironmentProp::blocking(),
+            Transform::from_translation(player + Vec3::new(dx as f32 * TILE, TILE * 2.0, 0.0)),
+        ));
+    }
+    let slime = game.spawn_enemy("slime", player + Vec3::new(0.0, TILE * 3.0, 0.0));
+
+    assert!(game.step_until(|world| {
+        world
+            .get::<EnemyPath>(slime)
+            .is_some_and(|path| path.waypoints.len() >= 2)
+    }));
+
+    let blocked: Vec<Vec2> = (-1..=1)
+        .map(|dx| (player + Vec3::new(dx as f32 * TILE, TILE * 2.0, 0.0)).truncate())
+        .collect();
+    let reached = game.step_until(|world| {
+        let position = world
+            .get::<Transform>(slime)
+            .unwrap()
+            .translation
+            .truncate();
+        assert!(
+            blocked
+                .iter()
+                .all(|prop| prop.distance(position) >= TILE * 0.5),
+            "slime walked through a prop at {position}"
+        );
+        position.distance(player.truncate()) < TILE
+    });
+    assert!(reached, "slime never reached the player");
+}
+
+#[test]
+fn patrol_routes_walk_waypoints_in_two_dimensions() {
+    let mut game = TestApp::training_hall();
+    let player = game.player_position();
+    let start = player + Vec3::new(-TILE * 3.0, TILE * 5.0, 0.0);
+    let slime = game.spawn_enemy("slime", start);
+    let waypoints = vec![
+        start.truncate(),
+        start.truncate() + Vec2::new(TILE * 2.0, 0.0),
+        start.truncate() + Vec2::new(TILE * 2.0, TILE * 2.0),
+    ];
+    game.world_mut()
+        .entity_mut(slime)
+        .insert(EnemyPatrolRoute::new(waypoints.clone()));
+
+    assert!(game.step_until(|world| world.get::<EnemyPatrolRoute>(slime).unwrap().index == 2));
+    assert!(game.step_until(|world| {
+        world.get::<Transform>(slime).unwrap().translation.y > waypoints[2].y - TILE * 0.5
+    }));
+    assert!((game.component::<Transform>(slime).translation.x - waypoints[2].x).abs() < TILE * 0.5);
+}
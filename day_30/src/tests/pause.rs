@@ -0,0 +1,77 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::resources::*;
+use bevy::prelude::*;
+
+fn pause(game: &mut TestApp) {
+    game.tap_key(KeyCode::Escape);
+    assert_eq!(game.phase(), GamePhase::Paused);
+}
+
+#[test]
+fn pausing_stops_virtual_time() {
+    let mut game = TestApp::training_hall();
+    assert!(!game.resource::<Time<Virtual>>().is_paused());
+
+    pause(&mut game);
+    assert!(game.resource::<Time<Virtual>>().is_paused());
+
+    game.tap_key(KeyCode::Escape);
+    assert_eq!(game.phase(), GamePhase::Playing);
+    assert!(!game.resource::<Time<Virtual>>().is_paused());
+}
+
+#[test]
+fn enemies_do_not_move_while_paused() {
+    let mut game = TestApp::training_hall();
+    let player = game.player_position();
+    let slime = game.spawn_enemy("slime", player + Vec3::new(120.0, 0.0, 0.0));
+
+    pause(&mut game);
+    let paused_at = game.component::<Transform>(slime).translation;
+    game.step_frames(60);
+    assert_eq!(game.component::<Transform>(slime).translation, paused_at);
+
+    game.tap_key(KeyCode::Escape);
+    game.step_frames(60);
+    assert_ne!(game.component::<Transform>(slime).translation, paused_at);
+}
+
+#[test]
+fn poison_does_not_tick_while_paused() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    game.world_mut()
+        .get_mut::<StatusEffects>(player)
+        .unwrap()
+        .apply(StatusEffectSpec::new(StatusEffectKind::Poison, 10.0, 1.0));
+    let starting_health = game.player_component::<Health>().current;
+
+    pause(&mut game);
+    game.step_frames(120);
+    assert_eq!(game.player_component::<Health>().current, starting_health);
+    assert!(
+        game.player_component::<StatusEffects>()
+            .has(StatusEffectKind::Poison)
+    );
+
+    game.tap_key(KeyCode::Escape);
+    game.step_frames(90);
+    assert!(game.player_component::<Health>().current < starting_health);
+}
+
+#[test]
+fn keys_pressed_while_paused_do_not_reach_gameplay() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+    let starting_health = game.component::<Health>(slime).current;
+    let start = game.player_position();
+
+    pause(&mut game);
+    game.tap_key(KeyCode::Space);
+    game.set_key(KeyCode::KeyD, true);
+    game.step_frames(20);
+
+    assert_eq!(game.component::<Health>(slime).current, starting_health);
+    assert_eq!(game.player_position(), start);
+}
@@ -0,0 +1,66 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::*;
+use bevy::prelude::*;
+
+#[test]
+fn defeating_an_enemy_grants_its_experience() {
+    let mut game = TestApp::training_hall();
+    let slime = game.spawn_enemy_in_reach("slime");
+    game.world_mut().get_mut::<Health>(slime).unwrap().current = 1;
+
+    game.attack();
+    game.step();
+
+    let progression = game.player_component::<PlayerProgression>();
+    assert_eq!(progression.level, 0);
+    assert_eq!(progression.experience, 30);
+}
+
+#[test]
+fn enough_experience_levels_up_and_raises_base_stats() {
+    let mut game = TestApp::training_hall();
+
+    game.send(EnemyDefeatedEvent {
+        position: Vec3::ZERO,
+        experience: PLAYER_LEVEL_XP_REQUIREMENTS[0] + 25,
+        enemy_name: "Training Dummy".to_string(),
+        is_boss: false,
+    });
+    game.step_frames(2);
+
+    let progression = game.player_component::<PlayerProgression>();
+    assert_eq!(progression.level, 1);
+    assert_eq!(progression.experience, 25);
+    assert_eq!(
+        game.player_component::<Attack>().base,
+        PLAYER_LEVEL_BASE_ATTACK[1]
+    );
+    assert_eq!(
+        game.player_component::<Defense>().base,
+        PLAYER_LEVEL_BASE_DEFENSE[1]
+    );
+}
+
+#[test]
+fn experience_stops_at_max_level() {
+    let mut game = TestApp::training_hall();
+    let total: u32 = PLAYER_LEVEL_XP_REQUIREMENTS.iter().sum();
+
+    game.send(EnemyDefeatedEvent {
+        position: Vec3::ZERO,
+        experience: total + 500,
+        enemy_name: "Training Dummy".to_string(),
+        is_boss: false,
+    });
+    game.step_frames(2);
+
+    let progression = game.player_component::<PlayerProgression>();
+    assert_eq!(progression.level, PLAYER_MAX_LEVEL);
+    assert_eq!(progression.experience, 0);
+    assert_eq!(
+        game.player_component::<Attack>().base,
+        PLAYER_LEVEL_BASE_ATTACK[PLAYER_MAX_LEVEL]
+    );
+}
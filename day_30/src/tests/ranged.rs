@@ -0,0 +1,110 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use bevy::prelude::*;
+
+fn equip(game: &mut TestApp, weapon: EquippedRangedWeapon) {
+    let player = game.player();
+    game.world_mut().entity_mut(player).insert(weapon);
+}
+
+fn projectile_count(game: &mut TestApp) -> usize {
+    game.world_mut()
+        .query_filtered::<Entity, With<PlayerProjectile>>()
+        .iter(game.world())
+        .count()
+}
+
+fn east_wall_x(game: &mut TestApp) -> f32 {
+    game.world_mut()
+        .query::<(&Transform, &RoomTile)>()
+        .iter(game.world())
+        .filter(|(_, tile)| tile.tile_type == RoomTileType::WallESide)
+        .map(|(transform, _)| transform.translation.x)
+        .fold(f32::MIN, f32::max)
+}
+
+#[test]
+fn bow_shot_damages_enemy_and_uses_an_arrow() {
+    let mut game = TestApp::training_hall();
+    equip(&mut game, EquippedRangedWeapon::new(RangedWeaponKind::Bow));
+    let player_position = game.player_position();
+    let slime = game.spawn_enemy(
+        "slime",
+        player_position + Vec3::new(ROOM_TILE_SIZE * PLAYER_SCALE * 2.0, 0.0, 0.0),
+    );
+    game.world_mut()
+        .entity_mut(slime)
+        .insert(HitStun::new(10.0));
+    let starting_health = game.component::<Health>(slime).current;
+    let defense = game.component::<Defense>(slime).value();
+    let attack = game.player_component::<Attack>().value();
+
+    game.tap_key(KeyCode::KeyF);
+    assert!(game.step_until(|world| world.get::<Health>(slime).unwrap().current < starting_health));
+
+    let expected = compute_damage(
+        (attack as f32 * RangedWeaponKind::Bow.damage_multiplier()).round() as i32,
+        Some(defense),
+    );
+    assert_eq!(
+        game.component::<Health>(slime).current,
+        starting_health - expected
+    );
+    assert_eq!(
+        game.player_component::<EquippedRangedWeapon>().ammo,
+        RangedWeaponKind::Bow.ammo_capacity().unwrap() - 1
+    );
+    assert_eq!(projectile_count(&mut game), 0);
+}
+
+#[test]
+fn empty_bow_does_not_fire() {
+    let mut game = TestApp::training_hall();
+    equip(
+        &mut game,
+        EquippedRangedWeapon::with_ammo(RangedWeaponKind::Bow, 0),
+    );
+
+    game.tap_key(KeyCode::KeyF);
+
+    assert_eq!(projectile_count(&mut game), 0);
+}
+
+#[test]
+fn staff_spends_stamina_instead_of_ammo() {
+    let mut game = TestApp::training_hall();
+    equip(
+        &mut game,
+        EquippedRangedWeapon::new(RangedWeaponKind::MagicStaff),
+    );
+
+    game.set_key(KeyCode::KeyF, true);
+    game.step();
+
+    assert_eq!(projectile_count(&mut game), 1);
+    assert!(game.player_component::<Stamina>().current < PLAYER_MAX_STAMINA);
+}
+
+#[test]
+fn projectiles_stop_at_walls() {
+    let mut game = TestApp::training_hall();
+    equip(&mut game, EquippedRangedWeapon::new(RangedWeaponKind::Bow));
+    let wall_x = east_wall_x(&mut game);
+    let player = game.player();
+    game.world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation
+        .x = wall_x - ROOM_TILE_SIZE * PLAYER_SCALE * 2.0;
+    game.step();
+
+    game.set_key(KeyCode::KeyF, true);
+    game.step();
+    game.set_key(KeyCode::KeyF, false);
+    assert_eq!(projectile_count(&mut game), 1);
+
+    // 存在時間約 70 個影格，提早消失代表被牆擋下
+    game.step_frames(20);
+    assert_eq!(projectile_count(&mut game), 0);
+}
@@ -0,0 +1,31 @@
+use super::harness::{TestApp, test_level};
+use crate::components::*;
+use crate::resources::EnemyArchetypeRegistry;
+
+#[test]
+fn respawn_roster_comes_from_the_bestiary() {
+    let game = TestApp::training_hall();
+    let roster: Vec<&str> = game
+        .resource::<EnemyArchetypeRegistry>()
+        .respawn_roster()
+        .map(|(archetype, _)| archetype.id.as_str())
+        .collect();
+    assert_eq!(roster, ["slime", "cyclops", "spider"]);
+}
+
+#[test]
+fn player_respawn_refills_missing_roster_enemies() {
+    let mut game = TestApp::new(vec![test_level("Training Hall", &[("slime", 1)])]);
+    assert_eq!(game.enemies_with_archetype("slime").len(), 1);
+    assert!(game.enemies_with_archetype("cyclops").is_empty());
+
+    game.kill_and_respawn_player();
+    game.step();
+
+    // 已在場上的敵人不會重複補回，沒有重生設定的頭目也不會出現
+    assert_eq!(game.enemies_with_archetype("slime").len(), 1);
+    assert_eq!(game.enemies_with_archetype("cyclops").len(), 1);
+    assert_eq!(game.enemies_with_archetype("spider").len(), 1);
+    assert!(game.enemies_with_archetype("wizard_boss").is_empty());
+    assert!(game.player_component::<Health>().current > 0);
+}
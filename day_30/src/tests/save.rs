@@ -0,0 +1,237 @@
+use super::harness::{TestApp, test_level};
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::game_session::{
+    RequestLoadGameEvent, RequestSaveGameEvent, StartEndlessRunEvent,
+};
+use crate::systems::save_slots::DeleteSaveSlotEvent;
+
+fn two_level_campaign() -> Vec<LevelDefinition> {
+    vec![
+        test_level("First Hall", &[]),
+        test_level("Second Hall", &[]),
+    ]
+}
+
+fn save(game: &mut TestApp) {
+    save_to(game, 0);
+}
+
+fn save_to(game: &mut TestApp, slot: usize) {
+    game.send(RequestSaveGameEvent { slot });
+    game.step();
+    assert!(
+        game.resource::<SaveStorage>().slot_file(slot).exists(),
+        "save file was not written"
+    );
+}
+
+fn load(game: &mut TestApp) {
+    load_from(game, 0);
+}
+
+fn load_from(game: &mut TestApp, slot: usize) {
+    game.send(RequestLoadGameEvent {
+        slot,
+        from_main_menu: false,
+    });
+    game.step();
+    game.wait_for_level();
+}
+
+fn slot_data(game: &TestApp, slot: usize) -> GameSaveData {
+    match game.resource::<SaveStorage>().read_slot(slot) {
+        SaveSlotState::Occupied(data) => *data,
+        other => panic!("slot {slot} should hold a save, found {other:?}"),
+    }
+}
+
+#[test]
+fn save_and_load_restores_player_and_level() {
+    let mut game = TestApp::new(two_level_campaign());
+    let player = game.player();
+
+    {
+        let world = game.world_mut();
+        world.get_mut::<Health>(player).unwrap().current = 42;
+        let mut progression = world.get_mut::<PlayerProgression>(player).unwrap();
+        progression.level = 1;
+        progression.experience = 75;
+        world.resource_mut::<LevelState>().set_current_index(1);
+    }
+    save(&mut game);
+
+    {
+        let world = game.world_mut();
+        world.get_mut::<Health>(player).unwrap().current = 5;
+        let mut progression = world.get_mut::<PlayerProgression>(player).unwrap();
+        progression.level = 0;
+        progression.experience = 0;
+        world.resource_mut::<LevelState>().set_current_index(0);
+    }
+    load(&mut game);
+
+    let health = game.player_component::<Health>();
+    let progression = game.player_component::<PlayerProgression>();
+    assert_eq!(health.current, 42);
+    assert_eq!(progression.level, 1);
+    assert_eq!(progression.experience, 75);
+    assert_eq!(
+        game.player_component::<Attack>().base,
+        progression.base_attack()
+    );
+    assert_eq!(game.resource::<LevelState>().current_index(), 1);
+    assert_eq!(game.phase(), GamePhase::Playing);
+}
+
+#[test]
+fn save_and_load_restores_equipment() {
+    let mut game = TestApp::new(two_level_campaign());
+    let player = game.player();
+
+    game.world_mut()
+        .entity_mut(player)
+        .insert(EquippedShield::new(ShieldKind::Level2));
+    *game.world_mut().get_mut::<EquippedWeapon>(player).unwrap() =
+        EquippedWeapon::new(WeaponKind::Level3, WeaponFamily::Hammer);
+    save(&mut game);
+
+    game.world_mut()
+        .entity_mut(player)
+        .remove::<EquippedShield>();
+    load(&mut game);
+    game.step();
+
+    let weapon = game.player_component::<EquippedWeapon>();
+    assert_eq!(weapon.kind, WeaponKind::Level3);
+    assert_eq!(weapon.family, WeaponFamily::Hammer);
+    assert_eq!(
+        game.player_component::<EquippedShield>().kind,
+        ShieldKind::Level2
+    );
+}
+
+#[test]
+fn save_and_load_restores_ranged_weapon_and_ammo() {
+    let mut game = TestApp::new(two_level_campaign());
+    let player = game.player();
+
+    game.world_mut()
+        .entity_mut(player)
+        .insert(EquippedRangedWeapon::with_ammo(RangedWeaponKind::Bow, 7));
+    save(&mut game);
+
+    game.world_mut()
+        .entity_mut(player)
+        .insert(EquippedRangedWeapon::new(RangedWeaponKind::MagicStaff));
+    load(&mut game);
+    game.step();
+
+    let ranged = game.player_component::<EquippedRangedWeapon>();
+    assert_eq!(ranged.kind, RangedWeaponKind::Bow);
+    assert_eq!(ranged.ammo, 7);
+}
+
+#[test]
+fn save_and_load_keeps_the_endless_run_seed() {
+    let mut game = TestApp::new(two_level_campaign());
+    game.send(StartEndlessRunEvent);
+    game.step();
+    game.wait_for_level();
+
+    let run_seed = game
+        .resource::<LevelState>()
+        .endless_run_seed()
+        .expect("endless run should have a seed");
+    save(&mut game);
+
+    game.world_mut()
+        .resource_mut::<LevelState>()
+        .start_campaign();
+    load(&mut game);
+
+    let level_state = game.resource::<LevelState>();
+    assert!(level_state.is_endless());
+    assert_eq!(level_state.endless_run_seed(), Some(run_seed));
+}
+
+#[test]
+fn loading_without_a_save_leaves_progress_untouched() {
+    let mut game = TestApp::new(two_level_campaign());
+    let player = game.player();
+    game.world_mut().get_mut::<Health>(player).unwrap().current = 33;
+
+    game.send(RequestLoadGameEvent {
+        slot: 0,
+        from_main_menu: false,
+    });
+    game.step();
+
+    assert!(!game.save_directory().exists());
+    assert_eq!(game.player_component::<Health>().current, 33);
+}
+
+#[test]
+fn save_slots_are_independent() {
+    let mut game = TestApp::new(two_level_campaign());
+    let player = game.player();
+
+    game.world_mut().get_mut::<Health>(player).unwrap().current = 40;
+    save_to(&mut game, 0);
+
+    game.world_mut().get_mut::<Health>(player).unwrap().current = 20;
+    game.world_mut()
+        .resource_mut::<LevelState>()
+        .set_current_index(1);
+    save_to(&mut game, 2);
+
+    assert!(matches!(
+        game.resource::<SaveStorage>().read_slot(1),
+        SaveSlotState::Empty
+    ));
+
+    load_from(&mut game, 0);
+    assert_eq!(game.player_component::<Health>().current, 40);
+    assert_eq!(game.resource::<LevelState>().current_index(), 0);
+
+    load_from(&mut game, 2);
+    assert_eq!(game.player_component::<Health>().current, 20);
+    assert_eq!(game.resource::<LevelState>().current_index(), 1);
+}
+
+#[test]
+fn deleting_a_slot_only_removes_that_save() {
+    let mut game = TestApp::new(two_level_campaign());
+    save_to(&mut game, 0);
+    save_to(&mut game, 1);
+
+    game.send(DeleteSaveSlotEvent { slot: 0 });
+    game.step();
+
+    let storage = game.resource::<SaveStorage>();
+    assert!(matches!(storage.read_slot(0), SaveSlotState::Empty));
+    assert!(matches!(storage.read_slot(1), SaveSlotState::Occupied(_)));
+}
+
+#[test]
+fn save_slot_records_metadata() {
+    let mut game = TestApp::new(two_level_campaign());
+    let player = game.player();
+    game.world_mut()
+        .get_mut::<PlayerProgression>(player)
+        .unwrap()
+        .level = 3;
+    game.world_mut()
+        .get_mut::<EquippedWeapon>(player)
+        .unwrap()
+        .kind = WeaponKind::Level2;
+    game.step_frames(30);
+    save(&mut game);
+
+    let data = slot_data(&game, 0);
+    assert_eq!(data.level_name, "First Hall");
+    assert_eq!(data.player_level, 3);
+    assert_eq!(data.equipped_weapon, Some(WeaponKind::Level2));
+    assert!(data.saved_at_unix_seconds > 0);
+    assert!(data.playtime_seconds > 0.0);
+}
@@ -0,0 +1,291 @@
+use super::harness::{TestApp, test_level};
+use crate::components::*;
+use crate::resources::*;
+use crate::systems::game_session::{RequestLoadGameEvent, RequestSaveGameEvent};
+use bevy::prelude::*;
+use std::fs;
+
+const V1_SAVE: &str = r#"{
+  "version": 1,
+  "level_index": 1,
+  "player_health": 37,
+  "player_max_health": 100,
+  "player_level": 2,
+  "player_experience": 15,
+  "equipped_weapon": "Level2",
+  "equipped_shield": null
+}"#;
+
+const V5_SAVE: &str = r#"{
+  "version": 5,
+  "level_index": 0,
+  "player_health": 80,
+  "player_max_health": 100,
+  "player_level": 4,
+  "player_experience": 0,
+  "equipped_weapon": "Level5",
+  "equipped_shield": null,
+  "equipped_ranged_weapon": null,
+  "ranged_ammo": 0,
+  "endless_run_seed": null,
+  "saved_at_unix_seconds": 0,
+  "level_name": "First Hall",
+  "playtime_seconds": 0.0,
+  "world": null
+}"#;
+
+fn two_level_campaign() -> Vec<LevelDefinition> {
+    vec![
+        test_level("First Hall", &[]),
+        test_level("Second Hall", &[]),
+    ]
+}
+
+fn write_raw_slot(game: &TestApp, slot: usize, contents: &str) {
+    fs::create_dir_all(game.save_directory()).unwrap();
+    fs::write(game.resource::<SaveStorage>().slot_file(slot), contents).unwrap();
+}
+
+fn load(game: &mut TestApp, slot: usize) {
+    game.send(RequestLoadGameEvent {
+        slot,
+        from_main_menu: false,
+    });
+    game.step();
+}
+
+#[test]
+fn v1_save_is_migrated_and_backed_up() {
+    let mut game = TestApp::new(two_level_campaign());
+    write_raw_slot(&game, 0, V1_SAVE);
+
+    load(&mut game, 0);
+    game.wait_for_level();
+
+    assert_eq!(game.player_component::<Health>().current, 37);
+    assert_eq!(game.player_component::<PlayerProgression>().level, 2);
+    assert_eq!(game.resource::<LevelState>().current_index(), 1);
+
+    let storage = game.resource::<SaveStorage>();
+    let backup = fs::read_to_string(storage.backup_file(0, 1)).expect("backup should exist");
+    assert_eq!(backup, V1_SAVE);
+
+    let upgraded = fs::read_to_string(storage.slot_file(0)).unwrap();
+    let migrated = migrate_save(&upgraded).unwrap();
+    assert!(!migrated.was_migrated());
+    assert_eq!(migrated.data.version, GameSaveData::CURRENT_VERSION);
+    assert_eq!(migrated.data.equipped_weapon, Some(WeaponKind::Level2));
+    assert_eq!(
+        migrated.data.equipped_weapon_family,
+        Some(WeaponFamily::Sword)
+    );
+}
+
+#[test]
+fn v5_weapon_stays_a_sword() {
+    let mut game = TestApp::new(two_level_campaign());
+    write_raw_slot(&game, 0, V5_SAVE);
+
+    load(&mut game, 0);
+    game.wait_for_level();
+
+    let weapon = game.player_component::<EquippedWeapon>();
+    assert_eq!(weapon.kind, WeaponKind::Level5);
+    assert_eq!(weapon.family, WeaponFamily::Sword);
+}
+
+#[test]
+fn previewing_an_old_save_does_not_touch_the_file() {
+    let game = TestApp::new(two_level_campaign());
+    write_raw_slot(&game, 0, V1_SAVE);
+
+    let storage = game.resource::<SaveStorage>();
+    let SaveSlotState::Occupied(data) = storage.read_slot(0) else {
+        panic!("v1 save should be readable");
+    };
+    assert_eq!(data.player_level, 2);
+    assert_eq!(data.display_level_name(), "Level 2");
+    assert_eq!(fs::read_to_string(storage.slot_file(0)).unwrap(), V1_SAVE);
+    assert!(!storage.backup_file(0, 1).exists());
+}
+
+#[test]
+fn newer_save_version_is_rejected() {
+    let mut game = TestApp::new(two_level_campaign());
+    let player = game.player();
+    game.world_mut().get_mut::<Health>(player).unwrap().current = 12;
+
+    let newer = V1_SAVE.replace(
+        "\"version\": 1",
+        &format!("\"version\": {}", GameSaveData::CURRENT_VERSION + 1),
+    );
+    write_raw_slot(&game, 0, &newer);
+
+    assert!(matches!(
+        game.resource::<SaveStorage>().read_slot(0),
+        SaveSlotState::Unreadable(SaveLoadError::NewerVersion { .. })
+    ));
+
+    load(&mut game, 0);
+    game.step();
+
+    assert_eq!(game.player_component::<Health>().current, 12);
+    let storage = game.resource::<SaveStorage>();
+    assert_eq!(fs::read_to_string(storage.slot_file(0)).unwrap(), newer);
+}
+
+#[test]
+fn save_without_version_is_rejected() {
+    let unversioned = V1_SAVE.replace("\"version\": 1,", "");
+    assert_eq!(
+        migrate_save(&unversioned).unwrap_err(),
+        SaveLoadError::MissingVersion
+    );
+}
+
+#[test]
+fn weapon_drops_without_a_family_keep_the_weapon_they_would_have_given() {
+    let mut game = TestApp::new(two_level_campaign());
+    game.send(RequestSaveGameEvent { slot: 0 });
+    game.step();
+    let saved = fs::read_to_string(game.resource::<SaveStorage>().slot_file(0)).unwrap();
+    let mut value: serde_json::Value = serde_json::from_str(&saved).unwrap();
+    value["world"]["pickups"] = serde_json::json!([
+        { "position": [0.0, 0.0, 0.0], "effect": { "EquipWeapon": "Level2" } }
+    ]);
+
+    let migrated_from = |value: &mut serde_json::Value, version: u32| {
+        value["version"] = serde_json::json!(version);
+        let migrated = migrate_save(&value.to_string()).unwrap();
+        migrated.data.world.unwrap().pickups[0].effect.clone()
+    };
+
+    // v7 到 v10 撿起時會換成該階級預設的種類
+    assert_eq!(
+        migrated_from(&mut value, 10),
+        PickupEffect::EquipWeapon {
+            kind: WeaponKind::Level2,
+            family: WeaponFamily::Dagger,
+        }
+    );
+    // 在那之前每一階都是劍
+    assert_eq!(
+        migrated_from(&mut value, 6),
+        PickupEffect::EquipWeapon {
+            kind: WeaponKind::Level2,
+            family: WeaponFamily::Sword,
+        }
+    );
+}
+
+#[test]
+fn v4_poison_becomes_a_status_effect() {
+    let mut game = TestApp::new(two_level_campaign());
+    game.send(RequestSaveGameEvent { slot: 0 });
+    game.step();
+
+    let storage = game.resource::<SaveStorage>();
+    let saved = fs::read_to_string(storage.slot_file(0)).unwrap();
+    let mut value: serde_json::Value = serde_json::from_str(&saved).unwrap();
+    value["version"] = serde_json::json!(4);
+    let player = value["world"]["player"].as_object_mut().unwrap();
+    player.remove("status_effects");
+    player.insert(
+        "poison".to_string(),
+        serde_json::json!({ "tick_seconds": 1.25, "elapsed_seconds": 0.5, "damage_per_tick": 3 }),
+    );
+
+    let migrated = migrate_save(&value.to_string()).unwrap();
+    assert_eq!(migrated.original_version, 4);
+    let effects = &migrated.data.world.unwrap().player.status_effects;
+    assert_eq!(effects.len(), 1);
+    assert_eq!(effects[0].kind, StatusEffectKind::Poison);
+    assert_eq!(effects[0].magnitude, 3.0);
+    assert_eq!(effects[0].tick_elapsed_seconds, 0.5);
+}
+
+#[test]
+fn each_step_only_fills_its_own_fields() {
+    let mut game = TestApp::new(two_level_campaign());
+    let position = game.player_position() + Vec3::new(-320.0, 256.0, 0.0);
+    game.spawn_enemy("slime", position);
+    game.send(RequestSaveGameEvent { slot: 0 });
+    game.step();
+    let saved = fs::read_to_string(game.resource::<SaveStorage>().slot_file(0)).unwrap();
+
+    // v8 的存檔已經有巡邏路徑，只缺最後看到玩家的位置與遭遇戰狀態
+    let mut value: serde_json::Value = serde_json::from_str(&saved).unwrap();
+    value["version"] = serde_json::json!(8);
+    let world_value = value["world"].as_object_mut().unwrap();
+    world_value.remove("encounter_cleared");
+    for enemy in world_value["enemies"].as_array_mut().unwrap() {
+        enemy.as_object_mut().unwrap().remove("last_known_position");
+    }
+    assert!(migrate_save(&value.to_string()).is_ok());
+
+    // 宣稱是 v8 卻少了 v8 才有的巡邏路徑，就不會被補上
+    for enemy in value["world"]["enemies"].as_array_mut().unwrap() {
+        enemy.as_object_mut().unwrap().remove("patrol_route");
+    }
+    assert!(matches!(
+        migrate_save(&value.to_string()),
+        Err(SaveLoadError::Parse(_))
+    ));
+}
+
+#[test]
+fn v5_enemy_snapshots_round_trip_to_the_current_version() {
+    let mut game = TestApp::new(two_level_campaign());
+    let start = game.player_position() + Vec3::new(-320.0, 256.0, 0.0);
+    let slime = game.spawn_enemy("slime", start);
+    let route = vec![start.truncate(), start.truncate() + Vec2::new(64.0, 0.0)];
+    let mut ai_state = EnemyAIState::new(EnemyBehaviorState::Patrolling);
+    ai_state.spot(start.truncate() + Vec2::new(0.0, 64.0));
+    ai_state.lose_sight();
+    game.world_mut()
+        .entity_mut(slime)
+        .insert((ai_state, EnemyPatrolRoute::new(route.clone())));
+
+    game.send(RequestSaveGameEvent { slot: 0 });
+    game.step();
+    let saved = fs::read_to_string(game.resource::<SaveStorage>().slot_file(0)).unwrap();
+
+    // 目前版本的存檔重新解析後內容不變
+    let current = migrate_save(&saved).unwrap();
+    assert!(!current.was_migrated());
+    let world = current.data.world.clone().unwrap();
+    let enemy = world
+        .enemies
+        .iter()
+        .find(|enemy| enemy.archetype_id == "slime")
+        .unwrap();
+    assert_eq!(enemy.ai_state, EnemyBehaviorState::Investigating);
+    assert!(enemy.last_known_position.is_some());
+    assert_eq!(enemy.patrol_route.len(), route.len());
+    let reparsed = migrate_save(&serde_json::to_string(&current.data).unwrap()).unwrap();
+    assert_eq!(reparsed.data.world, current.data.world);
+
+    // 退回 v5 的格式：沒有新欄位也沒有 Investigating
+    let mut value: serde_json::Value = serde_json::from_str(&saved).unwrap();
+    value["version"] = serde_json::json!(5);
+    let world_value = value["world"].as_object_mut().unwrap();
+    world_value.remove("encounter_cleared");
+    for enemy in world_value["enemies"].as_array_mut().unwrap() {
+        let enemy = enemy.as_object_mut().unwrap();
+        enemy.remove("last_known_position");
+        enemy.remove("patrol_route");
+        enemy.remove("status_effects");
+        enemy.insert("ai_state".to_string(), serde_json::json!("Patrolling"));
+    }
+
+    let migrated = migrate_save(&value.to_string()).unwrap();
+    assert_eq!(migrated.original_version, 5);
+    assert_eq!(migrated.data.version, GameSaveData::CURRENT_VERSION);
+    let world = migrated.data.world.unwrap();
+    assert!(!world.encounter_cleared);
+    for enemy in &world.enemies {
+        assert_eq!(enemy.last_known_position, None);
+        assert!(enemy.patrol_route.is_empty());
+        assert!(enemy.status_effects.is_empty());
+    }
+}
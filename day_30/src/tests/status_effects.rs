@@ -0,0 +1,155 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::StatusEffectRequest;
+use bevy::prelude::*;
+
+fn inflict(
+    game: &mut TestApp,
+    target: Entity,
+    kind: StatusEffectKind,
+    seconds: f32,
+    magnitude: f32,
+) {
+    game.send(StatusEffectRequest {
+        target,
+        spec: StatusEffectSpec::new(kind, seconds, magnitude),
+        incoming: Vec2::ZERO,
+    });
+    game.step();
+}
+
+fn distance_walked_right(game: &mut TestApp, frames: usize) -> f32 {
+    let start = game.player_position().x;
+    game.set_key(KeyCode::KeyD, true);
+    game.step_frames(frames);
+    game.set_key(KeyCode::KeyD, false);
+    game.step();
+    game.player_position().x - start
+}
+
+#[test]
+fn poison_stacks_to_its_limit_and_ticks_per_stack() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+
+    for _ in 0..STATUS_POISON_MAX_STACKS + 1 {
+        inflict(&mut game, player, StatusEffectKind::Poison, 10.0, 2.0);
+    }
+    let effects = game.player_component::<StatusEffects>();
+    let poison = effects.get(StatusEffectKind::Poison).unwrap();
+    assert_eq!(poison.stacks, STATUS_POISON_MAX_STACKS);
+
+    let starting_health = game.player_component::<Health>().current;
+    assert!(
+        game.step_until(|world| world.get::<Health>(player).unwrap().current < starting_health)
+    );
+    assert_eq!(
+        game.player_component::<Health>().current,
+        starting_health - 2 * STATUS_POISON_MAX_STACKS as i32
+    );
+}
+
+#[test]
+fn effects_expire_after_their_duration() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    let base_attack = game.player_component::<Attack>().value();
+
+    inflict(&mut game, player, StatusEffectKind::Weakness, 0.5, 0.5);
+    game.step();
+    assert_eq!(
+        game.player_component::<Attack>().value(),
+        (base_attack as f32 * 0.5).round() as i32
+    );
+
+    game.step_frames(40);
+    assert!(game.player_component::<StatusEffects>().is_empty());
+    assert_eq!(game.player_component::<Attack>().value(), base_attack);
+}
+
+#[test]
+fn immune_targets_and_invulnerable_players_ignore_effects() {
+    let mut game = TestApp::training_hall();
+    let player_position = game.player_position();
+    let spider = game.spawn_enemy(
+        "spider",
+        player_position + Vec3::new(ROOM_TILE_SIZE * 4.0, 0.0, 0.0),
+    );
+    let player = game.player();
+    game.world_mut()
+        .entity_mut(player)
+        .insert(Invulnerable::new(5.0));
+
+    inflict(&mut game, spider, StatusEffectKind::Poison, 5.0, 3.0);
+    inflict(&mut game, player, StatusEffectKind::Burn, 5.0, 3.0);
+
+    assert!(
+        !game
+            .component::<StatusEffects>(spider)
+            .has(StatusEffectKind::Poison)
+    );
+    assert!(game.player_component::<StatusEffects>().is_empty());
+}
+
+#[test]
+fn slow_and_freeze_limit_player_movement() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+
+    let normal = distance_walked_right(&mut game, 20);
+    inflict(&mut game, player, StatusEffectKind::Slow, 5.0, 0.5);
+    let slowed = distance_walked_right(&mut game, 20);
+    assert!(
+        (slowed - normal * 0.5).abs() < 1.0,
+        "slowed {slowed} should be half of {normal}"
+    );
+
+    inflict(&mut game, player, StatusEffectKind::Freeze, 1.0, 0.0);
+    assert!(game.world().get::<HitStun>(player).is_some());
+    assert_eq!(distance_walked_right(&mut game, 20), 0.0);
+}
+
+#[test]
+fn antidote_cures_only_poison() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    inflict(&mut game, player, StatusEffectKind::Poison, 10.0, 1.0);
+    inflict(&mut game, player, StatusEffectKind::Slow, 10.0, 0.2);
+
+    let position = game.player_position();
+    game.world_mut().spawn((
+        Pickup {
+            effect: PickupEffect::CurePoison,
+        },
+        Transform::from_translation(position),
+    ));
+    game.step();
+
+    let effects = game.player_component::<StatusEffects>();
+    assert!(!effects.has(StatusEffectKind::Poison));
+    assert!(effects.has(StatusEffectKind::Slow));
+}
+
+#[test]
+fn magic_staff_freezes_enemies_it_hits() {
+    let mut game = TestApp::training_hall();
+    let player = game.player();
+    game.world_mut()
+        .entity_mut(player)
+        .insert(EquippedRangedWeapon::new(RangedWeaponKind::MagicStaff));
+    let player_position = game.player_position();
+    let slime = game.spawn_enemy(
+        "slime",
+        player_position + Vec3::new(ROOM_TILE_SIZE * PLAYER_SCALE * 2.0, 0.0, 0.0),
+    );
+
+    game.tap_key(KeyCode::KeyF);
+    assert!(game.step_until(|world| {
+        world
+            .get::<StatusEffects>(slime)
+            .is_some_and(|effects| effects.has(StatusEffectKind::Freeze))
+    }));
+    game.step();
+    assert!(game.world().get::<HitStun>(slime).is_some());
+}
@@ -0,0 +1,86 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::resources::{NavigationGrid, TileMap};
+use crate::systems::DoorInteractionEvent;
+use bevy::prelude::*;
+
+const TILE: f32 = ROOM_TILE_SIZE * PLAYER_SCALE;
+
+fn tile(index: u32, x: i32, tile_type: RoomTileType) -> (Entity, Vec2, RoomTileType) {
+    (
+        Entity::from_raw(index),
+        Vec2::new(x as f32 * TILE, 0.0),
+        tile_type,
+    )
+}
+
+#[test]
+fn overlapping_tiles_resolve_to_a_single_tile_per_cell() {
+    let map = TileMap::from_tiles(
+        TILE,
+        [
+            // 走廊地板蓋在牆上：可以通行
+            tile(1, 0, RoomTileType::WallESide),
+            tile(2, 0, RoomTileType::Floor),
+            // 室外地板被牆蓋過：仍然是牆
+            tile(3, 1, RoomTileType::FloorOutdoor),
+            tile(4, 1, RoomTileType::WallSOuterMid),
+            tile(5, 2, RoomTileType::DoorOpen),
+        ],
+    );
+
+    assert!(map.is_walkable((0, 0)));
+    assert!(!map.blocks_projectile(Vec2::ZERO));
+    assert!(map.is_solid((1, 0)));
+    assert!(map.blocks_projectile(Vec2::new(TILE, 0.0)));
+    assert!(map.is_walkable((2, 0)));
+    assert!(
+        !map.is_walkable((3, 0)),
+        "cells without tiles are not walkable"
+    );
+}
+
+#[test]
+fn raycast_reports_the_first_blocking_point() {
+    let map = TileMap::from_tiles(
+        TILE,
+        [
+            tile(1, 0, RoomTileType::Floor),
+            tile(2, 1, RoomTileType::Floor),
+            tile(3, 2, RoomTileType::WallWSide),
+            tile(4, 3, RoomTileType::DoorClosed),
+        ],
+    );
+
+    let hit = map
+        .raycast(Vec2::ZERO, Vec2::new(TILE * 3.0, 0.0))
+        .expect("the wall should stop the ray");
+    assert_eq!(map.cell(hit), (2, 0));
+    assert!(map.raycast(Vec2::ZERO, Vec2::new(TILE, 0.0)).is_none());
+}
+
+#[test]
+fn opening_the_door_updates_the_tile_map_and_navigation() {
+    let mut game = TestApp::training_hall();
+    let (door, door_position) = game
+        .world_mut()
+        .query_filtered::<(Entity, &Transform), With<Door>>()
+        .iter(game.world())
+        .map(|(entity, transform)| (entity, transform.translation.truncate()))
+        .next()
+        .expect("the test room has an entrance door");
+
+    let player = game.player_position().truncate();
+    let map = game.resource::<TileMap>();
+    let door_cell = map.cell(door_position);
+    assert!(map.is_solid(door_cell));
+    assert!(map.is_walkable(map.cell(player)));
+
+    game.world_mut().send_event(DoorInteractionEvent);
+    game.step();
+    assert!(game.world().get::<Door>(door).unwrap().is_open);
+
+    assert!(game.resource::<TileMap>().is_walkable(door_cell));
+    assert!(game.resource::<NavigationGrid>().is_walkable(door_cell));
+}
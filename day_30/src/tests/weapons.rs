@@ -0,0 +1,215 @@
+use super::harness::TestApp;
+use crate::components::*;
+use crate::constants::*;
+use crate::systems::WeaponEquipEvent;
+use bevy::prelude::*;
+
+fn equip_weapon(game: &mut TestApp, kind: WeaponKind) {
+    game.send(WeaponEquipEvent {
+        kind,
+        family: kind.default_family(),
+    });
+    game.step();
+    game.wait_for_weapon_ready();
+}
+
+fn reticle_position(game: &mut TestApp) -> Vec3 {
+    game.world_mut()
+        .query_filtered::<&Transform, With<AttackReticle>>()
+        .single(game.world())
+        .unwrap()
+        .translation
+}
+
+/// 在準心右方 `distance` 處生成一隻耐打又不會移動的史萊姆
+fn spawn_dummy_ahead(game: &mut TestApp, distance: f32) -> Entity {
+    let reticle = reticle_position(game);
+    let slime = game.spawn_enemy("slime", Vec3::new(reticle.x + distance, reticle.y, 9.0));
+    let mut entity = game.world_mut().entity_mut(slime);
+    entity.get_mut::<Health>().unwrap().current = 10_000;
+    entity.insert(HitStun::new(60.0));
+    slime
+}
+
+fn damage_taken(game: &mut TestApp, enemy: Entity, starting_health: i32) -> i32 {
+    starting_health - game.component::<Health>(enemy).current
+}
+
+#[test]
+fn each_weapon_tier_drops_as_a_different_family() {
+    let families: Vec<WeaponFamily> = [
+        WeaponKind::Level1,
+        WeaponKind::Level2,
+        WeaponKind::Level3,
+        WeaponKind::Level4,
+        WeaponKind::Level5,
+    ]
+    .iter()
+    .map(WeaponKind::default_family)
+    .collect();
+
+    assert_eq!(
+        families,
+        vec![
+            WeaponFamily::Sword,
+            WeaponFamily::Dagger,
+            WeaponFamily::Spear,
+            WeaponFamily::Axe,
+            WeaponFamily::Hammer,
+        ]
+    );
+}
+
+#[test]
+fn spear_reaches_past_the_sword_and_ignores_defense() {
+    let distance = PLAYER_ATTACK_RADIUS * 1.3;
+
+    let mut game = TestApp::training_hall();
+    game.wait_for_weapon_ready();
+    let slime = spawn_dummy_ahead(&mut game, distance);
+    let starting_health = game.component::<Health>(slime).current;
+    game.attack();
+    assert_eq!(damage_taken(&mut game, slime, starting_health), 0);
+
+    let mut game = TestApp::training_hall();
+    equip_weapon(&mut game, WeaponKind::Level3);
+    let slime = spawn_dummy_ahead(&mut game, distance);
+    let starting_health = game.component::<Health>(slime).current;
+    let attack = game.player_component::<Attack>().value();
+    game.attack();
+
+    let swing = MeleeSwingSpec::combo_step(0, WeaponFamily::Spear);
+    assert_eq!(
+        damage_taken(&mut game, slime, starting_health),
+        compute_damage(swing.damage(attack), None)
+    );
+}
+
+#[test]
+fn axe_cleave_splashes_enemies_beside_the_target() {
+    let mut game = TestApp::training_hall();
+    equip_weapon(&mut game, WeaponKind::Level4);
+
+    let swing = MeleeSwingSpec::combo_step(0, WeaponFamily::Axe);
+    let WeaponSpecial::Cleave {
+        radius,
+        damage_multiplier,
+    } = swing.special
+    else {
+        panic!("axe should cleave");
+    };
+
+    let primary_distance = swing.radius * 0.9;
+    let primary = spawn_dummy_ahead(&mut game, primary_distance);
+    let beside = spawn_dummy_ahead(&mut game, primary_distance + radius * 0.8);
+    assert!(primary_distance + radius * 0.8 > swing.radius);
+
+    let starting_health = game.component::<Health>(beside).current;
+    let defense = game.component::<Defense>(beside).value();
+    let attack = game.player_component::<Attack>().value();
+    game.attack();
+
+    let splash = (swing.damage(attack) as f32 * damage_multiplier).round() as i32;
+    assert!(game.component::<Health>(primary).current < starting_health);
+    assert_eq!(
+        damage_taken(&mut game, beside, starting_health),
+        compute_damage(splash, Some(defense))
+    );
+}
+
+#[test]
+fn hammer_hits_stun_longer_than_usual() {
+    let mut game = TestApp::training_hall();
+    equip_weapon(&mut game, WeaponKind::Level5);
+    let slime = game.spawn_enemy_in_reach("slime");
+    game.world_mut().get_mut::<Health>(slime).unwrap().current = 10_000;
+
+    game.attack();
+    game.step();
+
+    let stun = game.component::<HitStun>(slime);
+    assert!(stun.timer.remaining_secs() > ENEMY_HIT_STUN_SECONDS);
+}
+
+#[test]
+fn family_is_equipped_independently_of_the_tier() {
+    let mut game = TestApp::training_hall();
+    let effect: PickupEffect =
+        serde_json::from_str(r#"{ "EquipWeapon": { "kind": "Level1", "family": "Hammer" } }"#)
+            .unwrap();
+    let position = game.player_position();
+    game.world_mut()
+        .spawn((Pickup::new(effect), Transform::from_translation(position)));
+    game.step();
+    game.wait_for_weapon_ready();
+
+    let weapon = game.player_component::<EquippedWeapon>();
+    assert_eq!(weapon.attack_bonus, WeaponKind::Level1.attack_bonus());
+    assert_eq!(weapon.family, WeaponFamily::Hammer);
+
+    let slime = game.spawn_enemy_in_reach("slime");
+    game.world_mut().get_mut::<Health>(slime).unwrap().current = 10_000;
+    game.attack();
+    game.step();
+
+    let stun = game.component::<HitStun>(slime);
+    assert!(stun.timer.remaining_secs() > ENEMY_HIT_STUN_SECONDS);
+}
+
+#[test]
+fn weapon_drop_without_family_uses_the_tier_default() {
+    let effect: PickupEffect =
+        serde_json::from_str(r#"{ "EquipWeapon": { "kind": "Level3" } }"#).unwrap();
+    assert_eq!(
+        effect,
+        PickupEffect::EquipWeapon {
+            kind: WeaponKind::Level3,
+            family: WeaponFamily::Spear,
+        }
+    );
+}
+
+#[test]
+fn dagger_swings_faster_and_cheaper_than_the_sword() {
+    let swing_cost = |kind: WeaponKind| {
+        let mut game = TestApp::training_hall();
+        equip_weapon(&mut game, kind);
+        game.attack();
+
+        let swing = game
+            .world_mut()
+            .query_filtered::<&WeaponSwing, With<Weapon>>()
+            .single(game.world())
+            .unwrap()
+            .timer
+            .duration()
+            .as_secs_f32();
+        let stamina = game.player_component::<Stamina>();
+        (swing, stamina.max - stamina.current)
+    };
+
+    let (sword_seconds, sword_stamina) = swing_cost(WeaponKind::Level1);
+    let (dagger_seconds, dagger_stamina) = swing_cost(WeaponKind::Level2);
+
+    assert!(dagger_seconds < sword_seconds);
+    assert!(dagger_stamina < sword_stamina);
+}
+
+#[test]
+fn spear_thrusts_instead_of_sweeping() {
+    let mut game = TestApp::training_hall();
+    equip_weapon(&mut game, WeaponKind::Level3);
+    game.attack();
+    game.step_frames(10);
+
+    let (style, offset_x) = game
+        .world_mut()
+        .query_filtered::<(&WeaponSwing, &Transform), With<Weapon>>()
+        .iter(game.world())
+        .map(|(swing, transform)| (swing.style, transform.translation.x))
+        .next()
+        .unwrap();
+
+    assert_eq!(style, WeaponSwingStyle::Thrust);
+    assert!(offset_x > WEAPON_IDLE_OFFSET_X);
+}
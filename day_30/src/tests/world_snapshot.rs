@@ -0,0 +1,247 @@
+use super::harness::{TestApp, test_level};
+use crate::components::level::LevelExitDoor;
+use crate::components::*;
+use crate::constants::CHEST_MIMIC_ARCHETYPE_ID;
+use crate::resources::*;
+use crate::systems::chest::ChestInteractionEvent;
+use crate::systems::game_session::{RequestLoadGameEvent, RequestSaveGameEvent};
+use bevy::prelude::*;
+
+fn save_and_reload(game: &mut TestApp, mutate: impl FnOnce(&mut TestApp)) -> LevelSnapshot {
+    game.send(RequestSaveGameEvent { slot: 0 });
+    game.step();
+    let SaveSlotState::Occupied(data) = game.resource::<SaveStorage>().read_slot(0) else {
+        panic!("save slot should hold a save");
+    };
+    let snapshot = data.world.expect("save should include a world snapshot");
+
+    mutate(game);
+
+    game.send(RequestLoadGameEvent {
+        slot: 0,
+        from_main_menu: false,
+    });
+    game.step();
+    game.wait_for_level();
+    game.step();
+    snapshot
+}
+
+fn chests(game: &mut TestApp) -> Vec<(Entity, Chest)> {
+    game.world_mut()
+        .query::<(Entity, &Chest)>()
+        .iter(game.world())
+        .map(|(entity, chest)| {
+            (
+                entity,
+                Chest {
+                    contents: chest.contents.clone(),
+                    state: chest.state,
+                },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn surviving_enemies_keep_their_health_after_loading() {
+    let mut game = TestApp::new(vec![test_level("Slime Den", &[("slime", 3)])]);
+    let slimes = game.enemies_with_archetype("slime");
+    assert_eq!(slimes.len(), 3);
+
+    game.world_mut()
+        .get_mut::<Health>(slimes[0])
+        .unwrap()
+        .current = 2;
+    game.world_mut().entity_mut(slimes[1]).despawn();
+
+    let snapshot = save_and_reload(&mut game, |_| {});
+    assert_eq!(snapshot.enemies.len(), 2);
+
+    let slimes = game.enemies_with_archetype("slime");
+    assert_eq!(slimes.len(), 2, "defeated slime should stay defeated");
+    let mut health: Vec<i32> = slimes
+        .iter()
+        .map(|slime| game.component::<Health>(*slime).current)
+        .collect();
+    health.sort();
+    let full_health = game
+        .resource::<EnemyArchetypeRegistry>()
+        .get("slime")
+        .unwrap()
+        .stats
+        .health;
+    assert_eq!(health, vec![2, full_health]);
+}
+
+#[test]
+fn enemy_status_effects_are_restored() {
+    let mut game = TestApp::new(vec![test_level("Slime Den", &[("slime", 1)])]);
+    let slime = game.enemies_with_archetype("slime")[0];
+    {
+        let mut effects = game.world_mut().get_mut::<StatusEffects>(slime).unwrap();
+        effects.apply(StatusEffectSpec::new(StatusEffectKind::Burn, 20.0, 2.0));
+        effects.apply(StatusEffectSpec::new(StatusEffectKind::Slow, 20.0, 0.5));
+    }
+
+    let snapshot = save_and_reload(&mut game, |_| {});
+    assert_eq!(snapshot.enemies[0].status_effects.len(), 2);
+
+    let slime = game.enemies_with_archetype("slime")[0];
+    let effects = game.component::<StatusEffects>(slime);
+    let burn = effects.get(StatusEffectKind::Burn).unwrap();
+    assert_eq!(burn.magnitude, 2.0);
+    assert!(burn.remaining_seconds() > 15.0);
+    assert!(effects.get(StatusEffectKind::Slow).is_some());
+}
+
+#[test]
+fn chest_states_and_awakened_mimics_are_restored() {
+    let mut game = TestApp::new(vec![test_level("Treasure Room", &[])]);
+    let all_chests = chests(&mut game);
+    let (mimic_chest, _) = all_chests
+        .iter()
+        .find(|(_, chest)| matches!(chest.contents, ChestContents::Mimic))
+        .expect("level should contain a mimic chest");
+    let (item_chest, _) = all_chests
+        .iter()
+        .find(|(_, chest)| matches!(chest.contents, ChestContents::Item(_)))
+        .expect("level should contain an item chest");
+
+    game.send(ChestInteractionEvent {
+        chest: *mimic_chest,
+    });
+    game.world_mut()
+        .get_mut::<Chest>(*item_chest)
+        .unwrap()
+        .state = ChestState::Empty;
+    game.step();
+
+    save_and_reload(&mut game, |_| {});
+
+    let restored = chests(&mut game);
+    assert_eq!(restored.len(), all_chests.len());
+    assert_eq!(
+        restored
+            .iter()
+            .filter(|(_, chest)| chest.state == ChestState::Empty)
+            .count(),
+        1
+    );
+
+    let (mimic, _) = restored
+        .iter()
+        .find(|(_, chest)| chest.state == ChestState::MimicAwakened)
+        .expect("awakened mimic should be restored");
+    assert!(game.world().get::<Enemy>(*mimic).is_some());
+    assert_eq!(
+        game.enemies_with_archetype(CHEST_MIMIC_ARCHETYPE_ID),
+        vec![*mimic]
+    );
+}
+
+#[test]
+fn player_state_doors_and_pickups_are_restored() {
+    let mut game = TestApp::new(vec![test_level("Quiet Hall", &[])]);
+    let player = game.player();
+
+    let position = game.player_position() + Vec3::new(24.0, 12.0, 0.0);
+    {
+        let world = game.world_mut();
+        world.get_mut::<Transform>(player).unwrap().translation = position;
+        world.get_mut::<Stamina>(player).unwrap().current = 10.0;
+        world
+            .get_mut::<StatusEffects>(player)
+            .unwrap()
+            .apply(StatusEffectSpec::new(StatusEffectKind::Poison, 20.0, 3.0));
+    }
+    let door = game
+        .world_mut()
+        .query_filtered::<Entity, With<Door>>()
+        .iter(game.world())
+        .next()
+        .expect("level should have a door");
+    let door_was_open = game.world().get::<Door>(door).unwrap().is_open;
+    game.world_mut().get_mut::<Door>(door).unwrap().is_open = !door_was_open;
+    let pickup_count = game
+        .world_mut()
+        .query::<&Pickup>()
+        .iter(game.world())
+        .count();
+
+    let snapshot = save_and_reload(&mut game, |game| {
+        let player = game.player();
+        game.world_mut()
+            .entity_mut(player)
+            .insert(StatusEffects::default());
+        game.world_mut()
+            .get_mut::<Stamina>(player)
+            .unwrap()
+            .refill();
+    });
+    assert_eq!(snapshot.pickups.len(), pickup_count);
+
+    let restored = game.player_position();
+    assert!(restored.truncate().distance(position.truncate()) < 1.0);
+    let poison = game.player_component::<StatusEffects>();
+    let poison = poison.get(StatusEffectKind::Poison).unwrap();
+    assert_eq!(poison.magnitude, 3.0);
+    assert!(poison.remaining_seconds() > 15.0);
+    let stamina = game.player_component::<Stamina>();
+    assert!(stamina.current < stamina.max);
+
+    let doors: Vec<bool> = game
+        .world_mut()
+        .query::<&Door>()
+        .iter(game.world())
+        .map(|door| door.is_open)
+        .collect();
+    assert!(doors.contains(&!door_was_open));
+    assert_eq!(
+        game.world_mut()
+            .query::<&Pickup>()
+            .iter(game.world())
+            .count(),
+        pickup_count
+    );
+}
+
+#[test]
+fn defeated_boss_rewards_survive_loading() {
+    let mut boss_level = test_level("Boss Arena", &[("wizard_boss", 1)]);
+    boss_level.boss_loot = vec![PickupEffect::EquipWeapon {
+        kind: WeaponKind::Level2,
+        family: WeaponFamily::Dagger,
+    }];
+    let mut game = TestApp::new(vec![boss_level, test_level("Aftermath", &[])]);
+
+    let boss = game.enemies_with_archetype("wizard_boss")[0];
+    game.world_mut().get_mut::<Health>(boss).unwrap().current = 0;
+    assert!(game.step_until(|world| world.resource::<PendingLevelRewards>().rewards_spawned));
+    game.step_frames(30);
+
+    save_and_reload(&mut game, |_| {});
+
+    assert!(game.enemies_with_archetype("wizard_boss").is_empty());
+    let rewards = game.resource::<PendingLevelRewards>();
+    assert!(rewards.rewards_spawned);
+    assert!(!rewards.rewards_available);
+
+    let portals = game
+        .world_mut()
+        .query::<&LevelExitDoor>()
+        .iter(game.world())
+        .count();
+    assert_eq!(portals, 1);
+    let loot_chests = chests(&mut game)
+        .into_iter()
+        .filter(|(_, chest)| {
+            chest.contents
+                == ChestContents::Item(PickupEffect::EquipWeapon {
+                    kind: WeaponKind::Level2,
+                    family: WeaponFamily::Dagger,
+                })
+        })
+        .count();
+    assert_eq!(loot_chests, 1);
+}